@@ -55,4 +55,262 @@ VALUES ('admin', '$argon2id$v=19$m=19456,t=2,p=1$pL1X6nNwXH/Qz777puvtwA$PfoXdJLS
 ON CONFLICT (username) DO NOTHING;
 
 -- 确保标准字段也有同义词索引
-CREATE INDEX IF NOT EXISTS idx_fields_associated_terms_trgm ON standard_fields USING GIN (associated_terms gin_trgm_ops);
\ No newline at end of file
+CREATE INDEX IF NOT EXISTS idx_fields_associated_terms_trgm ON standard_fields USING GIN (associated_terms gin_trgm_ops);
+-- 5. 命名规范配置表 (不同目标平台的英文名生成风格)
+CREATE TABLE IF NOT EXISTS naming_profiles (
+    id SERIAL PRIMARY KEY,
+    name VARCHAR(50) NOT NULL UNIQUE,           -- 规范名称 (如：default / oracle / hive)
+    case_style VARCHAR(20) NOT NULL DEFAULT 'snake', -- snake / upper_snake / camel / pascal
+    prefix VARCHAR(30),                         -- 统一前缀 (作为独立片段参与拼接)
+    suffix VARCHAR(30),                         -- 统一后缀
+    max_length INT,                             -- 标识符最大长度，NULL 表示不限制
+    is_default BOOLEAN NOT NULL DEFAULT FALSE,  -- 未指定规范时使用的默认规范
+    remark TEXT,
+    created_at TIMESTAMP WITH TIME ZONE DEFAULT CURRENT_TIMESTAMP
+);
+
+INSERT INTO naming_profiles (name, case_style, max_length, is_default, remark) VALUES
+    ('default', 'snake', NULL, TRUE, '小写下划线，与历史生成规则一致'),
+    ('oracle', 'upper_snake', 30, FALSE, 'Oracle 标识符最长 30 个字符'),
+    ('hive', 'snake', 128, FALSE, 'Hive 数仓层列名')
+ON CONFLICT (name) DO NOTHING;
+
+-- 最多一个默认规范
+CREATE UNIQUE INDEX IF NOT EXISTS uq_naming_profiles_default ON naming_profiles (is_default) WHERE is_default;
+
+-- 词根状态：active 可用于新字段 / deprecated 已废弃
+ALTER TABLE standard_word_roots ADD COLUMN IF NOT EXISTS status VARCHAR(20) NOT NULL DEFAULT 'active'
+    CHECK (status IN ('active', 'deprecated'));
+
+-- 类别词 (后缀词根)：字段须以类别词结尾，类别词决定推荐的数据类型
+ALTER TABLE standard_word_roots ADD COLUMN IF NOT EXISTS is_class_word BOOLEAN NOT NULL DEFAULT FALSE;
+ALTER TABLE standard_word_roots ADD COLUMN IF NOT EXISTS default_data_type VARCHAR(50);  -- 如：DECIMAL / DATE / VARCHAR
+ALTER TABLE standard_word_roots ADD COLUMN IF NOT EXISTS default_length INT;             -- 如：18
+ALTER TABLE standard_word_roots ADD COLUMN IF NOT EXISTS default_precision INT;          -- 小数位，如：2
+
+-- 6. 停用词与标点 (分词时直接丢弃，不参与词根匹配)
+CREATE TABLE IF NOT EXISTS stop_words (
+    id SERIAL PRIMARY KEY,
+    word VARCHAR(20) NOT NULL UNIQUE,
+    kind VARCHAR(20) NOT NULL DEFAULT 'stop' CHECK (kind IN ('stop', 'punct')), -- stop 停用词 / punct 标点
+    created_at TIMESTAMP WITH TIME ZONE DEFAULT CURRENT_TIMESTAMP
+);
+
+INSERT INTO stop_words (word, kind) VALUES
+    ('的', 'stop'), ('之', 'stop'), ('和', 'stop'), ('与', 'stop'), ('及', 'stop'), ('或', 'stop'),
+    ('（', 'punct'), ('）', 'punct'), ('(', 'punct'), (')', 'punct'), ('【', 'punct'), ('】', 'punct'),
+    ('[', 'punct'), (']', 'punct'), ('、', 'punct'), ('，', 'punct'), (',', 'punct'), ('。', 'punct'),
+    ('.', 'punct'), ('/', 'punct'), ('-', 'punct'), ('_', 'punct'), ('：', 'punct'), (':', 'punct'),
+    ('“', 'punct'), ('”', 'punct'), ('"', 'punct'), ('《', 'punct'), ('》', 'punct')
+ON CONFLICT (word) DO NOTHING;
+
+-- 数字片段的处理规则：separate 独立成段 / attach 并入前一段 / drop 丢弃
+ALTER TABLE naming_profiles ADD COLUMN IF NOT EXISTS number_style VARCHAR(20) NOT NULL DEFAULT 'separate'
+    CHECK (number_style IN ('separate', 'attach', 'drop'));
+
+-- 7. 拼音索引 (支持全拼与首字母检索，如 jine / jydm)
+ALTER TABLE standard_word_roots ADD COLUMN IF NOT EXISTS pinyin TEXT;           -- 中文名全拼
+ALTER TABLE standard_word_roots ADD COLUMN IF NOT EXISTS pinyin_initials TEXT;  -- 中文名首字母
+ALTER TABLE standard_word_roots ADD COLUMN IF NOT EXISTS terms_pinyin TEXT;     -- 同义词全拼及首字母
+ALTER TABLE standard_fields ADD COLUMN IF NOT EXISTS pinyin TEXT;
+ALTER TABLE standard_fields ADD COLUMN IF NOT EXISTS pinyin_initials TEXT;
+ALTER TABLE standard_fields ADD COLUMN IF NOT EXISTS terms_pinyin TEXT;
+
+CREATE INDEX IF NOT EXISTS idx_roots_pinyin_trgm ON standard_word_roots USING GIN (pinyin gin_trgm_ops);
+CREATE INDEX IF NOT EXISTS idx_roots_pinyin_initials_trgm ON standard_word_roots USING GIN (pinyin_initials gin_trgm_ops);
+CREATE INDEX IF NOT EXISTS idx_fields_pinyin_trgm ON standard_fields USING GIN (pinyin gin_trgm_ops);
+CREATE INDEX IF NOT EXISTS idx_fields_pinyin_initials_trgm ON standard_fields USING GIN (pinyin_initials gin_trgm_ops);
+
+-- 8. 结构化同义词 (associated_terms 保留为同义词表的缓存，由程序按本表重新生成)
+CREATE TABLE IF NOT EXISTS word_root_synonyms (
+    id SERIAL PRIMARY KEY,
+    root_id INT NOT NULL REFERENCES standard_word_roots(id) ON DELETE CASCADE,
+    term VARCHAR(100) NOT NULL,                 -- 同义词，允许包含空格
+    syn_type VARCHAR(20) NOT NULL DEFAULT 'exact'
+        CHECK (syn_type IN ('exact', 'near', 'abbreviation', 'misspelling')), -- 完全同义 / 近义 / 简称 / 错别字
+    weight INT NOT NULL DEFAULT 100 CHECK (weight BETWEEN 0 AND 100), -- 匹配优先级，0 表示仅记录不参与匹配
+    source VARCHAR(50) NOT NULL DEFAULT 'manual', -- 来源：manual / import / migrated / task 等
+    remark TEXT,
+    created_at TIMESTAMP WITH TIME ZONE DEFAULT CURRENT_TIMESTAMP,
+    UNIQUE (root_id, term)
+);
+
+CREATE INDEX IF NOT EXISTS idx_synonyms_term_lower ON word_root_synonyms (lower(term));
+
+-- 迁移历史数据：把空格分隔的 associated_terms 拆成同义词行
+INSERT INTO word_root_synonyms (root_id, term, syn_type, source)
+SELECT r.id, t.term, 'exact', 'migrated'
+FROM standard_word_roots r
+CROSS JOIN LATERAL regexp_split_to_table(r.associated_terms, '\s+') AS t(term)
+WHERE COALESCE(r.associated_terms, '') <> '' AND t.term <> ''
+ON CONFLICT (root_id, term) DO NOTHING;
+
+-- 9. 常用英文缩写词典 (生成词根缩写候选时优先采用)
+CREATE TABLE IF NOT EXISTS abbreviation_dictionary (
+    id SERIAL PRIMARY KEY,
+    full_word VARCHAR(100) NOT NULL UNIQUE,     -- 英文全称 (小写，可为词组，如：year to date)
+    abbr VARCHAR(30) NOT NULL,                  -- 约定缩写 (如：ytd)
+    remark TEXT,
+    created_at TIMESTAMP WITH TIME ZONE DEFAULT CURRENT_TIMESTAMP
+);
+
+INSERT INTO abbreviation_dictionary (full_word, abbr) VALUES
+    ('amount', 'amt'), ('number', 'no'), ('date', 'dt'), ('time', 'tm'), ('code', 'cd'), ('name', 'nm'),
+    ('flag', 'flg'), ('description', 'dscr'), ('quantity', 'qty'), ('customer', 'cust'), ('account', 'acct'),
+    ('transaction', 'txn'), ('address', 'addr'), ('telephone', 'tel'), ('identifier', 'id'), ('balance', 'bal'),
+    ('organization', 'org'), ('department', 'dept'), ('status', 'stat'), ('type', 'typ'), ('category', 'cat'),
+    ('percentage', 'pct'), ('price', 'prc'), ('rate', 'rt'), ('count', 'cnt'), ('total', 'tot'),
+    ('average', 'avg'), ('maximum', 'max'), ('minimum', 'min'), ('sequence', 'seq'), ('message', 'msg'),
+    ('currency', 'ccy'), ('product', 'prod'), ('contract', 'ctrt'), ('information', 'info'), ('reference', 'ref'),
+    ('indicator', 'ind'), ('level', 'lvl'), ('source', 'src'), ('target', 'tgt'), ('version', 'ver'),
+    ('begin', 'bgn'), ('start', 'strt'), ('create', 'crt'), ('update', 'upd'),
+    ('delete', 'del'), ('employee', 'emp'), ('manager', 'mgr'), ('business', 'biz'), ('channel', 'chnl'),
+    ('identification number', 'idno'), ('year to date', 'ytd')
+ON CONFLICT (full_word) DO NOTHING;
+
+-- 10. 操作审计日志 (词根合并等批量变更)
+CREATE TABLE IF NOT EXISTS audit_logs (
+    id SERIAL PRIMARY KEY,
+    actor_id INT,                               -- 操作人 (users.id)
+    action VARCHAR(50) NOT NULL,                -- 操作类型 (如：ROOT_MERGE)
+    target_type VARCHAR(50) NOT NULL,           -- 对象类型 (如：word_root)
+    target_id INT,                              -- 主对象 ID
+    detail JSONB,                               -- 变更明细
+    created_at TIMESTAMP WITH TIME ZONE DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX IF NOT EXISTS idx_audit_logs_target ON audit_logs (target_type, target_id);
+
+-- 11. 用户申请的处理流程：open 待处理 / in_progress 处理中 / approved 已采纳 / rejected 已驳回 / duplicate 重复申请
+ALTER TABLE notification_tasks ADD COLUMN IF NOT EXISTS status VARCHAR(20) NOT NULL DEFAULT 'open'
+    CHECK (status IN ('open', 'in_progress', 'approved', 'rejected', 'duplicate'));
+ALTER TABLE notification_tasks ADD COLUMN IF NOT EXISTS assignee_id INT REFERENCES users(id) ON DELETE SET NULL;
+ALTER TABLE notification_tasks ADD COLUMN IF NOT EXISTS resolution_note TEXT;
+-- 满足申请的词根 / 字段。不加外键：词根库与字段库支持 TRUNCATE 一键清空
+ALTER TABLE notification_tasks ADD COLUMN IF NOT EXISTS resolved_root_id INT;
+ALTER TABLE notification_tasks ADD COLUMN IF NOT EXISTS resolved_field_id INT;
+ALTER TABLE notification_tasks ADD COLUMN IF NOT EXISTS duplicate_of_id INT REFERENCES notification_tasks(id) ON DELETE SET NULL;
+ALTER TABLE notification_tasks ADD COLUMN IF NOT EXISTS resolved_by INT REFERENCES users(id) ON DELETE SET NULL;
+ALTER TABLE notification_tasks ADD COLUMN IF NOT EXISTS resolved_at TIMESTAMP WITH TIME ZONE;
+ALTER TABLE notification_tasks ADD COLUMN IF NOT EXISTS updated_at TIMESTAMP WITH TIME ZONE DEFAULT CURRENT_TIMESTAMP;
+
+-- 历史数据：已读即视为已处理
+UPDATE notification_tasks SET status = 'approved', resolution_note = '历史数据：已标记处理', resolved_at = created_at
+WHERE is_read = true AND status = 'open';
+
+CREATE INDEX IF NOT EXISTS idx_tasks_status ON notification_tasks (status, created_at DESC);
+CREATE INDEX IF NOT EXISTS idx_tasks_assignee ON notification_tasks (assignee_id);
+
+CREATE TABLE IF NOT EXISTS task_comments (
+    id SERIAL PRIMARY KEY,
+    task_id INT NOT NULL REFERENCES notification_tasks(id) ON DELETE CASCADE,
+    author_id INT REFERENCES users(id) ON DELETE SET NULL,
+    content TEXT NOT NULL,
+    created_at TIMESTAMP WITH TIME ZONE DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX IF NOT EXISTS idx_task_comments_task ON task_comments (task_id, created_at);
+
+-- 申请人 (匿名提交时为空)
+ALTER TABLE notification_tasks ADD COLUMN IF NOT EXISTS submitter_id INT REFERENCES users(id) ON DELETE SET NULL;
+CREATE INDEX IF NOT EXISTS idx_tasks_submitter ON notification_tasks (submitter_id, created_at DESC);
+
+-- 12. 用户站内通知 (申请处理结果等)
+CREATE TABLE IF NOT EXISTS user_notifications (
+    id SERIAL PRIMARY KEY,
+    user_id INT NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    kind VARCHAR(50) NOT NULL,                  -- TASK_RESOLVED 等
+    title VARCHAR(200) NOT NULL,
+    content TEXT,
+    task_id INT REFERENCES notification_tasks(id) ON DELETE SET NULL,
+    is_read BOOLEAN NOT NULL DEFAULT FALSE,
+    created_at TIMESTAMP WITH TIME ZONE DEFAULT CURRENT_TIMESTAMP
+);
+CREATE INDEX IF NOT EXISTS idx_user_notifications_user ON user_notifications (user_id, is_read, created_at DESC);
+
+-- 13. 标准字段状态 (支持通过废弃申请下线字段) 及申请类型
+ALTER TABLE standard_fields ADD COLUMN IF NOT EXISTS status VARCHAR(20) NOT NULL DEFAULT 'active'
+    CHECK (status IN ('active', 'deprecated'));
+-- task_type: FIELD_REQUEST 新增字段 / ROOT_REQUEST 新增词根 / FIELD_UPDATE 修改字段
+--            DEPRECATION_REQUEST 废弃词根或字段 / SYNONYM_SUGGESTION 建议同义词
+CREATE INDEX IF NOT EXISTS idx_tasks_type ON notification_tasks (task_type, status);
+
+-- 14. 相同申请合并：未结束的同类申请按 dedup_key 合并，其他用户的提交记为附议
+ALTER TABLE notification_tasks ADD COLUMN IF NOT EXISTS dedup_key TEXT;
+ALTER TABLE notification_tasks ADD COLUMN IF NOT EXISTS vote_count INT NOT NULL DEFAULT 1;
+CREATE INDEX IF NOT EXISTS idx_tasks_dedup ON notification_tasks (task_type, dedup_key) WHERE status IN ('open', 'in_progress');
+
+CREATE TABLE IF NOT EXISTS task_votes (
+    id SERIAL PRIMARY KEY,
+    task_id INT NOT NULL REFERENCES notification_tasks(id) ON DELETE CASCADE,
+    user_id INT REFERENCES users(id) ON DELETE CASCADE, -- 匿名附议为空
+    reason TEXT,
+    created_at TIMESTAMP WITH TIME ZONE DEFAULT CURRENT_TIMESTAMP,
+    UNIQUE (task_id, user_id)
+);
+
+-- 15. 外发 Webhook：订阅与投递记录
+CREATE TABLE IF NOT EXISTS webhook_subscriptions (
+    id SERIAL PRIMARY KEY,
+    name VARCHAR(100) NOT NULL,
+    url TEXT NOT NULL,
+    secret TEXT NOT NULL,                       -- HMAC-SHA256 签名密钥
+    events TEXT[] NOT NULL DEFAULT '{}',        -- 订阅的事件类型，空数组表示全部
+    is_active BOOLEAN NOT NULL DEFAULT TRUE,
+    created_by INT REFERENCES users(id) ON DELETE SET NULL,
+    created_at TIMESTAMP WITH TIME ZONE DEFAULT CURRENT_TIMESTAMP,
+    updated_at TIMESTAMP WITH TIME ZONE DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE TABLE IF NOT EXISTS webhook_deliveries (
+    id BIGSERIAL PRIMARY KEY,
+    subscription_id INT NOT NULL REFERENCES webhook_subscriptions(id) ON DELETE CASCADE,
+    event_type VARCHAR(50) NOT NULL,
+    payload JSONB NOT NULL,
+    status VARCHAR(20) NOT NULL DEFAULT 'pending'
+        CHECK (status IN ('pending', 'success', 'failed', 'dead')), -- failed 等待重试 / dead 重试耗尽
+    attempts INT NOT NULL DEFAULT 0,
+    next_attempt_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    last_status_code INT,
+    last_error TEXT,
+    replay_of BIGINT REFERENCES webhook_deliveries(id) ON DELETE SET NULL, -- 重放来源
+    delivered_at TIMESTAMP WITH TIME ZONE,
+    created_at TIMESTAMP WITH TIME ZONE DEFAULT CURRENT_TIMESTAMP
+);
+CREATE INDEX IF NOT EXISTS idx_webhook_deliveries_due ON webhook_deliveries (next_attempt_at) WHERE status IN ('pending', 'failed');
+CREATE INDEX IF NOT EXISTS idx_webhook_deliveries_sub ON webhook_deliveries (subscription_id, created_at DESC);
+
+-- 16. 申请统计与报表：按处理时间、提交时间汇总
+CREATE INDEX IF NOT EXISTS idx_tasks_resolved ON notification_tasks (resolved_at, resolved_by) WHERE resolved_at IS NOT NULL;
+CREATE INDEX IF NOT EXISTS idx_tasks_created ON notification_tasks (created_at DESC, id DESC);
+
+-- 17. 登录会话与刷新令牌：刷新令牌仅存 SHA-256 哈希，每次刷新轮换，旧令牌被再次使用时吊销整个会话
+ALTER TABLE users ADD COLUMN IF NOT EXISTS tokens_invalidated_at TIMESTAMP WITH TIME ZONE; -- 早于此时间签发的访问令牌失效
+
+CREATE TABLE IF NOT EXISTS auth_sessions (
+    id BIGSERIAL PRIMARY KEY,
+    user_id INT NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    user_agent TEXT,
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    last_used_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    revoked_at TIMESTAMP WITH TIME ZONE,
+    revoke_reason VARCHAR(20) -- logout / logout_all / reuse
+);
+CREATE INDEX IF NOT EXISTS idx_auth_sessions_user ON auth_sessions (user_id) WHERE revoked_at IS NULL;
+
+CREATE TABLE IF NOT EXISTS refresh_tokens (
+    id BIGSERIAL PRIMARY KEY,
+    session_id BIGINT NOT NULL REFERENCES auth_sessions(id) ON DELETE CASCADE,
+    token_hash CHAR(64) NOT NULL UNIQUE,
+    expires_at TIMESTAMP WITH TIME ZONE NOT NULL,
+    used_at TIMESTAMP WITH TIME ZONE, -- 已轮换；再次出现即视为泄露
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+CREATE INDEX IF NOT EXISTS idx_refresh_tokens_session ON refresh_tokens (session_id);
+
+-- 18. 事件流票据：EventSource 无法设置请求头，由已登录管理员换取一次性短期票据，避免访问令牌出现在 URL 与访问日志中
+CREATE TABLE IF NOT EXISTS event_stream_tickets (
+    ticket_hash CHAR(64) PRIMARY KEY,
+    session_id BIGINT NOT NULL REFERENCES auth_sessions(id) ON DELETE CASCADE,
+    expires_at TIMESTAMP WITH TIME ZONE NOT NULL
+);
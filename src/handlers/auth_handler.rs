@@ -1,8 +1,7 @@
-use axum::{extract::State, Json, http::StatusCode, response::IntoResponse};
+use axum::{extract::State, Extension, Json, http::{header, HeaderMap, StatusCode}, response::IntoResponse};
 use std::sync::Arc;
-use crate::{AppState, models::user::{User, Claims}};
+use crate::{AppState, models::user::{User, Claims}, services::{session_service::{self, RefreshError}, token_service}};
 use argon2::{Argon2, PasswordHash, PasswordVerifier, password_hash::{SaltString, PasswordHasher}};
-use jsonwebtoken::{encode, Header, EncodingKey};
 use serde::{Deserialize, Serialize};
 use chrono::Utc;
 use rand::rngs::OsRng;
@@ -16,10 +15,38 @@ pub struct AuthPayload {
 
 #[derive(Serialize)]
 pub struct AuthResponse {
-    pub token: String,
+    pub token: String,         // 访问令牌
+    pub refresh_token: String, // 一次性使用，刷新后返回新的刷新令牌
+    pub expires_in: i64,       // 访问令牌有效期 (秒)
     pub role: String,
 }
 
+#[derive(Deserialize)]
+pub struct RefreshPayload {
+    pub refresh_token: String,
+}
+
+/// 签发访问令牌并组装登录/刷新响应
+fn token_response(user_id: i32, role: String, session_id: i64, refresh_token: String) -> axum::response::Response {
+    let now = Utc::now();
+    let expires_in = session_service::access_ttl_secs();
+    let claims = Claims {
+        sub: user_id,
+        exp: (now + chrono::Duration::seconds(expires_in)).timestamp() as usize,
+        iat: now.timestamp() as usize,
+        iat_ms: now.timestamp_millis(),
+        sid: session_id,
+        role: role.clone(),
+    };
+    match token_service::issue(&claims) {
+        Ok(token) => (StatusCode::OK, Json(AuthResponse { token, refresh_token, expires_in, role })).into_response(),
+        Err(e) => {
+            tracing::error!("!!! 令牌签发失败: user_id={}, Error: {}", user_id, e);
+            (StatusCode::INTERNAL_SERVER_ERROR, "令牌签发失败").into_response()
+        }
+    }
+}
+
 #[derive(Deserialize)]
 pub struct AdminCreateUserPayload {
     pub username: String,
@@ -30,6 +57,7 @@ pub struct AdminCreateUserPayload {
 /// 用户登录
 pub async fn login(
     State(state): State<Arc<AppState>>,
+    headers: HeaderMap,
     Json(payload): Json<AuthPayload>,
 ) -> impl IntoResponse {
     tracing::info!(">>> 登录尝试: username={}", payload.username);
@@ -47,20 +75,17 @@ pub async fn login(
     if let Some(user) = user {
         if let Ok(parsed_hash) = PasswordHash::new(&user.password_hash) {
             if Argon2::default().verify_password(payload.password.as_bytes(), &parsed_hash).is_ok() {
-                let claims = Claims {
-                    sub: user.id,
-                    exp: (Utc::now() + chrono::Duration::hours(24)).timestamp() as usize,
-                    role: user.role.clone(),
+                let user_agent = headers.get(header::USER_AGENT).and_then(|h| h.to_str().ok());
+                let (session_id, refresh_token) = match session_service::create(&state.db, user.id, user_agent).await {
+                    Ok(s) => s,
+                    Err(e) => {
+                        tracing::error!("!!! 登录会话创建失败: username={}, Error: {}", user.username, e);
+                        return (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()).into_response();
+                    }
                 };
-                
-                let token = encode(
-                    &Header::default(), 
-                    &claims, 
-                    &EncodingKey::from_secret("secret_key".as_ref())
-                ).unwrap();
-
-                tracing::info!("<<< 登录成功: username={}, role={}, id={}", user.username, user.role, user.id);
-                return (StatusCode::OK, Json(AuthResponse { token, role: user.role })).into_response();
+
+                tracing::info!("<<< 登录成功: username={}, role={}, id={}, session={}", user.username, user.role, user.id, session_id);
+                return token_response(user.id, user.role, session_id, refresh_token);
             } else {
                 tracing::warn!("--- 登录失败: 用户[{}]密码校验未通过", payload.username);
             }
@@ -72,6 +97,70 @@ pub async fn login(
     (StatusCode::UNAUTHORIZED, "用户名或密码错误").into_response()
 }
 
+/// 刷新令牌：轮换刷新令牌并签发新的访问令牌，角色以当前数据库为准
+pub async fn refresh(
+    State(state): State<Arc<AppState>>,
+    Json(payload): Json<RefreshPayload>,
+) -> impl IntoResponse {
+    match session_service::rotate(&state.db, payload.refresh_token.trim()).await {
+        Ok(r) => token_response(r.user_id, r.role, r.session_id, r.refresh_token),
+        Err(RefreshError::Invalid(msg)) => (StatusCode::UNAUTHORIZED, msg).into_response(),
+        Err(RefreshError::Reused(session_id)) => {
+            tracing::warn!("--- 检测到刷新令牌重复使用，会话 ID={} 已吊销", session_id);
+            (StatusCode::UNAUTHORIZED, "刷新令牌已失效，请重新登录").into_response()
+        }
+        Err(RefreshError::Db(e)) => (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()).into_response(),
+    }
+}
+
+/// 退出当前会话
+pub async fn logout(State(state): State<Arc<AppState>>, Extension(claims): Extension<Claims>) -> impl IntoResponse {
+    match session_service::revoke(&state.db, claims.sub, claims.sid, "logout").await {
+        Ok(_) => {
+            tracing::info!("<<< 用户 ID={} 已退出会话 {}", claims.sub, claims.sid);
+            StatusCode::NO_CONTENT.into_response()
+        }
+        Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()).into_response(),
+    }
+}
+
+/// 退出全部会话 (所有设备)
+pub async fn logout_all(State(state): State<Arc<AppState>>, Extension(claims): Extension<Claims>) -> impl IntoResponse {
+    match session_service::revoke_all(&state.db, claims.sub, "logout_all").await {
+        Ok(count) => {
+            tracing::info!("<<< 用户 ID={} 已退出全部会话, 数量: {}", claims.sub, count);
+            StatusCode::NO_CONTENT.into_response()
+        }
+        Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()).into_response(),
+    }
+}
+
+/// 当前用户的有效会话
+pub async fn list_sessions(State(state): State<Arc<AppState>>, Extension(claims): Extension<Claims>) -> impl IntoResponse {
+    match session_service::list(&state.db, claims.sub).await {
+        Ok(list) => Json(list).into_response(),
+        Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()).into_response(),
+    }
+}
+
+/// 注销指定会话 (如其他设备)
+pub async fn revoke_session(
+    State(state): State<Arc<AppState>>,
+    Extension(claims): Extension<Claims>,
+    Path(id): Path<i64>,
+) -> impl IntoResponse {
+    match session_service::revoke(&state.db, claims.sub, id, "logout").await {
+        Ok(true) => StatusCode::NO_CONTENT.into_response(),
+        Ok(false) => StatusCode::NOT_FOUND.into_response(),
+        Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()).into_response(),
+    }
+}
+
+/// JWKS：供其他服务验证本系统签发的令牌 (仅 RS256 / EdDSA 公钥)
+pub async fn jwks() -> impl IntoResponse {
+    ([(header::CACHE_CONTROL, "public, max-age=300")], Json(token_service::jwks()))
+}
+
 /// 用户注册 (自主注册)
 pub async fn signup(
     State(state): State<Arc<AppState>>,
@@ -172,8 +261,9 @@ pub async fn update_user_role(
     let role = payload["role"].as_str().unwrap_or("user");
     tracing::info!(">>> 正在变更用户角色: ID={}, 新角色={}", id, role);
     
+    // 角色变更后已签发的访问令牌立即失效，客户端刷新后获得新角色
     let result = sqlx::query!(
-        "UPDATE users SET role = $1 WHERE id = $2",
+        "UPDATE users SET role = $1, tokens_invalidated_at = CURRENT_TIMESTAMP WHERE id = $2",
         role, id
     )
     .execute(&state.db)
@@ -203,6 +293,8 @@ pub async fn delete_user(
 ) -> impl IntoResponse {
     tracing::warn!(">>> 正在删除用户账号: ID={}", id);
 
+    // 会话与刷新令牌随用户级联删除，守卫校验时用户不存在即拒绝
+
     let result = sqlx::query!("DELETE FROM users WHERE id = $1", id)
         .execute(&state.db)
         .await;
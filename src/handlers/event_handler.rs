@@ -0,0 +1,105 @@
+use axum::{
+    extract::State,
+    http::StatusCode,
+    response::sse::{Event, KeepAlive, Sse},
+    response::IntoResponse,
+    Extension, Json,
+};
+use std::convert::Infallible;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio_stream::wrappers::{errors::BroadcastStreamRecvError, BroadcastStream, IntervalStream};
+use tokio_stream::{Stream, StreamExt};
+
+use crate::middleware::auth::StreamSession;
+use crate::models::user::Claims;
+use crate::services::event_bus::AppEvent;
+use crate::services::session_service;
+use crate::AppState;
+
+// 连接期间校验会话状态的间隔：注销、吊销全部会话或取消管理员角色后最迟在该间隔内断开
+const SESSION_CHECK_INTERVAL: Duration = Duration::from_secs(15);
+
+fn to_sse(event: &AppEvent) -> Event {
+    Event::default()
+        .event(event.kind.clone())
+        .json_data(&event.data)
+        .unwrap_or_else(|_| Event::default().event(event.kind.clone()))
+}
+
+/// 签发一次性事件流票据：前端以 /api/admin/events?ticket= 建立 EventSource 连接
+pub async fn issue_ticket(
+    State(state): State<Arc<AppState>>,
+    Extension(claims): Extension<Claims>,
+) -> impl IntoResponse {
+    match session_service::issue_stream_ticket(&state.db, claims.sid).await {
+        Ok((ticket, expires_in)) => Json(serde_json::json!({ "ticket": ticket, "expires_in": expires_in })).into_response(),
+        Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, format!("票据签发失败: {}", e)).into_response(),
+    }
+}
+
+/// 事件流内部项：业务事件，或会话失效后的结束标记
+enum StreamItem {
+    Event(Event),
+    Closed,
+}
+
+/// 管理端事件流 (SSE)：任务新建 / 更新 / 处理完成及词典变更
+/// 连接建立时先推送一次当前未结束任务数；订阅端处理过慢丢失事件时推送 resync，前端应重新拉取
+/// 连接期间定期校验所属会话，会话失效后结束事件流
+pub async fn stream_events(
+    State(state): State<Arc<AppState>>,
+    Extension(session): Extension<StreamSession>,
+) -> Sse<impl Stream<Item = Result<Event, Infallible>>> {
+    let receiver = state.events.subscribe();
+    let open_count = sqlx::query_scalar!(
+        r#"SELECT count(*) AS "count!" FROM notification_tasks WHERE status IN ('open', 'in_progress')"#
+    )
+    .fetch_one(&state.db)
+    .await
+    .unwrap_or_default();
+    tracing::info!(">>> 管理端订阅事件流，当前订阅数={}", state.events.receiver_count());
+
+    let hello = AppEvent { kind: "task_count".to_string(), data: serde_json::json!({ "open_count": open_count }) };
+    let initial = tokio_stream::once(to_sse(&hello));
+    let events = BroadcastStream::new(receiver).map(|item| match item {
+        Ok(event) => to_sse(&event),
+        Err(BroadcastStreamRecvError::Lagged(n)) => {
+            tracing::warn!("--- 事件流订阅端落后，丢弃 {} 条事件", n);
+            to_sse(&AppEvent { kind: "resync".to_string(), data: serde_json::Value::Null })
+        }
+    });
+
+    // 定期校验会话，失效时输出结束标记；校验异常时保持连接
+    let pool = state.db.clone();
+    let start = tokio::time::Instant::now() + SESSION_CHECK_INTERVAL;
+    let closed = IntervalStream::new(tokio::time::interval_at(start, SESSION_CHECK_INTERVAL))
+        .then(move |_| {
+            let pool = pool.clone();
+            async move {
+                match session_service::is_admin_session(&pool, session.session_id).await {
+                    Ok(active) => active,
+                    Err(e) => {
+                        tracing::warn!("--- 事件流会话校验失败: {}", e);
+                        true
+                    }
+                }
+            }
+        })
+        .filter(|active| !active)
+        .map(|_| StreamItem::Closed);
+
+    let stream = initial
+        .chain(events)
+        .map(StreamItem::Event)
+        .merge(closed)
+        .map_while(move |item| match item {
+            StreamItem::Event(event) => Some(Ok(event)),
+            StreamItem::Closed => {
+                tracing::info!("<<< 会话 ID={} 已失效，关闭事件流", session.session_id);
+                None
+            }
+        });
+
+    Sse::new(stream).keep_alive(KeepAlive::new().interval(Duration::from_secs(15)))
+}
@@ -1,38 +1,150 @@
-use axum::{extract::{State, Path, Query}, Json, http::StatusCode, response::IntoResponse};
+use axum::{extract::{State, Path, Query}, Json, http::StatusCode, response::{IntoResponse, Response}};
+use serde::{Deserialize, Serialize};
 use std::sync::Arc;
 use crate::AppState;
 use crate::models::field::{CreateFieldRequest, StandardField};
 use crate::models::word_root::WordRoot;
 use crate::handlers::mapping_handler::SuggestQuery; 
+use crate::services::compliance_service::{self, CheckMode, ComplianceReport};
+use crate::services::{dictionary_service, mapping_service, naming_service, pinyin_service, webhook_service};
 use qdrant_client::qdrant::SearchPointsBuilder;
 use qdrant_client::qdrant::point_id::PointIdOptions;
 use qdrant_client::qdrant::{DeletePointsBuilder, Filter};
 
-/// 1. 创建标准字段
-pub async fn create_field(
-    State(state): State<Arc<AppState>>,
-    Json(payload): Json<CreateFieldRequest>,
-) -> impl IntoResponse {
-    tracing::info!(">>> 开始创建标准字段: cn_name={}, en_name={}", payload.field_cn_name, payload.field_en_name);
+// 字段写入时的命名校验参数
+#[derive(Deserialize)]
+pub struct FieldWriteQuery {
+    pub check: Option<String>,   // off / warn / enforce，缺省读取 FIELD_NAME_CHECK_MODE
+    pub profile: Option<String>, // 命名规范名称
+}
 
-    let result = sqlx::query_as!(
+// 创建字段的响应：字段本身 + warn 模式下的合规报告
+#[derive(Serialize)]
+pub struct FieldWriteResponse {
+    #[serde(flatten)]
+    pub field: StandardField,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub compliance: Option<ComplianceReport>,
+}
+
+/// 辅助函数：按模式执行命名合规检查，enforce 模式下不合规直接返回 422
+pub(crate) async fn run_name_check(
+    state: &AppState,
+    payload: &CreateFieldRequest,
+    query: &FieldWriteQuery,
+    exclude_id: Option<i32>,
+) -> Result<Option<ComplianceReport>, Response> {
+    let mode = CheckMode::resolve(query.check.as_deref())
+        .map_err(|msg| (StatusCode::BAD_REQUEST, msg).into_response())?;
+    if mode == CheckMode::Off {
+        return Ok(None);
+    }
+
+    let profile = naming_service::resolve_profile(&state.db, query.profile.as_deref())
+        .await
+        .map_err(|msg| (StatusCode::BAD_REQUEST, msg).into_response())?;
+
+    let report = compliance_service::check_field_name(
+        &state.db, &payload.field_cn_name, &payload.field_en_name, &profile, exclude_id,
+    )
+    .await
+    .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, format!("命名检查异常: {}", e)).into_response())?;
+
+    if !report.passed {
+        if mode == CheckMode::Enforce {
+            tracing::warn!("--- 字段命名不合规，已拒绝: en_name={}", payload.field_en_name);
+            return Err((StatusCode::UNPROCESSABLE_ENTITY, Json(report)).into_response());
+        }
+        tracing::warn!("--- 字段命名不合规 (warn 模式放行): en_name={}", payload.field_en_name);
+    }
+    Ok(Some(report))
+}
+
+/// 辅助函数：未指定数据类型时，按末尾类别词推导
+pub(crate) async fn resolve_data_type(state: &AppState, payload: &CreateFieldRequest) -> Option<String> {
+    match payload.data_type.clone() {
+        Some(t) if !t.trim().is_empty() => Some(t),
+        _ => mapping_service::infer_data_type(&state.db, &payload.composition_ids).await,
+    }
+}
+
+/// 辅助函数：写入标准字段 (可在事务中调用)，派生数据由调用方刷新
+pub(crate) async fn insert_field<'e, E: sqlx::PgExecutor<'e>>(
+    db: E,
+    payload: &CreateFieldRequest,
+    data_type: Option<String>,
+) -> Result<StandardField, sqlx::Error> {
+    sqlx::query_as!(
         StandardField,
         r#"
-        INSERT INTO standard_fields (field_cn_name, field_en_name, composition_ids, data_type, associated_terms)
-        VALUES ($1, $2, $3::INT[], $4, $5)
+        INSERT INTO standard_fields (field_cn_name, field_en_name, composition_ids, data_type, associated_terms,
+                                     pinyin, pinyin_initials, terms_pinyin)
+        VALUES ($1, $2, $3::INT[], $4, $5, $6, $7, $8)
         RETURNING id, field_cn_name, field_en_name, composition_ids as "composition_ids!", 
-                  data_type, associated_terms, is_standard as "is_standard!", created_at
+                  data_type, associated_terms, is_standard as "is_standard!", status, created_at
         "#,
         payload.field_cn_name, payload.field_en_name, &payload.composition_ids, 
-        payload.data_type, payload.associated_terms
+        data_type, payload.associated_terms,
+        pinyin_service::full(&payload.field_cn_name), pinyin_service::initials(&payload.field_cn_name),
+        pinyin_service::terms_index(payload.associated_terms.as_deref())
     )
-    .fetch_one(&state.db)
-    .await;
+    .fetch_one(db)
+    .await
+}
+
+/// 辅助函数：按请求体整体更新标准字段 (可在事务中调用)，返回受影响行数
+pub(crate) async fn update_field_row<'e, E: sqlx::PgExecutor<'e>>(
+    db: E,
+    id: i32,
+    payload: &CreateFieldRequest,
+) -> Result<u64, sqlx::Error> {
+    sqlx::query!(
+        r#"UPDATE standard_fields SET field_cn_name=$1, field_en_name=$2, composition_ids=$3::INT[], 
+           data_type=$4, associated_terms=$5, pinyin=$6, pinyin_initials=$7, terms_pinyin=$8 WHERE id=$9"#,
+        payload.field_cn_name, payload.field_en_name, &payload.composition_ids, 
+        payload.data_type, payload.associated_terms,
+        pinyin_service::full(&payload.field_cn_name), pinyin_service::initials(&payload.field_cn_name),
+        pinyin_service::terms_index(payload.associated_terms.as_deref()), id
+    )
+    .execute(db)
+    .await
+    .map(|r| r.rows_affected())
+}
+
+/// 辅助函数：字段更新事件的数据
+pub(crate) fn field_event(id: i32, payload: &CreateFieldRequest) -> serde_json::Value {
+    serde_json::json!({
+        "id": id,
+        "field_cn_name": payload.field_cn_name,
+        "field_en_name": payload.field_en_name,
+        "composition_ids": payload.composition_ids,
+        "data_type": payload.data_type,
+        "associated_terms": payload.associated_terms,
+    })
+}
+
+/// 1. 创建标准字段
+pub async fn create_field(
+    State(state): State<Arc<AppState>>,
+    Query(query): Query<FieldWriteQuery>,
+    Json(payload): Json<CreateFieldRequest>,
+) -> impl IntoResponse {
+    tracing::info!(">>> 开始创建标准字段: cn_name={}, en_name={}", payload.field_cn_name, payload.field_en_name);
+
+    let compliance = match run_name_check(&state, &payload, &query, None).await {
+        Ok(report) => report,
+        Err(resp) => return resp,
+    };
+
+    let data_type = resolve_data_type(&state, &payload).await;
+    let result = insert_field(&state.db, &payload, data_type).await;
 
     match result {
         Ok(field) => {
             tracing::info!("<<< 标准字段创建成功: ID={}, en_name={}", field.id, field.field_en_name);
-            (StatusCode::CREATED, Json(field)).into_response()
+            dictionary_service::on_fields_changed(&state.db).await;
+            webhook_service::emit(&state.db, "field.created", &field).await;
+            (StatusCode::CREATED, Json(FieldWriteResponse { field, compliance })).into_response()
         },
         Err(e) => {
             tracing::error!("!!! 标准字段创建失败: [{}], Error: {}", payload.field_cn_name, e);
@@ -47,7 +159,7 @@ pub async fn list_fields(State(state): State<Arc<AppState>>) -> impl IntoRespons
         StandardField,
         r#"
         SELECT id, field_cn_name, field_en_name, composition_ids as "composition_ids!", 
-               data_type, associated_terms, is_standard as "is_standard!", created_at
+               data_type, associated_terms, is_standard as "is_standard!", status, created_at
         FROM standard_fields ORDER BY created_at DESC
         "#
     ).fetch_all(&state.db).await;
@@ -85,7 +197,9 @@ pub async fn get_field_details(
                 r#"
                 SELECT 
                     r.id, r.cn_name, r.en_abbr, r.en_full_name, 
-                    r.associated_terms, r.remark, r.created_at
+                    r.associated_terms, r.remark, r.status, r.is_class_word,
+                    r.default_data_type, r.default_length, r.default_precision,
+                    r.pinyin, r.pinyin_initials, r.terms_pinyin, r.created_at
                 FROM UNNEST($1::INT[]) WITH ORDINALITY AS x(id, ord)
                 JOIN standard_word_roots r ON r.id = x.id
                 ORDER BY x.ord
@@ -115,17 +229,27 @@ pub async fn get_field_details(
 pub async fn update_field(
     State(state): State<Arc<AppState>>,
     Path(id): Path<i32>,
+    Query(query): Query<FieldWriteQuery>,
     Json(payload): Json<CreateFieldRequest>,
 ) -> impl IntoResponse {
-    let res = sqlx::query!(
-        r#"UPDATE standard_fields SET field_cn_name=$1, field_en_name=$2, composition_ids=$3::INT[], 
-           data_type=$4, associated_terms=$5 WHERE id=$6"#,
-        payload.field_cn_name, payload.field_en_name, &payload.composition_ids, 
-        payload.data_type, payload.associated_terms, id
-    ).execute(&state.db).await;
+    let compliance = match run_name_check(&state, &payload, &query, Some(id)).await {
+        Ok(report) => report,
+        Err(resp) => return resp,
+    };
+
+    let res = update_field_row(&state.db, id, &payload).await;
 
+    if let Ok(n) = res {
+        dictionary_service::on_fields_changed(&state.db).await;
+        if n > 0 {
+            webhook_service::emit(&state.db, "field.updated", &field_event(id, &payload)).await;
+        }
+    }
     match res {
-        Ok(_) => StatusCode::OK.into_response(),
+        Ok(_) => match compliance {
+            Some(report) => (StatusCode::OK, Json(report)).into_response(),
+            None => StatusCode::OK.into_response(),
+        },
         Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()).into_response(),
     }
 }
@@ -133,66 +257,100 @@ pub async fn update_field(
 /// 5. 删除标准字段
 pub async fn delete_field(State(state): State<Arc<AppState>>, Path(id): Path<i32>) -> impl IntoResponse {
     match sqlx::query!("DELETE FROM standard_fields WHERE id = $1", id).execute(&state.db).await {
-        Ok(_) => StatusCode::NO_CONTENT.into_response(),
+        Ok(r) => {
+            dictionary_service::on_fields_changed(&state.db).await;
+            if r.rows_affected() > 0 {
+                webhook_service::emit(&state.db, "field.deleted", &serde_json::json!({ "id": id })).await;
+            }
+            StatusCode::NO_CONTENT.into_response()
+        }
         Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()).into_response(),
     }
 }
 
-/// 6. 用户端搜索接口
-pub async fn search_field(
-    State(state): State<Arc<AppState>>, 
-    Query(query): Query<SuggestQuery>
-) -> impl IntoResponse {
-    // 路径 A: SQL 模糊匹配
-    let q_pattern = format!("%{}%", query.q);
-    let sql_results = sqlx::query_as!(
+/// 向量检索命中的标准字段
+#[derive(Serialize)]
+pub struct FieldVectorHit {
+    pub id: serde_json::Value,
+    pub field_cn_name: Option<String>,
+    pub field_en_name: Option<String>,
+    pub score: f32,
+}
+
+/// 辅助函数：SQL 模糊匹配 (含拼音全拼/首字母)，中文命中排在拼音命中之前
+pub(crate) async fn search_fields_by_text(state: &AppState, q: &str) -> Vec<StandardField> {
+    let q_pattern = format!("%{}%", q);
+    let py_prefix = format!("{}%", q.trim().to_lowercase());
+    sqlx::query_as!(
         StandardField,
         r#"SELECT id, field_cn_name, field_en_name, composition_ids as "composition_ids!", 
-                  data_type, associated_terms, is_standard as "is_standard!", created_at
+                  data_type, associated_terms, is_standard as "is_standard!", status, created_at
            FROM standard_fields 
            WHERE field_cn_name ILIKE $1 OR associated_terms ILIKE $1 
+              OR pinyin LIKE $2 OR pinyin_initials LIKE $2 OR terms_pinyin LIKE $3
+           ORDER BY (status = 'active') DESC,
+                    CASE WHEN field_cn_name = $4 THEN 0
+                         WHEN field_cn_name ILIKE $1 OR associated_terms ILIKE $1 THEN 1
+                         ELSE 2 END, id
            LIMIT 10"#,
-        q_pattern
-    ).fetch_all(&state.db).await.unwrap_or_default();
-
-    if !sql_results.is_empty() {
-        return Json(sql_results).into_response();
-    }
+        q_pattern, py_prefix, format!("%{}%", q.trim().to_lowercase()), q.trim()
+    ).fetch_all(&state.db).await.unwrap_or_default()
+}
 
-    // 路径 B: 向量语义搜索
+/// 辅助函数：向量语义搜索，向量化或检索失败时返回 None
+pub(crate) async fn search_fields_by_vector(
+    state: &AppState,
+    q: &str,
+    limit: u64,
+    score_threshold: Option<f32>,
+) -> Option<Vec<FieldVectorHit>> {
     // 修复：parking_lot::Mutex 锁在 block 结束时自动释放，不阻塞异步 await
     let query_vector_res = {
         let mut model = state.embed_model.lock();
-        model.embed(vec![&query.q], None)
+        model.embed(vec![q], None)
     };
+    let query_vector = query_vector_res.ok()?.into_iter().next()?;
+
+    let mut builder = SearchPointsBuilder::new("standard_fields", query_vector, limit).with_payload(true);
+    if let Some(threshold) = score_threshold {
+        builder = builder.score_threshold(threshold);
+    }
+    let res = state.qdrant.search_points(builder).await.ok()?;
+
+    Some(res.result.into_iter().map(|p| {
+        let pay = p.payload;
+        let id_json = match p.id {
+            Some(pid) => match pid.point_id_options {
+                Some(PointIdOptions::Num(n)) => serde_json::json!(n),
+                Some(PointIdOptions::Uuid(u)) => serde_json::json!(u),
+                None => serde_json::json!(null),
+            },
+            None => serde_json::json!(null),
+        };
 
-    if let Ok(embeddings) = query_vector_res {
-        let query_vector = embeddings[0].clone();
-        let search_res = state.qdrant.search_points(
-            SearchPointsBuilder::new("standard_fields", query_vector, 5).with_payload(true)
-        ).await;
-
-        if let Ok(res) = search_res {
-            let fields: Vec<serde_json::Value> = res.result.into_iter().map(|p| {
-                let pay = p.payload;
-                let id_json = match p.id {
-                    Some(pid) => match pid.point_id_options {
-                        Some(PointIdOptions::Num(n)) => serde_json::json!(n),
-                        Some(PointIdOptions::Uuid(u)) => serde_json::json!(u),
-                        None => serde_json::json!(null),
-                    },
-                    None => serde_json::json!(null),
-                };
-
-                serde_json::json!({
-                    "id": id_json,
-                    "field_cn_name": pay.get("cn_name").and_then(|v| v.as_str()),
-                    "field_en_name": pay.get("en_name").and_then(|v| v.as_str()),
-                    "score": p.score
-                })
-            }).collect();
-            return (StatusCode::OK, Json(fields)).into_response();
+        FieldVectorHit {
+            id: id_json,
+            field_cn_name: pay.get("cn_name").and_then(|v| v.as_str()).map(|v| v.to_string()),
+            field_en_name: pay.get("en_name").and_then(|v| v.as_str()).map(|v| v.to_string()),
+            score: p.score,
         }
+    }).collect())
+}
+
+/// 6. 用户端搜索接口
+pub async fn search_field(
+    State(state): State<Arc<AppState>>, 
+    Query(query): Query<SuggestQuery>
+) -> impl IntoResponse {
+    // 路径 A: SQL 模糊匹配
+    let sql_results = search_fields_by_text(&state, &query.q).await;
+    if !sql_results.is_empty() {
+        return Json(sql_results).into_response();
+    }
+
+    // 路径 B: 向量语义搜索
+    if let Some(fields) = search_fields_by_vector(&state, &query.q, 5, None).await {
+        return (StatusCode::OK, Json(fields)).into_response();
     }
 
     Json(Vec::<StandardField>::new()).into_response()
@@ -208,6 +366,7 @@ pub async fn clear_all_fields(
 
     match db_res {
         Ok(_) => {
+            dictionary_service::on_fields_changed(&state.db).await;
             let q_res = state.qdrant.delete_points(
                 DeletePointsBuilder::new("standard_fields")
                     .points(Filter::default()) 
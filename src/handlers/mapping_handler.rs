@@ -8,19 +8,26 @@ use qdrant_client::qdrant::{point_id::PointIdOptions, SearchPointsBuilder};
 use serde::{Deserialize, Serialize};
 use std::sync::Arc;
 
-use crate::services::mapping_service;
+use crate::services::{mapping_service, naming_service, reverse_service};
 use crate::AppState;
 
 #[derive(Deserialize)]
 pub struct SuggestQuery {
     pub q: String,
+    pub profile: Option<String>, // 命名规范名称，缺省使用默认规范
 }
 
 #[derive(Serialize)]
 pub struct SuggestResponse {
+    pub normalized_input: String,
     pub suggested_en: String,
     pub missing_words: Vec<String>,
     pub matched_ids: Vec<i32>,
+    pub profile: String,
+    pub shortened: bool,
+    pub recommended_data_type: Option<String>,
+    pub warnings: Vec<String>,
+    pub ignored_tokens: Vec<String>,
 }
 
 #[derive(Serialize)]
@@ -32,7 +39,7 @@ pub struct RootSuggestion {
 }
 
 /// 1. 分词建议接口 (管理员生产标准字段的核心工具)
-/// 逻辑：将中文输入利用 JIEBA 切分，并匹配标准词根库（含同义词匹配）
+///    逻辑：将中文输入利用 JIEBA 切分，并匹配标准词根库（含同义词匹配）
 pub async fn suggest_mapping(
     State(state): State<Arc<AppState>>,
     Query(query): Query<SuggestQuery>,
@@ -43,26 +50,37 @@ pub async fn suggest_mapping(
         return (StatusCode::BAD_REQUEST, "查询内容不能为空").into_response();
     }
 
-    tracing::info!(">>> 正在为管理员生成分词建议: q='{}'", input);
+    tracing::info!(">>> 正在为管理员生成分词建议: q='{}', profile={:?}", input, query.profile);
+
+    let profile = match naming_service::resolve_profile(&state.db, query.profile.as_deref()).await {
+        Ok(p) => p,
+        Err(msg) => return (StatusCode::BAD_REQUEST, msg).into_response(),
+    };
 
     // 调用 Service 层逻辑
-    let (suggested_en, missing_words, matched_ids) =
-        mapping_service::suggest_field_name(&state.db, input).await;
+    let suggestion = mapping_service::suggest_field_name(&state.db, input, &profile).await;
 
-    if !missing_words.is_empty() {
-        tracing::warn!("--- 词汇未完全标准化: 缺失词汇={:?}", missing_words);
+    if !suggestion.missing_words.is_empty() {
+        tracing::warn!("--- 词汇未完全标准化: 缺失词汇={:?}", suggestion.missing_words);
     }
 
     tracing::info!(
-        "<<< 建议生成成功: en_abbr={}, matched_count={}",
-        suggested_en,
-        matched_ids.len()
+        "<<< 建议生成成功: en_abbr={}, matched_count={}, shortened={}",
+        suggestion.suggested_en,
+        suggestion.matched_ids.len(),
+        suggestion.shortened
     );
 
     Json(SuggestResponse {
-        suggested_en,
-        missing_words,
-        matched_ids,
+        normalized_input: suggestion.normalized_input,
+        suggested_en: suggestion.suggested_en,
+        missing_words: suggestion.missing_words,
+        matched_ids: suggestion.matched_ids,
+        profile: profile.name,
+        shortened: suggestion.shortened,
+        recommended_data_type: suggestion.data_type,
+        warnings: suggestion.warnings,
+        ignored_tokens: suggestion.ignored_tokens,
     })
     .into_response()
 }
@@ -153,4 +171,54 @@ pub async fn search_similar_roots(
             (StatusCode::INTERNAL_SERVER_ERROR, format!("向量计算失败: {}", e)).into_response()
         },
     }
-}
\ No newline at end of file
+}
+
+/// 3. 英文列名反向翻译 (遗留表无注释时还原中文名)
+///    逻辑：按分隔符、驼峰及贪婪最长匹配切分，再通过 en_abbr / en_full_name 映射回标准词根
+pub async fn reverse_translate(
+    State(state): State<Arc<AppState>>,
+    Query(query): Query<SuggestQuery>,
+) -> impl IntoResponse {
+    let input = query.q.trim();
+    if input.is_empty() {
+        return (StatusCode::BAD_REQUEST, "查询内容不能为空").into_response();
+    }
+
+    tracing::info!(">>> 正在反向翻译英文名: q='{}'", input);
+
+    match reverse_service::reverse_translate(&state.db, input).await {
+        Ok(result) => {
+            if !result.unknown_pieces.is_empty() {
+                tracing::warn!("--- 存在无法识别的片段: {:?}", result.unknown_pieces);
+            }
+            tracing::info!("<<< 反向翻译完成: cn_name={}", result.cn_name);
+            (StatusCode::OK, Json(result)).into_response()
+        }
+        Err(e) => {
+            tracing::error!("!!! 反向翻译失败: {}", e);
+            (StatusCode::INTERNAL_SERVER_ERROR, format!("查询异常: {}", e)).into_response()
+        }
+    }
+}
+
+/// 4. 分词调试 (排查建议结果异常)
+///    展示 cut (含/不含 HMM)、cut_all、cut_for_search 的结果、每个 token 的词根匹配途径，以及自定义词条的影响
+pub async fn debug_segmentation(
+    State(state): State<Arc<AppState>>,
+    Query(query): Query<SuggestQuery>,
+) -> impl IntoResponse {
+    let input = query.q.trim();
+    if input.is_empty() {
+        return (StatusCode::BAD_REQUEST, "查询内容不能为空").into_response();
+    }
+
+    tracing::debug!(">>> 分词调试: q='{}'", input);
+
+    match mapping_service::debug_segmentation(&state.db, input).await {
+        Ok(result) => (StatusCode::OK, Json(result)).into_response(),
+        Err(e) => {
+            tracing::error!("!!! 分词调试失败: {}", e);
+            (StatusCode::INTERNAL_SERVER_ERROR, format!("查询异常: {}", e)).into_response()
+        }
+    }
+}
@@ -2,4 +2,11 @@ pub mod word_root_handler;
 pub mod mapping_handler;
 pub mod field_handler;
 pub mod auth_handler;
-pub mod task_handler;
\ No newline at end of file
+pub mod task_handler;
+pub mod naming_handler;
+pub mod stop_word_handler;
+pub mod typeahead_handler;
+pub mod synonym_handler;
+pub mod notification_handler;
+pub mod event_handler;
+pub mod webhook_handler;
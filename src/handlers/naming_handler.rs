@@ -0,0 +1,206 @@
+use axum::{extract::{State, Path}, Json, http::StatusCode, response::IntoResponse};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use crate::AppState;
+use crate::models::naming_profile::{CreateNamingProfile, NamingProfile};
+use crate::services::{compliance_service, naming_service};
+
+#[derive(Deserialize)]
+pub struct ValidateNamePayload {
+    pub name: String,
+    pub profile: Option<String>,
+}
+
+#[derive(Serialize)]
+pub struct ValidateNameResponse {
+    pub valid: bool,
+    pub profile: String,
+    pub issues: Vec<String>,
+}
+
+#[derive(Deserialize)]
+pub struct CheckFieldNamePayload {
+    pub field_cn_name: String,
+    pub field_en_name: String,
+    pub profile: Option<String>,
+    pub exclude_field_id: Option<i32>, // 更新字段时排除自身的重复校验
+}
+
+fn check_payload(payload: &CreateNamingProfile) -> Result<(), String> {
+    if payload.name.trim().is_empty() {
+        return Err("规范名称不能为空".to_string());
+    }
+    if !naming_service::CASE_STYLES.contains(&payload.case_style.as_str()) {
+        return Err(format!("不支持的命名风格 [{}]，可选: {:?}", payload.case_style, naming_service::CASE_STYLES));
+    }
+    if let Some(style) = payload.number_style.as_deref() {
+        if !naming_service::NUMBER_STYLES.contains(&style) {
+            return Err(format!("不支持的数字规则 [{}]，可选: {:?}", style, naming_service::NUMBER_STYLES));
+        }
+    }
+    if matches!(payload.max_length, Some(m) if m <= 0) {
+        return Err("最大长度必须大于 0".to_string());
+    }
+    Ok(())
+}
+
+/// 1. 获取所有命名规范
+pub async fn list_profiles(State(state): State<Arc<AppState>>) -> impl IntoResponse {
+    let result = sqlx::query_as!(
+        NamingProfile,
+        "SELECT id, name, case_style, prefix, suffix, max_length, number_style, is_default, remark, created_at
+         FROM naming_profiles ORDER BY id ASC"
+    )
+    .fetch_all(&state.db)
+    .await;
+
+    match result {
+        Ok(profiles) => (StatusCode::OK, Json(profiles)).into_response(),
+        Err(e) => {
+            tracing::error!("获取命名规范列表异常: {}", e);
+            (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()).into_response()
+        }
+    }
+}
+
+/// 2. 创建命名规范
+pub async fn create_profile(
+    State(state): State<Arc<AppState>>,
+    Json(payload): Json<CreateNamingProfile>,
+) -> impl IntoResponse {
+    if let Err(msg) = check_payload(&payload) {
+        return (StatusCode::BAD_REQUEST, msg).into_response();
+    }
+    tracing::info!(">>> 创建命名规范: name={}, style={}", payload.name, payload.case_style);
+
+    // 取消原默认规范与写入新规范在同一事务内，写入失败时原默认规范保留
+    let is_default = payload.is_default.unwrap_or(false);
+    let result = async {
+        let mut tx = state.db.begin().await?;
+        if is_default {
+            sqlx::query!("UPDATE naming_profiles SET is_default = false WHERE is_default = true")
+                .execute(&mut *tx)
+                .await?;
+        }
+        let profile = sqlx::query_as!(
+            NamingProfile,
+            r#"
+            INSERT INTO naming_profiles (name, case_style, prefix, suffix, max_length, number_style, is_default, remark)
+            VALUES ($1, $2, $3, $4, $5, COALESCE($6, 'separate'), $7, $8)
+            RETURNING id, name, case_style, prefix, suffix, max_length, number_style, is_default, remark, created_at
+            "#,
+            payload.name.trim(), payload.case_style, payload.prefix, payload.suffix,
+            payload.max_length, payload.number_style, is_default, payload.remark
+        )
+        .fetch_one(&mut *tx)
+        .await?;
+        tx.commit().await?;
+        Ok::<_, sqlx::Error>(profile)
+    }
+    .await;
+
+    match result {
+        Ok(profile) => {
+            tracing::info!("<<< 命名规范创建成功: ID={}", profile.id);
+            (StatusCode::CREATED, Json(profile)).into_response()
+        },
+        Err(e) => {
+            tracing::error!("!!! 命名规范创建失败: {}", e);
+            (StatusCode::BAD_REQUEST, format!("创建失败，名称可能已存在: {}", e)).into_response()
+        }
+    }
+}
+
+/// 3. 更新命名规范
+pub async fn update_profile(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<i32>,
+    Json(payload): Json<CreateNamingProfile>,
+) -> impl IntoResponse {
+    if let Err(msg) = check_payload(&payload) {
+        return (StatusCode::BAD_REQUEST, msg).into_response();
+    }
+
+    let is_default = payload.is_default.unwrap_or(false);
+    let res = async {
+        let mut tx = state.db.begin().await?;
+        if is_default {
+            sqlx::query!("UPDATE naming_profiles SET is_default = false WHERE is_default = true AND id <> $1", id)
+                .execute(&mut *tx)
+                .await?;
+        }
+        let r = sqlx::query!(
+            r#"UPDATE naming_profiles SET name=$1, case_style=$2, prefix=$3, suffix=$4, max_length=$5,
+               number_style=COALESCE($6, number_style), is_default=$7, remark=$8 WHERE id=$9"#,
+            payload.name.trim(), payload.case_style, payload.prefix, payload.suffix,
+            payload.max_length, payload.number_style, is_default, payload.remark, id
+        )
+        .execute(&mut *tx)
+        .await?;
+        // 规范不存在时回滚，避免清除原默认规范
+        if r.rows_affected() > 0 {
+            tx.commit().await?;
+        }
+        Ok::<_, sqlx::Error>(r.rows_affected())
+    }
+    .await;
+
+    match res {
+        Ok(n) if n > 0 => StatusCode::OK.into_response(),
+        Ok(_) => StatusCode::NOT_FOUND.into_response(),
+        Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, format!("更新失败: {}", e)).into_response(),
+    }
+}
+
+/// 4. 删除命名规范
+pub async fn delete_profile(State(state): State<Arc<AppState>>, Path(id): Path<i32>) -> impl IntoResponse {
+    match sqlx::query!("DELETE FROM naming_profiles WHERE id = $1", id).execute(&state.db).await {
+        Ok(r) if r.rows_affected() > 0 => StatusCode::NO_CONTENT.into_response(),
+        Ok(_) => StatusCode::NOT_FOUND.into_response(),
+        Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()).into_response(),
+    }
+}
+
+/// 5. 按命名规范校验英文名
+pub async fn validate_name(
+    State(state): State<Arc<AppState>>,
+    Json(payload): Json<ValidateNamePayload>,
+) -> impl IntoResponse {
+    let profile = match naming_service::resolve_profile(&state.db, payload.profile.as_deref()).await {
+        Ok(p) => p,
+        Err(msg) => return (StatusCode::BAD_REQUEST, msg).into_response(),
+    };
+
+    let issues = naming_service::validate_name(payload.name.trim(), &profile);
+    Json(ValidateNameResponse { valid: issues.is_empty(), profile: profile.name, issues }).into_response()
+}
+
+/// 6. 字段命名合规检查 (中英文名组合)
+pub async fn check_field_name(
+    State(state): State<Arc<AppState>>,
+    Json(payload): Json<CheckFieldNamePayload>,
+) -> impl IntoResponse {
+    let cn_name = payload.field_cn_name.trim();
+    let en_name = payload.field_en_name.trim();
+    if cn_name.is_empty() || en_name.is_empty() {
+        return (StatusCode::BAD_REQUEST, "中文名和英文名均不能为空").into_response();
+    }
+
+    let profile = match naming_service::resolve_profile(&state.db, payload.profile.as_deref()).await {
+        Ok(p) => p,
+        Err(msg) => return (StatusCode::BAD_REQUEST, msg).into_response(),
+    };
+
+    tracing::info!(">>> 命名合规检查: cn={}, en={}, profile={}", cn_name, en_name, profile.name);
+
+    match compliance_service::check_field_name(&state.db, cn_name, en_name, &profile, payload.exclude_field_id).await {
+        Ok(report) => {
+            tracing::info!("<<< 合规检查完成: passed={}, issues={}", report.passed, report.issues.len());
+            (StatusCode::OK, Json(report)).into_response()
+        }
+        Err(e) => {
+            tracing::error!("!!! 合规检查异常: {}", e);
+            (StatusCode::INTERNAL_SERVER_ERROR, format!("查询异常: {}", e)).into_response()
+        }
+    }
+}
@@ -0,0 +1,72 @@
+use axum::{extract::{State, Path, Query}, Extension, Json, http::StatusCode, response::IntoResponse};
+use serde::Deserialize;
+use std::sync::Arc;
+use crate::AppState;
+use crate::models::user::Claims;
+use crate::services::notification_service::UserNotification;
+
+#[derive(Deserialize)]
+pub struct NotificationQuery {
+    pub unread: Option<bool>, // true 时只返回未读
+}
+
+/// 当前用户的站内通知
+pub async fn list_notifications(
+    State(state): State<Arc<AppState>>,
+    Extension(claims): Extension<Claims>,
+    Query(query): Query<NotificationQuery>,
+) -> impl IntoResponse {
+    let result = sqlx::query_as!(
+        UserNotification,
+        r#"SELECT id, kind, title, content, task_id, is_read, created_at
+           FROM user_notifications
+           WHERE user_id = $1 AND (NOT $2 OR is_read = false)
+           ORDER BY created_at DESC, id DESC
+           LIMIT 200"#,
+        claims.sub, query.unread.unwrap_or(false)
+    )
+    .fetch_all(&state.db)
+    .await;
+
+    match result {
+        Ok(list) => Json(list).into_response(),
+        Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()).into_response(),
+    }
+}
+
+/// 标记单条通知为已读
+pub async fn mark_notification_read(
+    State(state): State<Arc<AppState>>,
+    Extension(claims): Extension<Claims>,
+    Path(id): Path<i32>,
+) -> impl IntoResponse {
+    let res = sqlx::query!(
+        "UPDATE user_notifications SET is_read = true WHERE id = $1 AND user_id = $2",
+        id, claims.sub
+    )
+    .execute(&state.db)
+    .await;
+
+    match res {
+        Ok(r) if r.rows_affected() == 0 => StatusCode::NOT_FOUND.into_response(),
+        Ok(_) => StatusCode::OK.into_response(),
+        Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()).into_response(),
+    }
+}
+
+/// 全部标记为已读
+pub async fn mark_all_notifications_read(
+    State(state): State<Arc<AppState>>,
+    Extension(claims): Extension<Claims>,
+) -> impl IntoResponse {
+    match sqlx::query!(
+        "UPDATE user_notifications SET is_read = true WHERE user_id = $1 AND is_read = false",
+        claims.sub
+    )
+    .execute(&state.db)
+    .await
+    {
+        Ok(r) => Json(serde_json::json!({ "updated": r.rows_affected() })).into_response(),
+        Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()).into_response(),
+    }
+}
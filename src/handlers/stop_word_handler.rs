@@ -0,0 +1,100 @@
+use axum::{extract::{State, Path}, Json, http::StatusCode, response::IntoResponse};
+use std::sync::Arc;
+use crate::AppState;
+use crate::models::stop_word::{CreateStopWord, StopWord};
+use crate::services::dictionary_service;
+
+fn normalize_kind(kind: Option<&str>) -> Result<&'static str, String> {
+    match kind.map(str::trim).unwrap_or("stop") {
+        "stop" | "" => Ok("stop"),
+        "punct" => Ok("punct"),
+        other => Err(format!("不支持的类型 [{}]，可选: stop / punct", other)),
+    }
+}
+
+/// 1. 获取停用词列表
+pub async fn list_stop_words(State(state): State<Arc<AppState>>) -> impl IntoResponse {
+    let result = sqlx::query_as!(
+        StopWord,
+        "SELECT id, word, kind, created_at FROM stop_words ORDER BY kind, id"
+    )
+    .fetch_all(&state.db)
+    .await;
+
+    match result {
+        Ok(words) => (StatusCode::OK, Json(words)).into_response(),
+        Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()).into_response(),
+    }
+}
+
+/// 2. 新增停用词
+pub async fn create_stop_word(
+    State(state): State<Arc<AppState>>,
+    Json(payload): Json<CreateStopWord>,
+) -> impl IntoResponse {
+    let word = payload.word.trim();
+    if word.is_empty() {
+        return (StatusCode::BAD_REQUEST, "停用词不能为空").into_response();
+    }
+    let kind = match normalize_kind(payload.kind.as_deref()) {
+        Ok(k) => k,
+        Err(msg) => return (StatusCode::BAD_REQUEST, msg).into_response(),
+    };
+
+    let result = sqlx::query_as!(
+        StopWord,
+        "INSERT INTO stop_words (word, kind) VALUES ($1, $2) RETURNING id, word, kind, created_at",
+        word, kind
+    )
+    .fetch_one(&state.db)
+    .await;
+
+    match result {
+        Ok(sw) => {
+            dictionary_service::on_stop_words_changed(&state.db).await;
+            tracing::info!("<<< 停用词新增成功: {} ({})", sw.word, sw.kind);
+            (StatusCode::CREATED, Json(sw)).into_response()
+        }
+        Err(e) => (StatusCode::BAD_REQUEST, format!("新增失败，可能已存在: {}", e)).into_response(),
+    }
+}
+
+/// 3. 修改停用词
+pub async fn update_stop_word(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<i32>,
+    Json(payload): Json<CreateStopWord>,
+) -> impl IntoResponse {
+    let word = payload.word.trim();
+    if word.is_empty() {
+        return (StatusCode::BAD_REQUEST, "停用词不能为空").into_response();
+    }
+    let kind = match normalize_kind(payload.kind.as_deref()) {
+        Ok(k) => k,
+        Err(msg) => return (StatusCode::BAD_REQUEST, msg).into_response(),
+    };
+
+    match sqlx::query!("UPDATE stop_words SET word = $1, kind = $2 WHERE id = $3", word, kind, id)
+        .execute(&state.db)
+        .await
+    {
+        Ok(r) if r.rows_affected() > 0 => {
+            dictionary_service::on_stop_words_changed(&state.db).await;
+            StatusCode::OK.into_response()
+        }
+        Ok(_) => StatusCode::NOT_FOUND.into_response(),
+        Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, format!("更新失败: {}", e)).into_response(),
+    }
+}
+
+/// 4. 删除停用词
+pub async fn delete_stop_word(State(state): State<Arc<AppState>>, Path(id): Path<i32>) -> impl IntoResponse {
+    match sqlx::query!("DELETE FROM stop_words WHERE id = $1", id).execute(&state.db).await {
+        Ok(r) if r.rows_affected() > 0 => {
+            dictionary_service::on_stop_words_changed(&state.db).await;
+            StatusCode::NO_CONTENT.into_response()
+        }
+        Ok(_) => StatusCode::NOT_FOUND.into_response(),
+        Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()).into_response(),
+    }
+}
@@ -0,0 +1,183 @@
+use axum::{extract::{State, Path, Query}, Json, http::StatusCode, response::IntoResponse};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use crate::AppState;
+use crate::handlers::word_root_handler::upsert_root_vector;
+use crate::models::synonym::{CreateSynonym, Synonym};
+use crate::models::word_root::WordRoot;
+use crate::services::synonym_service::{self, SynonymConflict};
+use crate::services::dictionary_service;
+
+#[derive(Deserialize)]
+pub struct ConflictQuery {
+    pub term: Option<String>,
+}
+
+#[derive(Serialize)]
+pub struct SynonymWriteResponse {
+    #[serde(flatten)]
+    pub synonym: Synonym,
+    pub conflicts: Vec<SynonymConflict>, // 写入后该同义词涉及的跨词根冲突，仅提示不拦截
+}
+
+pub(crate) fn check_payload(payload: &CreateSynonym) -> Result<(), String> {
+    let term = payload.term.trim();
+    if term.is_empty() {
+        return Err("同义词不能为空".to_string());
+    }
+    if term.chars().count() > 100 {
+        return Err("同义词长度不能超过 100".to_string());
+    }
+    if let Some(t) = payload.syn_type.as_deref() {
+        if !synonym_service::SYN_TYPES.contains(&t) {
+            return Err(format!("不支持的同义词类型 [{}]，可选: {:?}", t, synonym_service::SYN_TYPES));
+        }
+    }
+    if matches!(payload.weight, Some(w) if !(0..=100).contains(&w)) {
+        return Err("权重必须在 0~100 之间".to_string());
+    }
+    Ok(())
+}
+
+/// 同义词变更后：重新生成词根缓存、向量与分词词典
+pub(crate) async fn after_change(state: &AppState, root_id: i32) {
+    let refreshed = async {
+        let mut conn = state.db.acquire().await?;
+        synonym_service::refresh_root_cache(&mut conn, root_id).await
+    }
+    .await;
+    if let Err(e) = refreshed {
+        tracing::error!("!!! 词根 ID={} 同义词缓存刷新失败: {}", root_id, e);
+    }
+    if let Ok(Some(root)) = sqlx::query_as!(WordRoot, "SELECT * FROM standard_word_roots WHERE id = $1", root_id)
+        .fetch_optional(&state.db)
+        .await
+    {
+        upsert_root_vector(state, &root).await;
+    }
+    dictionary_service::on_roots_changed(&state.db).await;
+}
+
+async fn conflicts_of(state: &AppState, term: &str) -> Vec<SynonymConflict> {
+    synonym_service::find_conflicts(&state.db, Some(term)).await.unwrap_or_else(|e| {
+        tracing::warn!("--- 同义词冲突检测失败: {}", e);
+        Vec::new()
+    })
+}
+
+/// 1. 获取词根的全部同义词
+pub async fn list_root_synonyms(State(state): State<Arc<AppState>>, Path(root_id): Path<i32>) -> impl IntoResponse {
+    let result = sqlx::query_as!(
+        Synonym,
+        "SELECT id, root_id, term, syn_type, weight, source, remark, created_at
+         FROM word_root_synonyms WHERE root_id = $1 ORDER BY weight DESC, id",
+        root_id
+    )
+    .fetch_all(&state.db)
+    .await;
+
+    match result {
+        Ok(items) => (StatusCode::OK, Json(items)).into_response(),
+        Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()).into_response(),
+    }
+}
+
+/// 2. 为词根新增同义词
+pub async fn create_synonym(
+    State(state): State<Arc<AppState>>,
+    Path(root_id): Path<i32>,
+    Json(payload): Json<CreateSynonym>,
+) -> impl IntoResponse {
+    if let Err(msg) = check_payload(&payload) {
+        return (StatusCode::BAD_REQUEST, msg).into_response();
+    }
+    let term = payload.term.trim();
+    tracing::info!(">>> 新增同义词: root_id={}, term={}", root_id, term);
+
+    let result = sqlx::query_as!(
+        Synonym,
+        r#"INSERT INTO word_root_synonyms (root_id, term, syn_type, weight, source, remark)
+           VALUES ($1, $2, COALESCE($3, 'exact'), COALESCE($4, 100), COALESCE($5, 'manual'), $6)
+           RETURNING id, root_id, term, syn_type, weight, source, remark, created_at"#,
+        root_id, term, payload.syn_type, payload.weight, payload.source, payload.remark
+    )
+    .fetch_one(&state.db)
+    .await;
+
+    match result {
+        Ok(synonym) => {
+            after_change(&state, root_id).await;
+            let conflicts = conflicts_of(&state, &synonym.term).await;
+            if !conflicts.is_empty() {
+                tracing::warn!("--- 同义词 [{}] 存在 {} 处跨词根冲突", synonym.term, conflicts.len());
+            }
+            (StatusCode::CREATED, Json(SynonymWriteResponse { synonym, conflicts })).into_response()
+        }
+        Err(e) => {
+            tracing::error!("!!! 同义词新增失败: {}", e);
+            (StatusCode::BAD_REQUEST, format!("新增失败，词根不存在或同义词已存在: {}", e)).into_response()
+        }
+    }
+}
+
+/// 3. 修改同义词
+pub async fn update_synonym(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<i32>,
+    Json(payload): Json<CreateSynonym>,
+) -> impl IntoResponse {
+    if let Err(msg) = check_payload(&payload) {
+        return (StatusCode::BAD_REQUEST, msg).into_response();
+    }
+
+    let result = sqlx::query_as!(
+        Synonym,
+        r#"UPDATE word_root_synonyms
+           SET term = $1, syn_type = COALESCE($2, syn_type), weight = COALESCE($3, weight),
+               source = COALESCE($4, source), remark = $5
+           WHERE id = $6
+           RETURNING id, root_id, term, syn_type, weight, source, remark, created_at"#,
+        payload.term.trim(), payload.syn_type, payload.weight, payload.source, payload.remark, id
+    )
+    .fetch_optional(&state.db)
+    .await;
+
+    match result {
+        Ok(Some(synonym)) => {
+            after_change(&state, synonym.root_id).await;
+            let conflicts = conflicts_of(&state, &synonym.term).await;
+            (StatusCode::OK, Json(SynonymWriteResponse { synonym, conflicts })).into_response()
+        }
+        Ok(None) => StatusCode::NOT_FOUND.into_response(),
+        Err(e) => (StatusCode::BAD_REQUEST, format!("更新失败，同义词可能已存在: {}", e)).into_response(),
+    }
+}
+
+/// 4. 删除同义词
+pub async fn delete_synonym(State(state): State<Arc<AppState>>, Path(id): Path<i32>) -> impl IntoResponse {
+    match sqlx::query_scalar!("DELETE FROM word_root_synonyms WHERE id = $1 RETURNING root_id", id)
+        .fetch_optional(&state.db)
+        .await
+    {
+        Ok(Some(root_id)) => {
+            after_change(&state, root_id).await;
+            StatusCode::NO_CONTENT.into_response()
+        }
+        Ok(None) => StatusCode::NOT_FOUND.into_response(),
+        Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()).into_response(),
+    }
+}
+
+/// 5. 跨词根同义词冲突检测
+pub async fn list_conflicts(
+    State(state): State<Arc<AppState>>,
+    Query(query): Query<ConflictQuery>,
+) -> impl IntoResponse {
+    match synonym_service::find_conflicts(&state.db, query.term.as_deref()).await {
+        Ok(conflicts) => (StatusCode::OK, Json(conflicts)).into_response(),
+        Err(e) => {
+            tracing::error!("!!! 同义词冲突检测异常: {}", e);
+            (StatusCode::INTERNAL_SERVER_ERROR, format!("查询异常: {}", e)).into_response()
+        }
+    }
+}
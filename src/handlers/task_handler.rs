@@ -1,39 +1,353 @@
-use axum::{extract::{State, Path}, Json, http::StatusCode, response::IntoResponse};
+use axum::{body::Bytes, extract::{rejection::JsonRejection, State, Path, Query}, Extension, Json, http::StatusCode, response::IntoResponse};
 use std::sync::Arc;
 use crate::AppState;
-use serde::{Deserialize, Serialize};
+use crate::models::user::Claims;
+use crate::models::field::{CreateFieldRequest, StandardField};
+use crate::models::synonym::Synonym;
+use crate::models::task_request::TaskRequest;
+use crate::models::word_root::{CreateWordRoot, WordRoot};
+use crate::handlers::field_handler::{self, FieldWriteQuery};
+use crate::handlers::word_root_handler::{self, CreateRootQuery, PaginatedResponse};
+use crate::handlers::synonym_handler;
+use crate::services::compliance_service::ComplianceReport;
+use crate::services::task_request_service::{self, TaskRequestError};
+use crate::services::task_report_service;
+use crate::services::{dictionary_service, event_bus, mapping_service, naming_service, notification_service, reverse_service, webhook_service};
+use serde::{de::DeserializeOwned, Deserialize, Deserializer, Serialize};
 
+/// 申请状态
+pub const TASK_STATUSES: [&str; 5] = ["open", "in_progress", "approved", "rejected", "duplicate"];
+
+/// 已结束的状态 (需记录处理人与处理时间)
+const TERMINAL_STATUSES: [&str; 3] = ["approved", "rejected", "duplicate"];
+
+// 旧版请求体，等同于 FIELD_REQUEST
 #[derive(Deserialize)]
 pub struct CreateTaskPayload {
     pub field_cn_name: String,
 }
 
+// 提交申请的请求体：带 task_type 的结构化申请，或旧版字段申请
+// 以是否携带 task_type 区分，结构化申请格式错误时直接报错，不回退为旧版字段申请
+pub enum SubmitTaskBody {
+    Typed(TaskRequest),
+    Legacy(CreateTaskPayload),
+}
+
+impl<'de> Deserialize<'de> for SubmitTaskBody {
+    fn deserialize<D: Deserializer<'de>>(d: D) -> Result<Self, D::Error> {
+        let value = serde_json::Value::deserialize(d)?;
+        if value.get("task_type").is_some() {
+            TaskRequest::deserialize(value).map(SubmitTaskBody::Typed).map_err(serde::de::Error::custom)
+        } else {
+            CreateTaskPayload::deserialize(value).map(SubmitTaskBody::Legacy).map_err(serde::de::Error::custom)
+        }
+    }
+}
+
+#[derive(Deserialize)]
+pub struct SubmitTaskQuery {
+    pub confirm: Option<bool>, // 已确认近似字段不满足需求，仍提交申请
+}
+
+#[derive(Serialize)]
+pub struct SimilarFieldsResponse {
+    pub message: String,
+    pub text_matches: Vec<StandardField>,
+    pub vector_matches: Vec<field_handler::FieldVectorHit>,
+}
+
+#[derive(Serialize)]
+pub struct SubmitResult {
+    pub id: i32,
+    pub merged: bool, // 是否合并到已有的相同申请
+    pub vote_count: i32,
+}
+
 #[derive(Serialize, sqlx::FromRow)]
 pub struct NotificationTask {
     pub id: i32,
     pub task_type: String,
     pub payload: serde_json::Value,
-    pub is_read: bool,
+    pub is_read: bool, // 兼容旧前端：状态已结束时为 true
+    pub status: String,
+    pub submitter_id: Option<i32>, // 申请人，匿名提交时为空
+    pub vote_count: i32,           // 提交次数 (申请人 + 附议)
+    pub assignee_id: Option<i32>,
+    pub resolution_note: Option<String>,
+    pub resolved_root_id: Option<i32>,  // 满足申请的词根
+    pub resolved_field_id: Option<i32>, // 满足申请的标准字段
+    pub duplicate_of_id: Option<i32>,   // 重复申请指向的原申请
+    pub resolved_by: Option<i32>,
+    pub resolved_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub updated_at: Option<chrono::DateTime<chrono::Utc>>,
     pub created_at: chrono::DateTime<chrono::Utc>,
 }
 
-/// 用户提交新增申请
+#[derive(Serialize, sqlx::FromRow)]
+pub struct TaskComment {
+    pub id: i32,
+    pub task_id: i32,
+    pub author_id: Option<i32>,
+    pub author_name: Option<String>,
+    pub content: String,
+    pub created_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+#[derive(Serialize)]
+pub struct TaskDetail {
+    #[serde(flatten)]
+    pub task: NotificationTask,
+    pub comments: Vec<TaskComment>,
+}
+
+// 任务列表筛选参数
+#[derive(Deserialize)]
+pub struct TaskListQuery {
+    pub status: Option<String>,    // 逗号分隔，all 表示全部；缺省为 open,in_progress
+    pub assignee: Option<String>,  // me 当前用户 / none 未指派 / 用户 ID
+    pub task_type: Option<String>, // 逗号分隔
+    pub submitter_id: Option<i32>,
+    pub q: Option<String>, // 按申请内容模糊查询
+    pub page: Option<i64>,
+    pub page_size: Option<i64>, // 缺省 20，最大 200
+}
+
+// 统计参数
+#[derive(Deserialize)]
+pub struct TaskStatsQuery {
+    pub older_than_days: Option<i32>, // 超期阈值，缺省 7 天
+    pub window_days: Option<i32>,     // 处理时长统计窗口，缺省 90 天
+}
+
+// 吞吐报表区间，日期含首尾，缺省最近 30 天
+#[derive(Deserialize)]
+pub struct TaskReportQuery {
+    pub from: Option<chrono::NaiveDate>,
+    pub to: Option<chrono::NaiveDate>,
+}
+
+// 区分 "未传" 与 "显式传 null"：未传为 None，null 为 Some(None)
+fn double_option<'de, D, T>(d: D) -> Result<Option<Option<T>>, D::Error>
+where
+    D: Deserializer<'de>,
+    T: Deserialize<'de>,
+{
+    Option::<T>::deserialize(d).map(Some)
+}
+
+// 处理任务的请求体，所有字段可选
+#[derive(Deserialize, Default)]
+pub struct UpdateTaskPayload {
+    pub status: Option<String>,
+    #[serde(default, deserialize_with = "double_option")]
+    pub assignee_id: Option<Option<i32>>, // null 表示取消指派
+    pub resolution_note: Option<String>,
+    pub resolved_root_id: Option<i32>,
+    pub resolved_field_id: Option<i32>,
+    pub duplicate_of_id: Option<i32>,
+    pub comment: Option<String>, // 同时追加一条评论
+}
+
+#[derive(Deserialize)]
+pub struct CreateCommentPayload {
+    pub content: String,
+}
+
+// 校验未通过时直接返回给调用方的响应
+type Rejection = (StatusCode, String);
+
+/// 可选的 JSON 请求体：仅在请求体为空时返回 None，格式错误返回 400 (不能按缺省值继续处理)
+fn optional_body<T: DeserializeOwned>(body: &Bytes) -> Result<Option<T>, Rejection> {
+    if body.iter().all(u8::is_ascii_whitespace) {
+        return Ok(None);
+    }
+    Json::<T>::from_bytes(body)
+        .map(|Json(b)| Some(b))
+        .map_err(|e| (StatusCode::BAD_REQUEST, format!("请求体格式错误: {}", e.body_text())))
+}
+
+async fn fetch_task(state: &AppState, id: i32) -> Result<Option<NotificationTask>, sqlx::Error> {
+    sqlx::query_as!(
+        NotificationTask,
+        r#"SELECT id, task_type, payload as "payload!", is_read as "is_read!", status, submitter_id, vote_count, assignee_id, resolution_note,
+                  resolved_root_id, resolved_field_id, duplicate_of_id, resolved_by, resolved_at, updated_at,
+                  created_at as "created_at!"
+           FROM notification_tasks WHERE id = $1"#,
+        id
+    )
+    .fetch_optional(&state.db)
+    .await
+}
+
+async fn fetch_comments(state: &AppState, task_id: i32) -> Result<Vec<TaskComment>, sqlx::Error> {
+    sqlx::query_as!(
+        TaskComment,
+        r#"SELECT c.id, c.task_id, c.author_id, u.username as "author_name?", c.content, c.created_at
+           FROM task_comments c LEFT JOIN users u ON u.id = c.author_id
+           WHERE c.task_id = $1 ORDER BY c.created_at, c.id"#,
+        task_id
+    )
+    .fetch_all(&state.db)
+    .await
+}
+
+async fn insert_comment(state: &AppState, task_id: i32, author_id: i32, content: &str) -> Result<(), sqlx::Error> {
+    sqlx::query!(
+        "INSERT INTO task_comments (task_id, author_id, content) VALUES ($1, $2, $3)",
+        task_id, author_id, content
+    )
+    .execute(&state.db)
+    .await?;
+    Ok(())
+}
+
+// 申请的标题：按申请类型解析 payload，无法解析的旧数据取任务类型
+fn task_subject(task: &NotificationTask) -> String {
+    TaskRequest::from_row(&task.task_type, &task.payload)
+        .map(|r| r.subject())
+        .unwrap_or_else(|_| task.task_type.clone())
+}
+
+// 申请结束后通知申请人及附议用户
+async fn notify_submitter(state: &AppState, task: &NotificationTask) {
+    let (title, content) = notification_service::task_resolved_message(
+        &task.status, &task_subject(task), task.resolution_note.as_deref(),
+    );
+    if let Err(e) = notification_service::notify_task_followers(
+        &state.db, task.id, "TASK_RESOLVED", &title, Some(&content),
+    ).await {
+        tracing::error!("!!! 任务 ID={} 通知申请人失败: {}", task.id, e);
+    }
+}
+
+/// 用户提交申请 (新增字段 / 新增词根 / 修改字段 / 废弃 / 同义词建议)，payload 按类型校验后存入 payload 列
+/// 与未结束的相同申请合并为附议 (200)，否则新建 (201)
+/// 登录用户提交时记录申请人；是否允许匿名提交由环境变量 ALLOW_ANONYMOUS_REQUESTS 控制 (默认 true)
 pub async fn submit_task(
     State(state): State<Arc<AppState>>,
-    Json(payload): Json<CreateTaskPayload>,
+    claims: Option<Extension<Claims>>,
+    Query(query): Query<SubmitTaskQuery>,
+    body: Result<Json<SubmitTaskBody>, JsonRejection>,
 ) -> impl IntoResponse {
-    tracing::info!(">>> 用户提交新字段申请: {}", payload.field_cn_name);
-    
-    let res = sqlx::query!(
-        "INSERT INTO notification_tasks (task_type, payload) VALUES ($1, $2)",
-        "FIELD_REQUEST",
-        serde_json::json!({ "field_cn_name": payload.field_cn_name })
-    )
-    .execute(&state.db)
+    let body = match body {
+        Ok(Json(b)) => b,
+        Err(e) => return (StatusCode::BAD_REQUEST, format!("请求体格式错误: {}", e.body_text())).into_response(),
+    };
+    let submitter_id = claims.map(|Extension(c)| c.sub);
+    if submitter_id.is_none() && !anonymous_allowed() {
+        return (StatusCode::UNAUTHORIZED, "请登录后再提交申请").into_response();
+    }
+    let mut request = match body {
+        SubmitTaskBody::Typed(r) => r,
+        SubmitTaskBody::Legacy(p) => TaskRequest::FieldRequest(crate::models::task_request::FieldRequestPayload {
+            field_cn_name: p.field_cn_name,
+            reason: None,
+        }),
+    };
+    match task_request_service::validate(&state.db, &mut request).await {
+        Ok(()) => {}
+        Err(TaskRequestError::Invalid(msg)) => return (StatusCode::BAD_REQUEST, msg).into_response(),
+        Err(TaskRequestError::Db(e)) => return (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()).into_response(),
+    }
+    tracing::info!(">>> 用户提交申请: {} [{}], 申请人={:?}", request.task_type(), request.subject(), submitter_id);
+
+    // 字段申请先按搜索接口的混合检索查重，命中时返回近似字段，用户确认 (?confirm=true) 后才提交
+    if let TaskRequest::FieldRequest(p) = &request {
+        if !query.confirm.unwrap_or(false) {
+            let text_matches = field_handler::search_fields_by_text(&state, p.field_cn_name.trim()).await;
+            let vector_matches = if text_matches.is_empty() {
+                field_handler::search_fields_by_vector(&state, p.field_cn_name.trim(), 5, Some(similar_threshold()))
+                    .await
+                    .unwrap_or_default()
+            } else {
+                Vec::new()
+            };
+            if !text_matches.is_empty() || !vector_matches.is_empty() {
+                tracing::info!("<<< 申请 [{}] 命中近似字段，等待用户确认", p.field_cn_name);
+                let message = "已存在相近的标准字段，请确认是否可直接使用；仍需新增请使用 confirm=true 提交".to_string();
+                return (StatusCode::CONFLICT, Json(SimilarFieldsResponse { message, text_matches, vector_matches })).into_response();
+            }
+        }
+    }
+
+    let dedup_key = task_request_service::dedup_key(&request);
+    let payload = request.payload();
+    let reason = payload.get("reason").and_then(|v| v.as_str()).map(String::from);
+    let result: Result<SubmitResult, sqlx::Error> = async {
+        let mut tx = state.db.begin().await?;
+        // 同一申请串行处理，避免并发提交时重复建单
+        sqlx::query("SELECT pg_advisory_xact_lock(hashtext($1))")
+            .bind(format!("{}:{}", request.task_type(), dedup_key))
+            .execute(&mut *tx)
+            .await?;
+
+        let existing = sqlx::query!(
+            r#"SELECT id, status, vote_count FROM notification_tasks
+               WHERE task_type = $1 AND dedup_key = $2 AND status IN ('open', 'in_progress')
+               ORDER BY id LIMIT 1"#,
+            request.task_type(), dedup_key
+        )
+        .fetch_optional(&mut *tx)
+        .await?;
+
+        let outcome = match existing {
+            // 已有相同的未结束申请：记为附议，同一用户重复提交不重复计数；匿名提交无法去重，不计票
+            Some(task) => {
+                let voted = match submitter_id {
+                    Some(user_id) => sqlx::query!(
+                        "INSERT INTO task_votes (task_id, user_id, reason) VALUES ($1, $2, $3) ON CONFLICT (task_id, user_id) DO NOTHING",
+                        task.id, user_id, reason
+                    )
+                    .execute(&mut *tx)
+                    .await?
+                    .rows_affected() > 0,
+                    None => false,
+                };
+                let vote_count = if voted {
+                    sqlx::query_scalar!(
+                        "UPDATE notification_tasks SET vote_count = vote_count + 1, updated_at = CURRENT_TIMESTAMP WHERE id = $1 RETURNING vote_count",
+                        task.id
+                    )
+                    .fetch_one(&mut *tx)
+                    .await?
+                } else {
+                    task.vote_count
+                };
+                event_bus::publish_task_event(&mut tx, "task_updated", task.id, request.task_type(), &task.status).await;
+                SubmitResult { id: task.id, merged: true, vote_count }
+            }
+            None => {
+                let id = sqlx::query_scalar!(
+                    "INSERT INTO notification_tasks (task_type, payload, submitter_id, dedup_key) VALUES ($1, $2, $3, $4) RETURNING id",
+                    request.task_type(), payload, submitter_id, dedup_key
+                )
+                .fetch_one(&mut *tx)
+                .await?;
+                // 申请人本人也记一票，之后重复提交不再计数
+                if submitter_id.is_some() {
+                    sqlx::query!(
+                        "INSERT INTO task_votes (task_id, user_id, reason) VALUES ($1, $2, $3)",
+                        id, submitter_id, reason
+                    )
+                    .execute(&mut *tx)
+                    .await?;
+                }
+                event_bus::publish_task_event(&mut tx, "task_created", id, request.task_type(), "open").await;
+                SubmitResult { id, merged: false, vote_count: 1 }
+            }
+        };
+        tx.commit().await?;
+        Ok(outcome)
+    }
     .await;
 
-    match res {
-        Ok(_) => StatusCode::CREATED.into_response(),
+    match result {
+        Ok(r) if r.merged => {
+            tracing::info!("<<< 申请已合并至任务 ID={}, 票数={}", r.id, r.vote_count);
+            (StatusCode::OK, Json(r)).into_response()
+        }
+        Ok(r) => (StatusCode::CREATED, Json(r)).into_response(),
         Err(e) => {
             tracing::error!("!!! 提交申请失败: {}", e);
             (StatusCode::INTERNAL_SERVER_ERROR, "提交失败").into_response()
@@ -41,40 +355,858 @@ pub async fn submit_task(
     }
 }
 
-/// 管理员获取待办任务列表
-pub async fn list_tasks(State(state): State<Arc<AppState>>) -> impl IntoResponse {
+fn similar_threshold() -> f32 {
+    std::env::var("REQUEST_SIMILAR_THRESHOLD").ok().and_then(|v| v.parse().ok()).unwrap_or(0.85)
+}
+
+fn anonymous_allowed() -> bool {
+    std::env::var("ALLOW_ANONYMOUS_REQUESTS").map(|v| v.trim() != "false").unwrap_or(true)
+}
+
+/// 管理员分页获取任务列表，支持按状态、类型、申请人与处理人筛选
+pub async fn list_tasks(
+    State(state): State<Arc<AppState>>,
+    Extension(claims): Extension<Claims>,
+    Query(query): Query<TaskListQuery>,
+) -> impl IntoResponse {
+    let statuses: Vec<String> = match query.status.as_deref().map(str::trim) {
+        None | Some("") => vec!["open".to_string(), "in_progress".to_string()],
+        Some("all") => TASK_STATUSES.iter().map(|s| s.to_string()).collect(),
+        Some(raw) => {
+            let list: Vec<String> = raw.split(',').map(|s| s.trim().to_string()).filter(|s| !s.is_empty()).collect();
+            if let Some(bad) = list.iter().find(|s| !TASK_STATUSES.contains(&s.as_str())) {
+                return (StatusCode::BAD_REQUEST, format!("不支持的状态 [{}]，可选: {:?}", bad, TASK_STATUSES)).into_response();
+            }
+            list
+        }
+    };
+    let (assignee_id, unassigned) = match query.assignee.as_deref().map(str::trim) {
+        None | Some("") => (None, false),
+        Some("me") => (Some(claims.sub), false),
+        Some("none") => (None, true),
+        Some(raw) => match raw.parse::<i32>() {
+            Ok(id) => (Some(id), false),
+            Err(_) => return (StatusCode::BAD_REQUEST, "assignee 可选: me / none / 用户 ID").into_response(),
+        },
+    };
+
+    let task_types: Option<Vec<String>> = query
+        .task_type
+        .as_deref()
+        .map(|raw| raw.split(',').map(|s| s.trim().to_string()).filter(|s| !s.is_empty()).collect::<Vec<_>>())
+        .filter(|list| !list.is_empty());
+    let pattern = query.q.as_deref().map(str::trim).filter(|q| !q.is_empty()).map(|q| format!("%{}%", q));
+    let page = query.page.unwrap_or(1).max(1);
+    let page_size = query.page_size.unwrap_or(20).clamp(1, 200);
+    let offset = (page - 1) * page_size;
+
+    let total = sqlx::query_scalar!(
+        r#"SELECT count(*) AS "count!" FROM notification_tasks
+           WHERE status = ANY($1)
+             AND ($2::INT IS NULL OR assignee_id = $2)
+             AND (NOT $3 OR assignee_id IS NULL)
+             AND ($4::TEXT[] IS NULL OR task_type = ANY($4))
+             AND ($5::INT IS NULL OR submitter_id = $5)
+             AND ($6::TEXT IS NULL OR payload::TEXT ILIKE $6)"#,
+        &statuses, assignee_id, unassigned, task_types.as_deref(), query.submitter_id, pattern
+    )
+    .fetch_one(&state.db)
+    .await;
+    let total = match total {
+        Ok(t) => t,
+        Err(e) => return (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()).into_response(),
+    };
+
     let result = sqlx::query_as!(
         NotificationTask,
-        "SELECT id, task_type, payload, is_read as \"is_read!\", created_at as \"created_at!\" 
-         FROM notification_tasks WHERE is_read = false ORDER BY created_at DESC"
+        r#"SELECT id, task_type, payload as "payload!", is_read as "is_read!", status, submitter_id, vote_count, assignee_id, resolution_note,
+                  resolved_root_id, resolved_field_id, duplicate_of_id, resolved_by, resolved_at, updated_at,
+                  created_at as "created_at!"
+           FROM notification_tasks
+           WHERE status = ANY($1)
+             AND ($2::INT IS NULL OR assignee_id = $2)
+             AND (NOT $3 OR assignee_id IS NULL)
+             AND ($4::TEXT[] IS NULL OR task_type = ANY($4))
+             AND ($5::INT IS NULL OR submitter_id = $5)
+             AND ($6::TEXT IS NULL OR payload::TEXT ILIKE $6)
+           ORDER BY created_at DESC, id DESC
+           LIMIT $7 OFFSET $8"#,
+        &statuses, assignee_id, unassigned, task_types.as_deref(), query.submitter_id, pattern, page_size, offset
     )
     .fetch_all(&state.db)
     .await;
 
     match result {
-        Ok(tasks) => Json(tasks).into_response(),
+        Ok(items) => Json(PaginatedResponse { items, total }).into_response(),
         Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()).into_response(),
     }
 }
 
-/// 管理员标记任务为已处理
+/// 积压与时效统计：各类型未结束数、处理时长中位数、超期未处理的申请
+pub async fn task_stats(
+    State(state): State<Arc<AppState>>,
+    Query(query): Query<TaskStatsQuery>,
+) -> impl IntoResponse {
+    let older_than_days = query.older_than_days.unwrap_or(7).max(0);
+    let window_days = query.window_days.unwrap_or(90).clamp(1, 3650);
+    match task_report_service::stats(&state.db, older_than_days, window_days).await {
+        Ok(stats) => Json(stats).into_response(),
+        Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()).into_response(),
+    }
+}
+
+/// 吞吐报表：区间内按申请人、处理人与周汇总
+pub async fn task_report(
+    State(state): State<Arc<AppState>>,
+    Query(query): Query<TaskReportQuery>,
+) -> impl IntoResponse {
+    let to = query.to.unwrap_or_else(|| chrono::Utc::now().date_naive());
+    let from = query.from.unwrap_or(to - chrono::Duration::days(29));
+    if from > to {
+        return (StatusCode::BAD_REQUEST, "from 不能晚于 to").into_response();
+    }
+    if (to - from).num_days() > 366 {
+        return (StatusCode::BAD_REQUEST, "统计区间不能超过一年").into_response();
+    }
+    match task_report_service::throughput(&state.db, from, to).await {
+        Ok(report) => Json(report).into_response(),
+        Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()).into_response(),
+    }
+}
+
+/// 获取任务详情及评论
+pub async fn get_task(State(state): State<Arc<AppState>>, Path(id): Path<i32>) -> impl IntoResponse {
+    let task = match fetch_task(&state, id).await {
+        Ok(Some(t)) => t,
+        Ok(None) => return StatusCode::NOT_FOUND.into_response(),
+        Err(e) => return (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()).into_response(),
+    };
+    match fetch_comments(&state, id).await {
+        Ok(comments) => Json(TaskDetail { task, comments }).into_response(),
+        Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()).into_response(),
+    }
+}
+
+/// 管理员处理任务：流转状态、指派、填写处理说明、关联满足申请的词根或字段
+/// 请求体为空时沿用旧行为，直接标记为已采纳；请求体格式错误时返回 400
+/// 已结束的任务 (已采纳 / 已驳回 / 重复) 不能再修改，返回 409
 pub async fn complete_task(
     State(state): State<Arc<AppState>>,
+    Extension(claims): Extension<Claims>,
     Path(id): Path<i32>,
+    body: Bytes,
 ) -> impl IntoResponse {
-    let res = sqlx::query!("UPDATE notification_tasks SET is_read = true WHERE id = $1", id)
-        .execute(&state.db)
-        .await;
+    let payload = match optional_body::<UpdateTaskPayload>(&body) {
+        Ok(Some(p)) => p,
+        Ok(None) => UpdateTaskPayload { status: Some("approved".to_string()), ..Default::default() },
+        Err(resp) => return resp.into_response(),
+    };
 
-    match res {
-        Ok(_) => StatusCode::OK.into_response(),
+    let status = payload.status.clone();
+    if let Some(s) = status.as_deref().filter(|s| !TASK_STATUSES.contains(s)) {
+        return (StatusCode::BAD_REQUEST, format!("不支持的状态 [{}]，可选: {:?}", s, TASK_STATUSES)).into_response();
+    }
+
+    // 锁定任务后校验状态流转与关联对象，并发处理同一任务时只有一方成功
+    let result: Result<Result<(NotificationTask, String), Rejection>, sqlx::Error> = async {
+        let mut tx = state.db.begin().await?;
+        let current = sqlx::query_as!(
+            NotificationTask,
+            r#"SELECT id, task_type, payload as "payload!", is_read as "is_read!", status, submitter_id, vote_count, assignee_id, resolution_note,
+                      resolved_root_id, resolved_field_id, duplicate_of_id, resolved_by, resolved_at, updated_at,
+                      created_at as "created_at!"
+               FROM notification_tasks WHERE id = $1 FOR UPDATE"#,
+            id
+        )
+        .fetch_optional(&mut *tx)
+        .await?;
+        let Some(current) = current else {
+            return Ok(Err((StatusCode::NOT_FOUND, format!("任务 ID={} 不存在", id))));
+        };
+        if TERMINAL_STATUSES.contains(&current.status.as_str()) {
+            return Ok(Err((StatusCode::CONFLICT, format!("任务已结束 (状态: {})，不能再修改", current.status))));
+        }
+        let status = status.unwrap_or_else(|| current.status.clone());
+
+        let duplicate_of_id = if status == "duplicate" {
+            let Some(dup) = payload.duplicate_of_id.or(current.duplicate_of_id) else {
+                return Ok(Err((StatusCode::BAD_REQUEST, "标记重复时必须指定 duplicate_of_id".to_string())));
+            };
+            if dup == id {
+                return Ok(Err((StatusCode::BAD_REQUEST, "不能标记为与自身重复".to_string())));
+            }
+            let original = sqlx::query_scalar!("SELECT status FROM notification_tasks WHERE id = $1", dup)
+                .fetch_optional(&mut *tx)
+                .await?;
+            match original.as_deref() {
+                None => return Ok(Err((StatusCode::BAD_REQUEST, format!("原申请 ID={} 不存在", dup)))),
+                Some("duplicate") => {
+                    return Ok(Err((StatusCode::BAD_REQUEST, format!("原申请 ID={} 本身是重复申请，请指向最初的申请", dup))));
+                }
+                Some(_) => Some(dup),
+            }
+        } else {
+            None
+        };
+        if let Some(root_id) = payload.resolved_root_id {
+            let exists = sqlx::query_scalar!(r#"SELECT EXISTS (SELECT 1 FROM standard_word_roots WHERE id = $1) AS "exists!""#, root_id)
+                .fetch_one(&mut *tx)
+                .await?;
+            if !exists {
+                return Ok(Err((StatusCode::BAD_REQUEST, format!("词根 ID={} 不存在", root_id))));
+            }
+        }
+        if let Some(field_id) = payload.resolved_field_id {
+            let exists = sqlx::query_scalar!(r#"SELECT EXISTS (SELECT 1 FROM standard_fields WHERE id = $1) AS "exists!""#, field_id)
+                .fetch_one(&mut *tx)
+                .await?;
+            if !exists {
+                return Ok(Err((StatusCode::BAD_REQUEST, format!("标准字段 ID={} 不存在", field_id))));
+            }
+        }
+
+        // 开始处理且无人负责时，默认指派给当前操作人
+        let assignee_id = match payload.assignee_id {
+            Some(a) => a,
+            None if status == "in_progress" && current.assignee_id.is_none() => Some(claims.sub),
+            None => current.assignee_id,
+        };
+        let terminal = TERMINAL_STATUSES.contains(&status.as_str());
+        let (resolved_by, resolved_at) = if terminal { (Some(claims.sub), Some(chrono::Utc::now())) } else { (None, None) };
+
+        let task = sqlx::query_as!(
+            NotificationTask,
+            r#"UPDATE notification_tasks
+               SET status = $1, assignee_id = $2, resolution_note = COALESCE($3, resolution_note),
+                   resolved_root_id = COALESCE($4, resolved_root_id), resolved_field_id = COALESCE($5, resolved_field_id),
+                   duplicate_of_id = $6, resolved_by = $7, resolved_at = $8, is_read = $9, updated_at = CURRENT_TIMESTAMP
+               WHERE id = $10
+               RETURNING id, task_type, payload as "payload!", is_read as "is_read!", status, submitter_id, vote_count, assignee_id, resolution_note,
+                         resolved_root_id, resolved_field_id, duplicate_of_id, resolved_by, resolved_at, updated_at,
+                         created_at as "created_at!""#,
+            status, assignee_id, payload.resolution_note, payload.resolved_root_id, payload.resolved_field_id,
+            duplicate_of_id, resolved_by, resolved_at, terminal, id
+        )
+        .fetch_one(&mut *tx)
+        .await?;
+        tx.commit().await?;
+        Ok(Ok((task, current.status)))
+    }
+    .await;
+
+    match result {
+        Ok(Ok((task, old_status))) => {
+            if let Some(content) = payload.comment.as_deref().map(str::trim).filter(|c| !c.is_empty()) {
+                if let Err(e) = insert_comment(&state, id, claims.sub, content).await {
+                    tracing::error!("!!! 任务 ID={} 评论写入失败: {}", id, e);
+                }
+            }
+            let resolved = TERMINAL_STATUSES.contains(&task.status.as_str());
+            if resolved {
+                notify_submitter(&state, &task).await;
+                webhook_service::emit(&state.db, &format!("task.{}", task.status), &task).await;
+            }
+            let kind = if resolved { "task_resolved" } else { "task_updated" };
+            match state.db.acquire().await {
+                Ok(mut conn) => event_bus::publish_task_event(&mut conn, kind, task.id, &task.task_type, &task.status).await,
+                Err(e) => tracing::warn!("--- 事件通知发送失败: {}", e),
+            }
+            tracing::info!("<<< 任务 ID={} 状态: {} -> {}, 操作人={}", id, old_status, task.status, claims.sub);
+            Json(task).into_response()
+        }
+        Ok(Err(resp)) => resp.into_response(),
         Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()).into_response(),
     }
 }
 
+#[derive(Deserialize)]
+pub struct DraftQuery {
+    pub profile: Option<String>, // 命名规范名称，缺省使用默认规范
+}
+
+/// 字段草稿：与创建字段的请求体一致，管理员可修改后直接提交
+#[derive(Serialize)]
+pub struct FieldDraft {
+    pub field_cn_name: String,
+    pub field_en_name: String,
+    pub composition_ids: Vec<i32>,
+    pub data_type: Option<String>,
+    pub associated_terms: Option<String>,
+}
+
+#[derive(Serialize)]
+pub struct FieldDraftResponse {
+    pub task_id: i32,
+    pub draft: FieldDraft,
+    pub profile: String,
+    pub missing_words: Vec<String>, // 缺失的词根，需先补充词根再确认
+    pub warnings: Vec<String>,
+    pub shortened: bool,
+    pub existing_field_id: Option<i32>, // 已存在同名标准字段时给出，可直接标记处理
+}
+
+#[derive(Deserialize)]
+pub struct FulfilPayload {
+    #[serde(flatten)]
+    pub field: CreateFieldRequest,
+    pub resolution_note: Option<String>,
+}
+
+#[derive(Serialize)]
+pub struct FulfilResponse {
+    pub field: StandardField,
+    pub task: NotificationTask,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub compliance: Option<ComplianceReport>,
+}
+
+// 校验任务可以被一键处理：类型匹配且未结束，返回任务及解析后的申请
+async fn fetch_open_task(
+    state: &AppState,
+    id: i32,
+    task_type: &str,
+) -> Result<(NotificationTask, TaskRequest), axum::response::Response> {
+    let task = match fetch_task(state, id).await {
+        Ok(Some(t)) => t,
+        Ok(None) => return Err(StatusCode::NOT_FOUND.into_response()),
+        Err(e) => return Err((StatusCode::INTERNAL_SERVER_ERROR, e.to_string()).into_response()),
+    };
+    if task.task_type != task_type {
+        return Err((StatusCode::BAD_REQUEST, format!("任务类型为 [{}]，该操作仅适用于 {}", task.task_type, task_type)).into_response());
+    }
+    if TERMINAL_STATUSES.contains(&task.status.as_str()) {
+        return Err((StatusCode::CONFLICT, format!("任务已结束 (状态: {})", task.status)).into_response());
+    }
+    let request = TaskRequest::from_row(&task.task_type, &task.payload)
+        .map_err(|e| (StatusCode::UNPROCESSABLE_ENTITY, format!("申请内容无法解析: {}", e)).into_response())?;
+    Ok((task, request))
+}
+
+// 以已采纳关闭仍未结束的任务并通知申请人及附议用户 (在事务中调用)；任务已被他人处理时返回 None
+async fn close_task(
+    conn: &mut sqlx::PgConnection,
+    id: i32,
+    actor_id: i32,
+    resolved_root_id: Option<i32>,
+    resolved_field_id: Option<i32>,
+    note: &str,
+) -> Result<Option<NotificationTask>, sqlx::Error> {
+    let task = sqlx::query_as!(
+        NotificationTask,
+        r#"UPDATE notification_tasks
+           SET status = 'approved', resolved_root_id = COALESCE($1, resolved_root_id),
+               resolved_field_id = COALESCE($2, resolved_field_id), resolution_note = $3,
+               resolved_by = $4, resolved_at = CURRENT_TIMESTAMP, is_read = true, updated_at = CURRENT_TIMESTAMP,
+               assignee_id = COALESCE(assignee_id, $4)
+           WHERE id = $5 AND status IN ('open', 'in_progress')
+           RETURNING id, task_type, payload as "payload!", is_read as "is_read!", status, submitter_id, vote_count, assignee_id, resolution_note,
+                     resolved_root_id, resolved_field_id, duplicate_of_id, resolved_by, resolved_at, updated_at,
+                     created_at as "created_at!""#,
+        resolved_root_id, resolved_field_id, note, actor_id, id
+    )
+    .fetch_optional(&mut *conn)
+    .await?;
+    if let Some(task) = &task {
+        let (title, content) = notification_service::task_resolved_message(&task.status, &task_subject(task), Some(note));
+        notification_service::notify_task_followers(&mut *conn, task.id, "TASK_RESOLVED", &title, Some(&content)).await?;
+        event_bus::publish_task_event(&mut *conn, "task_resolved", task.id, &task.task_type, &task.status).await;
+    }
+    Ok(task)
+}
+
+/// 为字段申请生成字段草稿 (按申请的中文名调用分词建议)
+pub async fn draft_task_field(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<i32>,
+    Query(query): Query<DraftQuery>,
+) -> impl IntoResponse {
+    let cn_name = match fetch_open_task(&state, id, "FIELD_REQUEST").await {
+        Ok((_, TaskRequest::FieldRequest(p))) => p.field_cn_name,
+        Ok(_) => return (StatusCode::UNPROCESSABLE_ENTITY, "申请内容与任务类型不符").into_response(),
+        Err(resp) => return resp,
+    };
+    let cn_name = cn_name.trim();
+    if cn_name.is_empty() {
+        return (StatusCode::BAD_REQUEST, "申请中缺少字段中文名").into_response();
+    }
+
+    let profile = match naming_service::resolve_profile(&state.db, query.profile.as_deref()).await {
+        Ok(p) => p,
+        Err(msg) => return (StatusCode::BAD_REQUEST, msg).into_response(),
+    };
+    let suggestion = mapping_service::suggest_field_name(&state.db, cn_name, &profile).await;
+    let existing_field_id = sqlx::query_scalar!(
+        "SELECT id FROM standard_fields WHERE field_cn_name = $1 ORDER BY id LIMIT 1",
+        cn_name
+    )
+    .fetch_optional(&state.db)
+    .await
+    .unwrap_or(None);
+
+    tracing::info!(">>> 任务 ID={} 生成字段草稿: {} -> {}", id, cn_name, suggestion.suggested_en);
+    Json(FieldDraftResponse {
+        task_id: id,
+        draft: FieldDraft {
+            field_cn_name: cn_name.to_string(),
+            field_en_name: suggestion.suggested_en,
+            composition_ids: suggestion.matched_ids,
+            data_type: suggestion.data_type,
+            associated_terms: None,
+        },
+        profile: profile.name,
+        missing_words: suggestion.missing_words,
+        warnings: suggestion.warnings,
+        shortened: suggestion.shortened,
+        existing_field_id,
+    })
+    .into_response()
+}
+
+/// 确认字段草稿：创建标准字段、以已采纳关闭任务并关联该字段、通知申请人
+/// 命名校验参数与创建字段接口一致 (?check=&profile=)
+pub async fn fulfil_task(
+    State(state): State<Arc<AppState>>,
+    Extension(claims): Extension<Claims>,
+    Path(id): Path<i32>,
+    Query(query): Query<FieldWriteQuery>,
+    Json(payload): Json<FulfilPayload>,
+) -> impl IntoResponse {
+    if let Err(resp) = fetch_open_task(&state, id, "FIELD_REQUEST").await {
+        return resp;
+    }
+    let compliance = match field_handler::run_name_check(&state, &payload.field, &query, None).await {
+        Ok(report) => report,
+        Err(resp) => return resp,
+    };
+    let data_type = field_handler::resolve_data_type(&state, &payload.field).await;
+    let note = payload
+        .resolution_note
+        .clone()
+        .unwrap_or_else(|| format!("已创建标准字段 {}", payload.field.field_en_name));
+
+    let result: Result<Option<(StandardField, NotificationTask)>, sqlx::Error> = async {
+        let mut tx = state.db.begin().await?;
+        let field = field_handler::insert_field(&mut *tx, &payload.field, data_type).await?;
+        // 只关闭仍未结束的任务，防止并发重复处理
+        let Some(task) = close_task(&mut tx, id, claims.sub, None, Some(field.id), &note).await? else {
+            tx.rollback().await?;
+            return Ok(None);
+        };
+        tx.commit().await?;
+        Ok(Some((field, task)))
+    }
+    .await;
+
+    match result {
+        Ok(Some((field, task))) => {
+            tracing::info!("<<< 任务 ID={} 已一键处理: 字段 ID={}, en_name={}", id, field.id, field.field_en_name);
+            dictionary_service::on_fields_changed(&state.db).await;
+            webhook_service::emit(&state.db, "field.created", &field).await;
+            webhook_service::emit(&state.db, "task.approved", &task).await;
+            (StatusCode::CREATED, Json(FulfilResponse { field, task, compliance })).into_response()
+        }
+        Ok(None) => (StatusCode::CONFLICT, "任务已被其他人处理").into_response(),
+        Err(e) => {
+            tracing::error!("!!! 任务 ID={} 一键处理失败: {}", id, e);
+            (StatusCode::INTERNAL_SERVER_ERROR, format!("数据库错误: {}", e)).into_response()
+        }
+    }
+}
+
+#[derive(Deserialize, Default)]
+pub struct ApplyNotePayload {
+    pub resolution_note: Option<String>,
+}
+
+#[derive(Deserialize)]
+pub struct ApplyRootPayload {
+    #[serde(flatten)]
+    pub root: CreateWordRoot, // 管理员按申请确认后的词根
+    pub resolution_note: Option<String>,
+}
+
+#[derive(Deserialize, Default)]
+pub struct ApplySynonymPayload {
+    pub syn_type: Option<String>, // 缺省沿用申请中的类型
+    pub weight: Option<i32>,
+    pub resolution_note: Option<String>,
+}
+
+#[derive(Serialize)]
+pub struct ApplyResponse<T: Serialize> {
+    pub result: T,
+    pub task: NotificationTask,
+}
+
+fn apply_error(id: i32, e: sqlx::Error) -> axum::response::Response {
+    tracing::error!("!!! 任务 ID={} 处理失败: {}", id, e);
+    (StatusCode::INTERNAL_SERVER_ERROR, format!("数据库错误: {}", e)).into_response()
+}
+
+/// 处理新增词根申请：按管理员确认的内容创建词根 (同创建词根接口，支持 ?force=true)，关闭任务并关联词根
+/// 先锁定任务再写入词根，词根与任务状态在同一事务中提交，任务已被他人处理时不会留下词根
+pub async fn apply_root_request(
+    State(state): State<Arc<AppState>>,
+    Extension(claims): Extension<Claims>,
+    Path(id): Path<i32>,
+    Query(query): Query<CreateRootQuery>,
+    Json(payload): Json<ApplyRootPayload>,
+) -> impl IntoResponse {
+    if let Err(resp) = fetch_open_task(&state, id, "ROOT_REQUEST").await {
+        return resp;
+    }
+    let terms = match word_root_handler::check_new_root(&state, &payload.root, query.force.unwrap_or(false)).await {
+        Ok(terms) => terms,
+        Err(resp) => return resp,
+    };
+
+    let result: Result<Option<(WordRoot, NotificationTask)>, sqlx::Error> = async {
+        let mut tx = state.db.begin().await?;
+        let locked = sqlx::query_scalar!(
+            "SELECT id FROM notification_tasks WHERE id = $1 AND status IN ('open', 'in_progress') FOR UPDATE",
+            id
+        )
+        .fetch_optional(&mut *tx)
+        .await?;
+        if locked.is_none() {
+            tx.rollback().await?;
+            return Ok(None);
+        }
+        let root = word_root_handler::insert_root_row(&mut tx, &payload.root, &terms).await?;
+        let note = payload
+            .resolution_note
+            .clone()
+            .unwrap_or_else(|| format!("已创建词根 {} ({})", root.cn_name, root.en_abbr));
+        let Some(task) = close_task(&mut tx, id, claims.sub, Some(root.id), None, &note).await? else {
+            tx.rollback().await?;
+            return Ok(None);
+        };
+        tx.commit().await?;
+        Ok(Some((root, task)))
+    }
+    .await;
+
+    match result {
+        Ok(Some((root, task))) => {
+            tracing::info!("<<< 任务 ID={} 已处理: 新增词根 ID={}", id, root.id);
+            word_root_handler::after_root_created(&state, &root).await;
+            webhook_service::emit(&state.db, "task.approved", &task).await;
+            (StatusCode::CREATED, Json(ApplyResponse { result: root, task })).into_response()
+        }
+        Ok(None) => (StatusCode::CONFLICT, "任务已被其他人处理").into_response(),
+        Err(e) => apply_error(id, e),
+    }
+}
+
+/// 处理字段修改申请：字段自提交后被改动过时返回 409，否则按申请的差异更新字段并关闭任务
+/// 名称变化时按新的英文名重新拆分组成词根；命名校验参数与创建字段接口一致 (?check=&profile=)
+pub async fn apply_field_update(
+    State(state): State<Arc<AppState>>,
+    Extension(claims): Extension<Claims>,
+    Path(id): Path<i32>,
+    Query(query): Query<FieldWriteQuery>,
+    body: Bytes,
+) -> impl IntoResponse {
+    let body = match optional_body::<ApplyNotePayload>(&body) {
+        Ok(b) => b,
+        Err(resp) => return resp.into_response(),
+    };
+    let request = match fetch_open_task(&state, id, "FIELD_UPDATE").await {
+        Ok((_, TaskRequest::FieldUpdate(p))) => p,
+        Ok(_) => return (StatusCode::UNPROCESSABLE_ENTITY, "申请内容与任务类型不符").into_response(),
+        Err(resp) => return resp,
+    };
+    let note = body
+        .and_then(|b| b.resolution_note)
+        .unwrap_or_else(|| format!("已按申请修改字段 ID={}", request.field_id));
+
+    // 校验与命名检查在加锁前完成，事务内确认字段未被改动后再写入
+    let field = sqlx::query!(
+        r#"SELECT field_cn_name, field_en_name, composition_ids as "composition_ids!", data_type, associated_terms
+           FROM standard_fields WHERE id = $1"#,
+        request.field_id
+    )
+    .fetch_optional(&state.db)
+    .await;
+    let field = match field {
+        Ok(Some(f)) => f,
+        Ok(None) => return (StatusCode::CONFLICT, format!("标准字段 ID={} 已被删除", request.field_id)).into_response(),
+        Err(e) => return apply_error(id, e),
+    };
+
+    // 按差异构造更新后的字段；提交后被改动过的属性视为冲突
+    let mut update = CreateFieldRequest {
+        field_cn_name: field.field_cn_name.clone(),
+        field_en_name: field.field_en_name.clone(),
+        composition_ids: field.composition_ids.clone(),
+        data_type: field.data_type.clone(),
+        associated_terms: field.associated_terms.clone(),
+    };
+    let mut stale = Vec::new();
+    for d in &request.diff {
+        let slot = match d.column.as_str() {
+            "field_cn_name" => &mut update.field_cn_name,
+            "field_en_name" => &mut update.field_en_name,
+            "data_type" => update.data_type.get_or_insert_with(String::new),
+            "associated_terms" => update.associated_terms.get_or_insert_with(String::new),
+            other => {
+                return (StatusCode::UNPROCESSABLE_ENTITY, format!("申请包含不支持修改的属性 [{}]", other)).into_response();
+            }
+        };
+        if slot.as_str() != d.old.as_deref().unwrap_or("") {
+            stale.push(d.column.clone());
+        }
+        *slot = d.new.clone().unwrap_or_default();
+    }
+    if !stale.is_empty() {
+        return (StatusCode::CONFLICT, format!("字段在申请提交后已被修改: {:?}，请驳回或重新提交申请", stale)).into_response();
+    }
+    update.data_type = update.data_type.filter(|v| !v.is_empty());
+    update.associated_terms = update.associated_terms.filter(|v| !v.is_empty());
+
+    // 名称变化时按新的英文名重新拆分组成词根，无法完整拆分时拒绝
+    if update.field_cn_name != field.field_cn_name || update.field_en_name != field.field_en_name {
+        let reverse = match reverse_service::reverse_translate(&state.db, &update.field_en_name).await {
+            Ok(r) => r,
+            Err(e) => return apply_error(id, e),
+        };
+        if !reverse.unknown_pieces.is_empty() || reverse.matched_roots.is_empty() {
+            return (
+                StatusCode::UNPROCESSABLE_ENTITY,
+                format!("字段英文名 [{}] 无法拆分为已有词根: {:?}", update.field_en_name, reverse.unknown_pieces),
+            )
+                .into_response();
+        }
+        update.composition_ids = reverse.matched_roots.iter().filter_map(|s| s.root_id).collect();
+    }
+
+    let compliance = match field_handler::run_name_check(&state, &update, &query, Some(request.field_id)).await {
+        Ok(report) => report,
+        Err(resp) => return resp,
+    };
+
+    let result: Result<Result<NotificationTask, &'static str>, sqlx::Error> = async {
+        let mut tx = state.db.begin().await?;
+        let current = sqlx::query!(
+            r#"SELECT field_cn_name, field_en_name, composition_ids as "composition_ids!", data_type, associated_terms
+               FROM standard_fields WHERE id = $1 FOR UPDATE"#,
+            request.field_id
+        )
+        .fetch_optional(&mut *tx)
+        .await?;
+        let unchanged = current.is_some_and(|c| {
+            (c.field_cn_name, c.field_en_name, c.composition_ids, c.data_type, c.associated_terms)
+                == (field.field_cn_name, field.field_en_name, field.composition_ids, field.data_type, field.associated_terms)
+        });
+        if !unchanged {
+            tx.rollback().await?;
+            return Ok(Err("字段在处理期间被修改或删除，请重试"));
+        }
+        field_handler::update_field_row(&mut *tx, request.field_id, &update).await?;
+        let Some(task) = close_task(&mut tx, id, claims.sub, None, Some(request.field_id), &note).await? else {
+            tx.rollback().await?;
+            return Ok(Err("任务已被其他人处理"));
+        };
+        tx.commit().await?;
+        Ok(Ok(task))
+    }
+    .await;
+
+    match result {
+        Ok(Ok(task)) => {
+            tracing::info!("<<< 任务 ID={} 已处理: 修改字段 ID={}", id, request.field_id);
+            dictionary_service::on_fields_changed(&state.db).await;
+            webhook_service::emit(&state.db, "field.updated", &field_handler::field_event(request.field_id, &update)).await;
+            webhook_service::emit(&state.db, "task.approved", &task).await;
+            Json(serde_json::json!({ "task": task, "diff": request.diff, "compliance": compliance })).into_response()
+        }
+        Ok(Err(msg)) => (StatusCode::CONFLICT, msg).into_response(),
+        Err(e) => apply_error(id, e),
+    }
+}
+
+/// 处理废弃申请：将词根或标准字段标记为废弃，备注中记录原因与替代对象，并关闭任务
+pub async fn apply_deprecation(
+    State(state): State<Arc<AppState>>,
+    Extension(claims): Extension<Claims>,
+    Path(id): Path<i32>,
+    body: Bytes,
+) -> impl IntoResponse {
+    let body = match optional_body::<ApplyNotePayload>(&body) {
+        Ok(b) => b,
+        Err(resp) => return resp.into_response(),
+    };
+    let request = match fetch_open_task(&state, id, "DEPRECATION_REQUEST").await {
+        Ok((_, TaskRequest::DeprecationRequest(p))) => p,
+        Ok(_) => return (StatusCode::UNPROCESSABLE_ENTITY, "申请内容与任务类型不符").into_response(),
+        Err(resp) => return resp,
+    };
+    let is_root = request.target == "root";
+    let note = body
+        .and_then(|b| b.resolution_note)
+        .unwrap_or_else(|| format!("已废弃{} ID={}", if is_root { "词根" } else { "字段" }, request.target_id));
+
+    let result: Result<Option<NotificationTask>, sqlx::Error> = async {
+        let mut tx = state.db.begin().await?;
+        let affected = if is_root {
+            let mut remark = format!("申请废弃 (任务 ID={})：{}", id, request.reason.trim());
+            if let Some(rid) = request.replacement_id {
+                remark.push_str(&format!("，建议使用词根 ID={}", rid));
+            }
+            sqlx::query!(
+                r#"UPDATE standard_word_roots SET status = 'deprecated',
+                          remark = concat_ws('；', NULLIF(remark, ''), $2::TEXT)
+                   WHERE id = $1 AND status = 'active'"#,
+                request.target_id, remark
+            )
+            .execute(&mut *tx)
+            .await?
+            .rows_affected()
+        } else {
+            sqlx::query!(
+                "UPDATE standard_fields SET status = 'deprecated' WHERE id = $1 AND status = 'active'",
+                request.target_id
+            )
+            .execute(&mut *tx)
+            .await?
+            .rows_affected()
+        };
+        if affected == 0 {
+            tx.rollback().await?;
+            return Ok(None);
+        }
+        let (root_id, field_id) = if is_root { (Some(request.target_id), None) } else { (None, Some(request.target_id)) };
+        let Some(task) = close_task(&mut tx, id, claims.sub, root_id, field_id, &note).await? else {
+            tx.rollback().await?;
+            return Ok(None);
+        };
+        tx.commit().await?;
+        Ok(Some(task))
+    }
+    .await;
+
+    match result {
+        Ok(Some(task)) => {
+            tracing::info!("<<< 任务 ID={} 已处理: 废弃 {} ID={}", id, request.target, request.target_id);
+            if is_root {
+                dictionary_service::on_roots_changed(&state.db).await;
+            } else {
+                dictionary_service::on_fields_changed(&state.db).await;
+            }
+            let event = if is_root { "root.deprecated" } else { "field.deprecated" };
+            webhook_service::emit(&state.db, event, &serde_json::json!({
+                "id": request.target_id, "reason": request.reason, "replacement_id": request.replacement_id,
+            })).await;
+            webhook_service::emit(&state.db, "task.approved", &task).await;
+            Json(task).into_response()
+        }
+        Ok(None) => (StatusCode::CONFLICT, "任务已被其他人处理，或对象已不存在 / 已废弃").into_response(),
+        Err(e) => apply_error(id, e),
+    }
+}
+
+/// 处理同义词建议：为词根新增同义词 (来源记为 user_request)，并关闭任务
+pub async fn apply_synonym_suggestion(
+    State(state): State<Arc<AppState>>,
+    Extension(claims): Extension<Claims>,
+    Path(id): Path<i32>,
+    body: Bytes,
+) -> impl IntoResponse {
+    let body = match optional_body::<ApplySynonymPayload>(&body) {
+        Ok(b) => b,
+        Err(resp) => return resp.into_response(),
+    };
+    let request = match fetch_open_task(&state, id, "SYNONYM_SUGGESTION").await {
+        Ok((_, TaskRequest::SynonymSuggestion(p))) => p,
+        Ok(_) => return (StatusCode::UNPROCESSABLE_ENTITY, "申请内容与任务类型不符").into_response(),
+        Err(resp) => return resp,
+    };
+    let options = body.unwrap_or_default();
+    let synonym = crate::models::synonym::CreateSynonym {
+        term: request.term.trim().to_string(),
+        syn_type: options.syn_type.or(request.syn_type),
+        weight: options.weight,
+        source: Some("user_request".to_string()),
+        remark: request.reason,
+    };
+    if let Err(msg) = synonym_handler::check_payload(&synonym) {
+        return (StatusCode::BAD_REQUEST, msg).into_response();
+    }
+    let note = options
+        .resolution_note
+        .unwrap_or_else(|| format!("已为词根 ID={} 新增同义词 {}", request.root_id, synonym.term));
+
+    let result: Result<Option<(Synonym, NotificationTask)>, sqlx::Error> = async {
+        let mut tx = state.db.begin().await?;
+        let created = sqlx::query_as!(
+            Synonym,
+            r#"INSERT INTO word_root_synonyms (root_id, term, syn_type, weight, source, remark)
+               VALUES ($1, $2, COALESCE($3, 'exact'), COALESCE($4, 100), $5, $6)
+               RETURNING id, root_id, term, syn_type, weight, source, remark, created_at"#,
+            request.root_id, synonym.term, synonym.syn_type, synonym.weight, synonym.source, synonym.remark
+        )
+        .fetch_one(&mut *tx)
+        .await?;
+        let Some(task) = close_task(&mut tx, id, claims.sub, Some(request.root_id), None, &note).await? else {
+            tx.rollback().await?;
+            return Ok(None);
+        };
+        tx.commit().await?;
+        Ok(Some((created, task)))
+    }
+    .await;
+
+    match result {
+        Ok(Some((created, task))) => {
+            tracing::info!("<<< 任务 ID={} 已处理: 词根 ID={} 新增同义词 {}", id, request.root_id, created.term);
+            synonym_handler::after_change(&state, request.root_id).await;
+            webhook_service::emit(&state.db, "root.updated", &serde_json::json!({ "id": request.root_id, "synonym": created })).await;
+            webhook_service::emit(&state.db, "task.approved", &task).await;
+            (StatusCode::CREATED, Json(ApplyResponse { result: created, task })).into_response()
+        }
+        Ok(None) => (StatusCode::CONFLICT, "任务已被其他人处理").into_response(),
+        Err(e) => {
+            tracing::error!("!!! 任务 ID={} 同义词写入失败: {}", id, e);
+            (StatusCode::BAD_REQUEST, format!("新增失败，词根不存在或同义词已存在: {}", e)).into_response()
+        }
+    }
+}
+
+/// 为任务添加评论
+pub async fn add_comment(
+    State(state): State<Arc<AppState>>,
+    Extension(claims): Extension<Claims>,
+    Path(id): Path<i32>,
+    Json(payload): Json<CreateCommentPayload>,
+) -> impl IntoResponse {
+    let content = payload.content.trim();
+    if content.is_empty() {
+        return (StatusCode::BAD_REQUEST, "评论内容不能为空").into_response();
+    }
+    match insert_comment(&state, id, claims.sub, content).await {
+        Ok(_) => {
+            let task = sqlx::query!(
+                "UPDATE notification_tasks SET updated_at = CURRENT_TIMESTAMP WHERE id = $1 RETURNING task_type, status",
+                id
+            )
+            .fetch_optional(&state.db)
+            .await;
+            if let Ok(Some(t)) = task {
+                match state.db.acquire().await {
+                    Ok(mut conn) => event_bus::publish_task_event(&mut conn, "task_updated", id, &t.task_type, &t.status).await,
+                    Err(e) => tracing::warn!("--- 事件通知发送失败: {}", e),
+                }
+            }
+            StatusCode::CREATED.into_response()
+        }
+        Err(e) => (StatusCode::BAD_REQUEST, format!("评论失败，任务可能不存在: {}", e)).into_response(),
+    }
+}
+
+/// 未结束 (待处理与处理中) 的任务数
 pub async fn count_unprocessed_tasks(State(state): State<Arc<AppState>>) -> impl IntoResponse {
     let res = sqlx::query_scalar!(
-        "SELECT count(*) FROM notification_tasks WHERE is_read = false"
+        "SELECT count(*) FROM notification_tasks WHERE status IN ('open', 'in_progress')"
     )
     .fetch_one(&state.db)
     .await;
@@ -83,4 +1215,99 @@ pub async fn count_unprocessed_tasks(State(state): State<Arc<AppState>>) -> impl
         Ok(count) => Json(serde_json::json!({ "count": count.unwrap_or(0) })).into_response(),
         Err(_) => (StatusCode::INTERNAL_SERVER_ERROR, "查询失败").into_response(),
     }
-}
\ No newline at end of file
+}
+
+/// 申请最终产出的标准字段
+#[derive(Serialize)]
+pub struct ResolvedField {
+    pub id: i32,
+    pub field_cn_name: String,
+    pub field_en_name: String,
+    pub data_type: Option<String>,
+}
+
+/// 申请人视角的申请记录
+#[derive(Serialize)]
+pub struct MyTask {
+    pub id: i32,
+    pub task_type: String,
+    pub payload: serde_json::Value,
+    pub status: String,
+    pub resolution_note: Option<String>,
+    pub duplicate_of_id: Option<i32>,
+    pub resolved_field: Option<ResolvedField>,
+    pub resolved_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub updated_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+}
+
+#[derive(Serialize)]
+pub struct MyTaskDetail {
+    #[serde(flatten)]
+    pub task: MyTask,
+    pub comments: Vec<TaskComment>,
+}
+
+async fn fetch_my_tasks(state: &AppState, user_id: i32, task_id: Option<i32>) -> Result<Vec<MyTask>, sqlx::Error> {
+    let rows = sqlx::query!(
+        r#"SELECT t.id, t.task_type, t.payload as "payload!", t.status, t.resolution_note, t.duplicate_of_id,
+                  t.resolved_at, t.updated_at, t.created_at as "created_at!",
+                  f.id as "field_id?", f.field_cn_name as "field_cn_name?", f.field_en_name as "field_en_name?",
+                  f.data_type as "data_type?"
+           FROM notification_tasks t
+           LEFT JOIN standard_fields f ON f.id = t.resolved_field_id
+           WHERE (t.submitter_id = $1 OR EXISTS (SELECT 1 FROM task_votes v WHERE v.task_id = t.id AND v.user_id = $1))
+             AND ($2::INT IS NULL OR t.id = $2)
+           ORDER BY t.created_at DESC"#,
+        user_id, task_id
+    )
+    .fetch_all(&state.db)
+    .await?;
+
+    Ok(rows
+        .into_iter()
+        .map(|r| MyTask {
+            resolved_field: match (r.field_id, r.field_cn_name, r.field_en_name) {
+                (Some(id), Some(cn), Some(en)) => Some(ResolvedField { id, field_cn_name: cn, field_en_name: en, data_type: r.data_type }),
+                _ => None,
+            },
+            id: r.id,
+            task_type: r.task_type,
+            payload: r.payload,
+            status: r.status,
+            resolution_note: r.resolution_note,
+            duplicate_of_id: r.duplicate_of_id,
+            resolved_at: r.resolved_at,
+            updated_at: r.updated_at,
+            created_at: r.created_at,
+        })
+        .collect())
+}
+
+/// 当前用户的申请列表 (含附议合并的申请)
+pub async fn list_my_tasks(
+    State(state): State<Arc<AppState>>,
+    Extension(claims): Extension<Claims>,
+) -> impl IntoResponse {
+    match fetch_my_tasks(&state, claims.sub, None).await {
+        Ok(tasks) => Json(tasks).into_response(),
+        Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()).into_response(),
+    }
+}
+
+/// 当前用户的单条申请详情及评论 (只能查看自己提交或附议的申请)
+pub async fn get_my_task(
+    State(state): State<Arc<AppState>>,
+    Extension(claims): Extension<Claims>,
+    Path(id): Path<i32>,
+) -> impl IntoResponse {
+    let task = match fetch_my_tasks(&state, claims.sub, Some(id)).await {
+        Ok(mut tasks) if !tasks.is_empty() => tasks.remove(0),
+        Ok(_) => return StatusCode::NOT_FOUND.into_response(),
+        Err(e) => return (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()).into_response(),
+    };
+    match fetch_comments(&state, id).await {
+        Ok(comments) => Json(MyTaskDetail { task, comments }).into_response(),
+        Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()).into_response(),
+    }
+}
@@ -0,0 +1,16 @@
+use axum::{extract::Query, Json, response::IntoResponse};
+use serde::Deserialize;
+use crate::services::typeahead_service;
+
+#[derive(Deserialize)]
+pub struct TypeaheadQuery {
+    pub q: String,
+    pub limit: Option<usize>,
+}
+
+/// 输入联想：基于内存前缀索引，按前缀匹配字段与词根的中文名、同义词、英文名、拼音全拼及首字母
+/// 索引在字段/词根变更时重建，查询不访问数据库与向量模型
+pub async fn typeahead(Query(query): Query<TypeaheadQuery>) -> impl IntoResponse {
+    let limit = query.limit.unwrap_or(10).clamp(1, 50);
+    Json(typeahead_service::search(&query.q, limit)).into_response()
+}
@@ -0,0 +1,218 @@
+use axum::{extract::{State, Path, Query}, Extension, Json, http::StatusCode, response::IntoResponse};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use crate::AppState;
+use crate::models::user::Claims;
+use crate::services::webhook_service;
+
+#[derive(Serialize)]
+pub struct WebhookSubscription {
+    pub id: i32,
+    pub name: String,
+    pub url: String,
+    pub events: Vec<String>,
+    pub is_active: bool,
+    pub created_by: Option<i32>,
+    pub created_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub updated_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+// 创建订阅时返回一次完整密钥，之后不再展示
+#[derive(Serialize)]
+pub struct CreatedSubscription {
+    #[serde(flatten)]
+    pub subscription: WebhookSubscription,
+    pub secret: String,
+}
+
+#[derive(Deserialize)]
+pub struct WebhookPayload {
+    pub name: String,
+    pub url: String,
+    pub secret: Option<String>,      // 缺省自动生成
+    pub events: Option<Vec<String>>, // 缺省或空数组表示订阅全部事件
+    pub is_active: Option<bool>,
+}
+
+#[derive(Serialize)]
+pub struct WebhookDelivery {
+    pub id: i64,
+    pub subscription_id: i32,
+    pub event_type: String,
+    pub payload: serde_json::Value,
+    pub status: String,
+    pub attempts: i32,
+    pub next_attempt_at: chrono::DateTime<chrono::Utc>,
+    pub last_status_code: Option<i32>,
+    pub last_error: Option<String>,
+    pub replay_of: Option<i64>,
+    pub delivered_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub created_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+#[derive(Deserialize)]
+pub struct DeliveryQuery {
+    pub subscription_id: Option<i32>,
+    pub status: Option<String>,
+    pub limit: Option<i64>, // 缺省 50，最大 500
+}
+
+async fn check_payload(payload: &WebhookPayload) -> Result<Vec<String>, String> {
+    if payload.name.trim().is_empty() {
+        return Err("订阅名称不能为空".to_string());
+    }
+    webhook_service::check_target(payload.url.trim()).await?;
+    let events = payload.events.clone().unwrap_or_default();
+    if let Some(bad) = events.iter().find(|e| !webhook_service::EVENT_TYPES.contains(&e.as_str())) {
+        return Err(format!("不支持的事件类型 [{}]，可选: {:?}", bad, webhook_service::EVENT_TYPES));
+    }
+    Ok(events)
+}
+
+fn generate_secret() -> String {
+    hex::encode(rand::random::<[u8; 32]>())
+}
+
+/// 1. 订阅列表 (不含密钥)
+pub async fn list_webhooks(State(state): State<Arc<AppState>>) -> impl IntoResponse {
+    let result = sqlx::query_as!(
+        WebhookSubscription,
+        "SELECT id, name, url, events, is_active, created_by, created_at, updated_at FROM webhook_subscriptions ORDER BY id"
+    )
+    .fetch_all(&state.db)
+    .await;
+
+    match result {
+        Ok(list) => Json(list).into_response(),
+        Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()).into_response(),
+    }
+}
+
+/// 2. 新增订阅
+pub async fn create_webhook(
+    State(state): State<Arc<AppState>>,
+    Extension(claims): Extension<Claims>,
+    Json(payload): Json<WebhookPayload>,
+) -> impl IntoResponse {
+    let events = match check_payload(&payload).await {
+        Ok(e) => e,
+        Err(msg) => return (StatusCode::BAD_REQUEST, msg).into_response(),
+    };
+    let secret = payload.secret.clone().filter(|s| !s.trim().is_empty()).unwrap_or_else(generate_secret);
+
+    let result = sqlx::query_as!(
+        WebhookSubscription,
+        r#"INSERT INTO webhook_subscriptions (name, url, secret, events, is_active, created_by)
+           VALUES ($1, $2, $3, $4, COALESCE($5, true), $6)
+           RETURNING id, name, url, events, is_active, created_by, created_at, updated_at"#,
+        payload.name.trim(), payload.url.trim(), secret, &events, payload.is_active, claims.sub
+    )
+    .fetch_one(&state.db)
+    .await;
+
+    match result {
+        Ok(subscription) => {
+            tracing::info!("<<< Webhook 订阅已创建: ID={}, url={}", subscription.id, subscription.url);
+            (StatusCode::CREATED, Json(CreatedSubscription { subscription, secret })).into_response()
+        }
+        Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()).into_response(),
+    }
+}
+
+/// 3. 修改订阅；未传密钥时保留原密钥
+pub async fn update_webhook(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<i32>,
+    Json(payload): Json<WebhookPayload>,
+) -> impl IntoResponse {
+    let events = match check_payload(&payload).await {
+        Ok(e) => e,
+        Err(msg) => return (StatusCode::BAD_REQUEST, msg).into_response(),
+    };
+    let secret = payload.secret.as_deref().map(str::trim).filter(|s| !s.is_empty());
+
+    let result = sqlx::query_as!(
+        WebhookSubscription,
+        r#"UPDATE webhook_subscriptions
+           SET name = $1, url = $2, secret = COALESCE($3, secret), events = $4,
+               is_active = COALESCE($5, is_active), updated_at = CURRENT_TIMESTAMP
+           WHERE id = $6
+           RETURNING id, name, url, events, is_active, created_by, created_at, updated_at"#,
+        payload.name.trim(), payload.url.trim(), secret, &events, payload.is_active, id
+    )
+    .fetch_optional(&state.db)
+    .await;
+
+    match result {
+        Ok(Some(subscription)) => Json(subscription).into_response(),
+        Ok(None) => StatusCode::NOT_FOUND.into_response(),
+        Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()).into_response(),
+    }
+}
+
+/// 4. 删除订阅 (投递记录一并删除)
+pub async fn delete_webhook(State(state): State<Arc<AppState>>, Path(id): Path<i32>) -> impl IntoResponse {
+    match sqlx::query!("DELETE FROM webhook_subscriptions WHERE id = $1", id).execute(&state.db).await {
+        Ok(r) if r.rows_affected() == 0 => StatusCode::NOT_FOUND.into_response(),
+        Ok(_) => StatusCode::NO_CONTENT.into_response(),
+        Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()).into_response(),
+    }
+}
+
+/// 5. 发送测试事件 (ping)，用于验证回调地址与签名
+pub async fn ping_webhook(State(state): State<Arc<AppState>>, Path(id): Path<i32>) -> impl IntoResponse {
+    let result = sqlx::query_scalar!(
+        r#"INSERT INTO webhook_deliveries (subscription_id, event_type, payload)
+           SELECT id, 'ping', jsonb_build_object('subscription_id', id) FROM webhook_subscriptions WHERE id = $1
+           RETURNING id"#,
+        id
+    )
+    .fetch_optional(&state.db)
+    .await;
+
+    match result {
+        Ok(Some(delivery_id)) => {
+            webhook_service::wake();
+            (StatusCode::ACCEPTED, Json(serde_json::json!({ "delivery_id": delivery_id }))).into_response()
+        }
+        Ok(None) => StatusCode::NOT_FOUND.into_response(),
+        Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()).into_response(),
+    }
+}
+
+/// 6. 投递记录，可按订阅与状态筛选
+pub async fn list_deliveries(
+    State(state): State<Arc<AppState>>,
+    Query(query): Query<DeliveryQuery>,
+) -> impl IntoResponse {
+    let limit = query.limit.unwrap_or(50).clamp(1, 500);
+    let result = sqlx::query_as!(
+        WebhookDelivery,
+        r#"SELECT id, subscription_id, event_type, payload, status, attempts, next_attempt_at,
+                  last_status_code, last_error, replay_of, delivered_at, created_at
+           FROM webhook_deliveries
+           WHERE ($1::INT IS NULL OR subscription_id = $1) AND ($2::TEXT IS NULL OR status = $2)
+           ORDER BY id DESC
+           LIMIT $3"#,
+        query.subscription_id, query.status, limit
+    )
+    .fetch_all(&state.db)
+    .await;
+
+    match result {
+        Ok(list) => Json(list).into_response(),
+        Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()).into_response(),
+    }
+}
+
+/// 7. 重放投递：按原请求体重新投递一次，生成新的投递记录
+pub async fn replay_delivery(State(state): State<Arc<AppState>>, Path(id): Path<i64>) -> impl IntoResponse {
+    match webhook_service::replay(&state.db, id).await {
+        Ok(Some(new_id)) => {
+            tracing::info!("<<< Webhook 投递 ID={} 已重放为 ID={}", id, new_id);
+            (StatusCode::ACCEPTED, Json(serde_json::json!({ "delivery_id": new_id }))).into_response()
+        }
+        Ok(None) => StatusCode::NOT_FOUND.into_response(),
+        Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()).into_response(),
+    }
+}
@@ -1,10 +1,19 @@
+use crate::models::user::Claims;
 use crate::models::word_root::{CreateWordRoot, WordRoot};
-use crate::{AppState, JIEBA};
+use crate::AppState;
+use crate::services::duplicate_service::{self, RootCandidate, RootConflict};
+use crate::services::abbr_lint::{self, LintConfig, LintIssue};
+use crate::services::merge_service::{self, MergeError, MergePlan, MergeRootsPayload};
+use crate::services::{abbreviation_service, dictionary_service, pinyin_service, synonym_service, webhook_service};
 use axum::{
-    extract::Path, extract::Query, extract::State, http::StatusCode, response::IntoResponse, Json,
+    extract::Path, extract::Query, extract::State, http::StatusCode, response::IntoResponse, Extension, Json,
 };
-use qdrant_client::qdrant::{DeletePointsBuilder, Filter, PointStruct, UpsertPointsBuilder, Value};
+use qdrant_client::qdrant::{
+    DeletePointsBuilder, Filter, PointId, PointStruct, PointsIdsList, SetPayloadPointsBuilder, UpsertPointsBuilder, Value,
+};
+use qdrant_client::Payload;
 use serde::Serialize;
+use sqlx::PgConnection;
 use std::collections::HashMap;
 use std::sync::Arc;
 
@@ -21,6 +30,33 @@ pub struct ImportResult {
     pub errors: Vec<String>,
 }
 
+// 创建词根的参数：force=true 时忽略查重结果强制创建
+#[derive(serde::Deserialize)]
+pub struct CreateRootQuery {
+    pub force: Option<bool>,
+}
+
+// 查重未通过时的 409 响应
+#[derive(Serialize)]
+pub struct RootConflictResponse {
+    pub message: String,
+    pub conflicts: Vec<RootConflict>,
+}
+
+// 缩写不符合规范时的 422 响应
+#[derive(Serialize)]
+pub struct AbbrLintResponse {
+    pub message: String,
+    pub issues: Vec<LintIssue>,
+}
+
+// 缩写候选参数
+#[derive(serde::Deserialize)]
+pub struct AbbrSuggestQuery {
+    pub cn_name: String,
+    pub en_full_name: Option<String>,
+}
+
 // 分页与搜索参数结构
 #[derive(serde::Deserialize)]
 pub struct PaginationQuery {
@@ -36,80 +72,195 @@ pub struct PaginatedResponse<T> {
     pub total: i64,
 }
 
-/// 辅助函数：规范化同义词字符串
-fn normalize_terms(input: Option<String>) -> Option<String> {
-    input.map(|s| {
-        s.replace(',', " ")        // 把英文逗号换成空格
-         .replace('，', " ")       // 把中文逗号换成空格
-         .split_whitespace()       // 按任意空白符切分
-         .collect::<Vec<_>>()
-         .join(" ")                // 用单空格重新连接
-    })
+/// 辅助函数：整理表单中的同义词
+/// 优先使用 synonyms 列表 (同义词可含空格)；allow_legacy 时兼容空格/逗号分隔的 associated_terms，
+/// 旧格式无法表达含空格的同义词，仅用于新建词根。均未传时返回 None
+fn synonym_list(payload: &CreateWordRoot, allow_legacy: bool) -> Result<Option<Vec<String>>, String> {
+    let raw: Vec<String> = match (&payload.synonyms, payload.associated_terms.as_deref()) {
+        (Some(list), _) => list.iter().map(|t| t.trim().to_string()).collect(),
+        (None, Some(terms)) if allow_legacy => terms
+            .split(|c: char| c.is_whitespace() || c == ',' || c == '，')
+            .map(String::from)
+            .collect(),
+        _ => return Ok(None),
+    };
+    let mut list: Vec<String> = Vec::new();
+    for term in raw.into_iter().filter(|t| !t.is_empty()) {
+        if term.chars().count() > 100 {
+            return Err(format!("同义词 [{}] 长度不能超过 100", term));
+        }
+        if !list.contains(&term) {
+            list.push(term);
+        }
+    }
+    Ok(Some(list))
+}
+
+/// 辅助函数：计算词根向量并写入 Qdrant (文本为中文名 + 英文全称 + 同义词)
+pub(crate) async fn upsert_root_vector(state: &AppState, root: &WordRoot) {
+    let text = format!("{} {} {}",
+        root.cn_name,
+        root.en_full_name.as_deref().unwrap_or(""),
+        root.associated_terms.as_deref().unwrap_or("")
+    );
+
+    // 修复：parking_lot 使用同步锁且限定作用域
+    let embeddings_res = {
+        let mut model = state.embed_model.lock();
+        model.embed(vec![text], None)
+    };
+
+    if let Ok(embeddings) = embeddings_res {
+        let mut payload_map: HashMap<String, Value> = HashMap::new();
+        payload_map.insert("cn_name".to_string(), root.cn_name.clone().into());
+        payload_map.insert("en_abbr".to_string(), root.en_abbr.clone().into());
+
+        let point = PointStruct::new(root.id as u64, embeddings[0].clone(), payload_map);
+        let _ = state.qdrant.upsert_points(UpsertPointsBuilder::new("word_roots", vec![point])).await;
+    }
+}
+
+/// 辅助函数：校验词根缩写，不合规时返回 422 响应
+async fn check_abbr(state: &AppState, payload: &CreateWordRoot) -> Result<(), axum::response::Response> {
+    let issues = abbr_lint::lint(&state.db, &payload.en_abbr, payload.en_full_name.as_deref(), &LintConfig::from_env())
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, format!("缩写检查异常: {}", e)).into_response())?;
+    if issues.is_empty() {
+        return Ok(());
+    }
+    tracing::warn!("--- 词根缩写不合规，已拒绝: en_abbr={:?}", payload.en_abbr);
+    let message = format!("英文缩写 [{}] 不符合缩写规范", payload.en_abbr);
+    Err((StatusCode::UNPROCESSABLE_ENTITY, Json(AbbrLintResponse { message, issues })).into_response())
 }
 
 /// 1. 创建单个词根
 pub async fn create_root(
     State(state): State<Arc<AppState>>,
-    Json(mut payload): Json<CreateWordRoot>,
+    Query(query): Query<CreateRootQuery>,
+    Json(payload): Json<CreateWordRoot>,
 ) -> impl IntoResponse {
-    payload.associated_terms = normalize_terms(payload.associated_terms);
-    tracing::info!(">>> 开始创建词根: cn_name={}, en_abbr={}", payload.cn_name, payload.en_abbr);
+    match insert_root_checked(&state, payload, query.force.unwrap_or(false)).await {
+        Ok(root) => (StatusCode::CREATED, Json(root)).into_response(),
+        Err(resp) => resp,
+    }
+}
 
-    let result = sqlx::query_as!(
-        WordRoot,
+/// 辅助函数：新建词根前的校验 (同义词、缩写规范、查重)，返回整理后的同义词
+/// 存在疑似重复时返回 409，管理员确认后可强制创建
+pub(crate) async fn check_new_root(
+    state: &AppState,
+    payload: &CreateWordRoot,
+    force: bool,
+) -> Result<Vec<String>, axum::response::Response> {
+    let terms = synonym_list(payload, true)
+        .map_err(|msg| (StatusCode::BAD_REQUEST, msg).into_response())?
+        .unwrap_or_default();
+
+    check_abbr(state, payload).await?;
+
+    // 查重：存在疑似重复时返回 409，管理员确认后可带 ?force=true 强制创建
+    let candidate = RootCandidate {
+        cn_name: &payload.cn_name,
+        en_abbr: &payload.en_abbr,
+        en_full_name: payload.en_full_name.as_deref(),
+        terms: terms.clone(),
+    };
+    match duplicate_service::find_root_conflicts(state, &candidate).await {
+        Ok(conflicts) if !conflicts.is_empty() => {
+            if force {
+                tracing::warn!("--- 词根 [{}] 存在 {} 处疑似重复，已强制创建", payload.cn_name, conflicts.len());
+            } else {
+                tracing::info!("<<< 词根 [{}] 疑似重复，拒绝创建: {} 处", payload.cn_name, conflicts.len());
+                let message = "存在疑似重复的词根，确认无误后请使用 force=true 强制创建".to_string();
+                return Err((StatusCode::CONFLICT, Json(RootConflictResponse { message, conflicts })).into_response());
+            }
+        }
+        Ok(_) => {}
+        Err(e) => {
+            tracing::error!("!!! 词根查重异常: {}", e);
+            return Err((StatusCode::INTERNAL_SERVER_ERROR, format!("查重异常: {}", e)).into_response());
+        }
+    }
+    Ok(terms)
+}
+
+/// 辅助函数：在调用方事务中写入词根及其同义词，返回含同义词缓存的词根
+pub(crate) async fn insert_root_row(
+    conn: &mut PgConnection,
+    payload: &CreateWordRoot,
+    terms: &[String],
+) -> Result<WordRoot, sqlx::Error> {
+    let root_id = sqlx::query_scalar!(
         r#"
-        INSERT INTO standard_word_roots (cn_name, en_abbr, en_full_name, associated_terms, remark)
-        VALUES ($1, $2, $3, $4, $5)
-        RETURNING id, cn_name, en_abbr, en_full_name, associated_terms, remark, created_at
+        INSERT INTO standard_word_roots (cn_name, en_abbr, en_full_name, remark, status,
+                                         is_class_word, default_data_type, default_length, default_precision,
+                                         pinyin, pinyin_initials)
+        VALUES ($1, $2, $3, $4, COALESCE($5, 'active'), COALESCE($6, false), $7, $8, $9, $10, $11)
+        RETURNING id
         "#,
-        payload.cn_name, payload.en_abbr, payload.en_full_name, payload.associated_terms, payload.remark
+        payload.cn_name, payload.en_abbr, payload.en_full_name, payload.remark, payload.status,
+        payload.is_class_word, payload.default_data_type, payload.default_length, payload.default_precision,
+        pinyin_service::full(&payload.cn_name), pinyin_service::initials(&payload.cn_name)
     )
-    .fetch_one(&state.db)
+    .fetch_one(&mut *conn)
+    .await?;
+
+    synonym_service::replace_synonyms(&mut *conn, root_id, terms).await?;
+    sqlx::query_as!(WordRoot, "SELECT * FROM standard_word_roots WHERE id = $1", root_id)
+        .fetch_one(&mut *conn)
+        .await
+}
+
+/// 辅助函数：词根提交后刷新分词词典、向量并发送 Webhook
+pub(crate) async fn after_root_created(state: &AppState, root: &WordRoot) {
+    // A. 重建分词词典并通知其他实例
+    dictionary_service::on_roots_changed(&state.db).await;
+
+    // B. 计算向量并推送到 Qdrant
+    upsert_root_vector(state, root).await;
+
+    webhook_service::emit(&state.db, "root.created", root).await;
+}
+
+/// 辅助函数：校验缩写、查重后写入词根，并刷新同义词、分词词典与向量
+async fn insert_root_checked(
+    state: &AppState,
+    payload: CreateWordRoot,
+    force: bool,
+) -> Result<WordRoot, axum::response::Response> {
+    tracing::info!(">>> 开始创建词根: cn_name={}, en_abbr={}", payload.cn_name, payload.en_abbr);
+    let terms = check_new_root(state, &payload, force).await?;
+
+    // 词根与同义词在同一事务中写入
+    let result = async {
+        let mut tx = state.db.begin().await?;
+        let root = insert_root_row(&mut tx, &payload, &terms).await?;
+        tx.commit().await?;
+        Ok::<_, sqlx::Error>(root)
+    }
     .await;
 
     match result {
         Ok(root) => {
-            // A. 更新分词
-            let mut jieba_write = JIEBA.write().await;
-            jieba_write.add_word(&root.cn_name, Some(99999), None);
-
-            // B. 计算向量并推送到 Qdrant
-            let text_to_embed = format!("{} {} {}", 
-                root.cn_name, 
-                root.en_full_name.as_deref().unwrap_or(""), 
-                root.associated_terms.as_deref().unwrap_or("")
-            );
-            
-            // 修复：parking_lot 使用同步锁且限定作用域
-            let embeddings_res = {
-                let mut model = state.embed_model.lock();
-                model.embed(vec![text_to_embed], None)
-            };
-
-            if let Ok(embeddings) = embeddings_res {
-                let mut payload_map: HashMap<String, Value> = HashMap::new();
-                payload_map.insert("cn_name".to_string(), root.cn_name.clone().into());
-                payload_map.insert("en_abbr".to_string(), root.en_abbr.clone().into());
-
-                let point = PointStruct::new(root.id as u64, embeddings[0].clone(), payload_map);
-                let _ = state.qdrant.upsert_points(UpsertPointsBuilder::new("word_roots", vec![point])).await;
-            }
-
+            after_root_created(state, &root).await;
             tracing::info!("<<< 词根创建成功: ID={}", root.id);
-            (StatusCode::CREATED, Json(root)).into_response()
+            Ok(root)
         },
         Err(e) => {
             tracing::error!("词根创建失败: {}", e);
-            (StatusCode::INTERNAL_SERVER_ERROR, format!("创建失败: {}", e)).into_response()
+            Err((StatusCode::INTERNAL_SERVER_ERROR, format!("创建失败: {}", e)).into_response())
         }
     }
 }
 
 /// 2. 批量导入词根 (高性能版)
+///    逐条执行缩写检查与查重，疑似重复的行计入失败 (带 ?force=true 时只记录日志并继续导入)
 pub async fn batch_create_roots(
     State(state): State<Arc<AppState>>,
+    Query(query): Query<CreateRootQuery>,
     Json(payload): Json<BatchCreateWordRoot>,
 ) -> impl IntoResponse {
+    let force = query.force.unwrap_or(false);
     let total_items = payload.items.len();
     let mut success_count = 0;
     let mut errors = Vec::new();
@@ -121,14 +272,14 @@ pub async fn batch_create_roots(
     let mut texts_to_embed = Vec::new();
 
     for item in payload.items {
-        let norm_terms = normalize_terms(item.associated_terms.clone());
+        let terms = synonym_list(&item, true).map(Option::unwrap_or_default);
         let embed_text = format!("{} {} {}", 
             item.cn_name, 
             item.en_full_name.as_deref().unwrap_or(""), 
-            norm_terms.as_deref().unwrap_or("")
+            terms.as_deref().map(|t| t.join(" ")).unwrap_or_default()
         );
         texts_to_embed.push(embed_text);
-        processed_items.push((item, norm_terms));
+        processed_items.push((item, terms));
     }
 
     // 批量计算向量 (修复：同步锁 lock())
@@ -145,29 +296,67 @@ pub async fn batch_create_roots(
     };
 
     // 执行 SQL 插入
-    for (index, (item, norm_terms)) in processed_items.into_iter().enumerate() {
-        let res = sqlx::query_as!(
-            WordRoot,
-            r#"
-            INSERT INTO standard_word_roots (cn_name, en_abbr, en_full_name, associated_terms, remark)
-            VALUES ($1, $2, $3, $4, $5)
-            RETURNING id, cn_name, en_abbr, en_full_name, associated_terms, remark, created_at
-            "#,
-            item.cn_name, item.en_abbr, item.en_full_name, norm_terms, item.remark
-        )
-        .fetch_one(&state.db)
+    let lint_config = LintConfig::from_env();
+    for (index, (item, terms)) in processed_items.into_iter().enumerate() {
+        let terms = match terms {
+            Ok(t) => t,
+            Err(msg) => {
+                errors.push(format!("行 {}: 词根 [{}] {}", index + 1, item.cn_name, msg));
+                continue;
+            }
+        };
+        match abbr_lint::lint(&state.db, &item.en_abbr, item.en_full_name.as_deref(), &lint_config).await {
+            Ok(issues) if !issues.is_empty() => {
+                errors.push(format!("行 {}: 词根 [{}] 缩写 [{}] 不合规: {}", index + 1, item.cn_name, item.en_abbr, abbr_lint::summarize(&issues)));
+                continue;
+            }
+            Ok(_) => {}
+            Err(e) => {
+                errors.push(format!("行 {}: 词根 [{}] 缩写检查异常: {}", index + 1, item.cn_name, e));
+                continue;
+            }
+        }
+
+        // 查重：已导入的行已提交，同一批次内的重复也会被检出
+        let candidate = RootCandidate {
+            cn_name: &item.cn_name,
+            en_abbr: &item.en_abbr,
+            en_full_name: item.en_full_name.as_deref(),
+            terms: terms.clone(),
+        };
+        match duplicate_service::find_root_conflicts(&state, &candidate).await {
+            Ok(conflicts) if !conflicts.is_empty() => {
+                let details = conflicts.iter().map(|c| c.detail.as_str()).collect::<Vec<_>>().join("；");
+                if !force {
+                    errors.push(format!("行 {}: 词根 [{}] 疑似重复: {}", index + 1, item.cn_name, details));
+                    continue;
+                }
+                tracing::warn!("--- 行 {}: 词根 [{}] 疑似重复，已强制导入: {}", index + 1, item.cn_name, details);
+            }
+            Ok(_) => {}
+            Err(e) => {
+                errors.push(format!("行 {}: 词根 [{}] 查重异常: {}", index + 1, item.cn_name, e));
+                continue;
+            }
+        }
+
+        let res = async {
+            let mut tx = state.db.begin().await?;
+            let root = insert_root_row(&mut tx, &item, &terms).await?;
+            tx.commit().await?;
+            Ok::<_, sqlx::Error>(root)
+        }
         .await;
 
         match res {
             Ok(root) => {
                 success_count += 1;
-                let mut jieba_write = JIEBA.write().await;
-                jieba_write.add_word(&root.cn_name, Some(99999), None);
 
                 let mut payload_map: HashMap<String, Value> = HashMap::new();
                 payload_map.insert("cn_name".to_string(), root.cn_name.clone().into());
                 payload_map.insert("en_abbr".to_string(), root.en_abbr.clone().into());
                 points_to_upsert.push(PointStruct::new(root.id as u64, all_embeddings[index].clone(), payload_map));
+                webhook_service::emit(&state.db, "root.created", &root).await;
             },
             Err(e) => {
                 errors.push(format!("行 {}: 词根 [{}] 失败: {}", index + 1, item.cn_name, e));
@@ -178,6 +367,9 @@ pub async fn batch_create_roots(
     if !points_to_upsert.is_empty() {
         let _ = state.qdrant.upsert_points(UpsertPointsBuilder::new("word_roots", points_to_upsert)).await;
     }
+    if success_count > 0 {
+        dictionary_service::on_roots_changed(&state.db).await;
+    }
 
     tracing::info!("<<< 批量导入完成. 成功: {}", success_count);
     (StatusCode::OK, Json(ImportResult { success_count, failure_count: errors.len(), errors })).into_response()
@@ -197,14 +389,33 @@ pub async fn list_roots(
         sqlx::query_scalar!("SELECT count(*) FROM standard_word_roots").fetch_one(&state.db).await.unwrap_or(Some(0)).unwrap_or(0)
     } else {
         let pattern = format!("%{}%", search_q);
-        sqlx::query_scalar!("SELECT count(*) FROM standard_word_roots WHERE cn_name ILIKE $1 OR en_abbr ILIKE $1", pattern).fetch_one(&state.db).await.unwrap_or(Some(0)).unwrap_or(0)
+        let py_prefix = format!("{}%", search_q.to_lowercase());
+        sqlx::query_scalar!(
+            r#"SELECT count(*) FROM standard_word_roots
+               WHERE cn_name ILIKE $1 OR en_abbr ILIKE $1
+                  OR pinyin LIKE $2 OR pinyin_initials LIKE $2 OR terms_pinyin LIKE $3"#,
+            pattern, py_prefix, format!("%{}%", search_q.to_lowercase())
+        ).fetch_one(&state.db).await.unwrap_or(Some(0)).unwrap_or(0)
     };
 
     let items_res = if search_q.is_empty() {
         sqlx::query_as!(WordRoot, "SELECT * FROM standard_word_roots ORDER BY created_at DESC LIMIT $1 OFFSET $2", page_size, offset).fetch_all(&state.db).await
     } else {
+        // 排序：中文名完全匹配 > 中文名/缩写包含 > 拼音/首字母匹配
         let pattern = format!("%{}%", search_q);
-        sqlx::query_as!(WordRoot, "SELECT * FROM standard_word_roots WHERE cn_name ILIKE $1 OR en_abbr ILIKE $1 ORDER BY created_at DESC LIMIT $2 OFFSET $3", pattern, page_size, offset).fetch_all(&state.db).await
+        let py_prefix = format!("{}%", search_q.to_lowercase());
+        sqlx::query_as!(
+            WordRoot,
+            r#"SELECT * FROM standard_word_roots
+               WHERE cn_name ILIKE $1 OR en_abbr ILIKE $1
+                  OR pinyin LIKE $2 OR pinyin_initials LIKE $2 OR terms_pinyin LIKE $3
+               ORDER BY CASE WHEN cn_name = $4 THEN 0
+                             WHEN cn_name ILIKE $1 OR en_abbr ILIKE $1 THEN 1
+                             ELSE 2 END,
+                        created_at DESC
+               LIMIT $5 OFFSET $6"#,
+            pattern, py_prefix, format!("%{}%", search_q.to_lowercase()), search_q, page_size, offset
+        ).fetch_all(&state.db).await
     };
 
     match items_res {
@@ -217,43 +428,67 @@ pub async fn list_roots(
 pub async fn update_root(
     State(state): State<Arc<AppState>>,
     Path(id): Path<i32>,
-    Json(mut payload): Json<CreateWordRoot>,
+    Json(payload): Json<CreateWordRoot>,
 ) -> impl IntoResponse {
-    payload.associated_terms = normalize_terms(payload.associated_terms);
     tracing::info!(">>> 更新词根 ID: {}", id);
 
-    let result = sqlx::query_as!(
-        WordRoot,
-        r#"
-        UPDATE standard_word_roots 
-        SET cn_name = $1, en_abbr = $2, en_full_name = $3, associated_terms = $4, remark = $5
-        WHERE id = $6
-        RETURNING id, cn_name, en_abbr, en_full_name, associated_terms, remark, created_at
-        "#,
-        payload.cn_name, payload.en_abbr, payload.en_full_name, payload.associated_terms, payload.remark, id
-    )
-    .fetch_one(&state.db)
+    // 未传 synonyms 时保持同义词表不变，否则按列表同步
+    let synonyms = match synonym_list(&payload, false) {
+        Ok(list) => list,
+        Err(msg) => return (StatusCode::BAD_REQUEST, msg).into_response(),
+    };
+    if let Err(resp) = check_abbr(&state, &payload).await {
+        return resp;
+    }
+    let old_status = sqlx::query_scalar!("SELECT status FROM standard_word_roots WHERE id = $1", id)
+        .fetch_optional(&state.db)
+        .await
+        .unwrap_or(None);
+
+    // 词根字段与同义词在同一事务中更新
+    let result = async {
+        let mut tx = state.db.begin().await?;
+        let updated = sqlx::query_scalar!(
+            r#"
+            UPDATE standard_word_roots 
+            SET cn_name = $1, en_abbr = $2, en_full_name = $3, remark = $4,
+                status = COALESCE($5, status), is_class_word = COALESCE($6, is_class_word),
+                default_data_type = $7, default_length = $8, default_precision = $9,
+                pinyin = $10, pinyin_initials = $11
+            WHERE id = $12
+            RETURNING id
+            "#,
+            payload.cn_name, payload.en_abbr, payload.en_full_name, payload.remark, payload.status,
+            payload.is_class_word, payload.default_data_type, payload.default_length, payload.default_precision,
+            pinyin_service::full(&payload.cn_name), pinyin_service::initials(&payload.cn_name), id
+        )
+        .fetch_optional(&mut *tx)
+        .await?;
+        if updated.is_none() {
+            return Ok(None);
+        }
+        if let Some(terms) = &synonyms {
+            synonym_service::replace_synonyms(&mut tx, id, terms).await?;
+        }
+        let root = sqlx::query_as!(WordRoot, "SELECT * FROM standard_word_roots WHERE id = $1", id)
+            .fetch_one(&mut *tx)
+            .await?;
+        tx.commit().await?;
+        Ok::<_, sqlx::Error>(Some(root))
+    }
     .await;
 
     match result {
-        Ok(root) => {
-            let text = format!("{} {} {}", root.cn_name, root.en_full_name.as_deref().unwrap_or(""), root.associated_terms.as_deref().unwrap_or(""));
-            
-            // 修复：同步锁 lock()
-            let embeddings_res = {
-                let mut model = state.embed_model.lock();
-                model.embed(vec![text], None)
-            };
-
-            if let Ok(embeddings) = embeddings_res {
-                let mut payload_map: HashMap<String, Value> = HashMap::new();
-                payload_map.insert("cn_name".to_string(), root.cn_name.clone().into());
-                payload_map.insert("en_abbr".to_string(), root.en_abbr.clone().into());
-                let point = PointStruct::new(root.id as u64, embeddings[0].clone(), payload_map);
-                let _ = state.qdrant.upsert_points(UpsertPointsBuilder::new("word_roots", vec![point])).await;
+        Ok(Some(root)) => {
+            dictionary_service::on_roots_changed(&state.db).await;
+            upsert_root_vector(&state, &root).await;
+            webhook_service::emit(&state.db, "root.updated", &root).await;
+            if root.status == "deprecated" && old_status.as_deref() != Some("deprecated") {
+                webhook_service::emit(&state.db, "root.deprecated", &root).await;
             }
             StatusCode::OK.into_response()
         },
+        Ok(None) => StatusCode::NOT_FOUND.into_response(),
         Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, format!("更新失败: {}", e)).into_response(),
     }
 }
@@ -269,6 +504,8 @@ pub async fn delete_root(
         Ok(res) => {
             if res.rows_affected() > 0 {
                 let _ = state.qdrant.delete_points(DeletePointsBuilder::new("word_roots").points(vec![id as u64])).await;
+                dictionary_service::on_roots_changed(&state.db).await;
+                webhook_service::emit(&state.db, "root.deleted", &serde_json::json!({ "id": id })).await;
                 StatusCode::NO_CONTENT.into_response()
             } else {
                 StatusCode::NOT_FOUND.into_response()
@@ -280,13 +517,103 @@ pub async fn delete_root(
 
 /// 6. 一键清空
 pub async fn clear_all_roots(State(state): State<Arc<AppState>>) -> impl IntoResponse {
-    let db_res = sqlx::query!("TRUNCATE TABLE standard_word_roots RESTART IDENTITY").execute(&state.db).await;
+    let db_res = sqlx::query!("TRUNCATE TABLE standard_word_roots, word_root_synonyms RESTART IDENTITY").execute(&state.db).await;
 
     match db_res {
         Ok(_) => {
             let _ = state.qdrant.delete_points(DeletePointsBuilder::new("word_roots").points(Filter::default())).await;
+            dictionary_service::on_roots_changed(&state.db).await;
             (StatusCode::OK, "所有词根数据已成功清空").into_response()
         }
         Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, format!("清空异常: {}", e)).into_response(),
     }
-}
\ No newline at end of file
+}
+/// 7. 为新词根生成英文缩写候选
+pub async fn suggest_abbreviations(
+    State(state): State<Arc<AppState>>,
+    Query(query): Query<AbbrSuggestQuery>,
+) -> impl IntoResponse {
+    let cn_name = query.cn_name.trim();
+    if cn_name.is_empty() {
+        return (StatusCode::BAD_REQUEST, "中文名不能为空").into_response();
+    }
+    let en_full_name = query.en_full_name.as_deref().map(str::trim).filter(|n| !n.is_empty());
+    tracing::info!(">>> 生成缩写候选: cn_name={}, en_full_name={:?}", cn_name, en_full_name);
+
+    match abbreviation_service::suggest(&state.db, cn_name, en_full_name).await {
+        Ok(res) => (StatusCode::OK, Json(res)).into_response(),
+        Err(e) => {
+            tracing::error!("!!! 缩写候选生成异常: {}", e);
+            (StatusCode::INTERNAL_SERVER_ERROR, format!("查询异常: {}", e)).into_response()
+        }
+    }
+}
+
+fn merge_error_response(e: MergeError) -> axum::response::Response {
+    match e {
+        MergeError::Invalid(msg) => (StatusCode::BAD_REQUEST, msg).into_response(),
+        MergeError::Db(e) => {
+            tracing::error!("!!! 词根合并异常: {}", e);
+            (StatusCode::INTERNAL_SERVER_ERROR, format!("数据库错误: {}", e)).into_response()
+        }
+    }
+}
+
+/// 8. 预览词根合并 (不做修改)
+pub async fn preview_merge_roots(
+    State(state): State<Arc<AppState>>,
+    Json(payload): Json<MergeRootsPayload>,
+) -> impl IntoResponse {
+    match merge_service::preview(&state.db, &payload).await {
+        Ok(plan) => (StatusCode::OK, Json(plan)).into_response(),
+        Err(e) => merge_error_response(e),
+    }
+}
+
+/// 9. 合并词根：被合并词根的名称与同义词并入保留词根，引用它们的标准字段随之改写
+pub async fn merge_roots(
+    State(state): State<Arc<AppState>>,
+    Extension(claims): Extension<Claims>,
+    Json(payload): Json<MergeRootsPayload>,
+) -> impl IntoResponse {
+    tracing::info!(">>> 词根合并: survivor={}, victims={:?}, 操作人={}", payload.survivor_id, payload.victim_ids, claims.sub);
+
+    let plan: MergePlan = match merge_service::execute(&state.db, &payload, claims.sub).await {
+        Ok(plan) => plan,
+        Err(e) => return merge_error_response(e),
+    };
+
+    // 事务提交后刷新派生数据：同义词缓存、向量、分词词典与联想索引
+    let refreshed = async {
+        let mut conn = state.db.acquire().await?;
+        synonym_service::refresh_root_cache(&mut conn, plan.survivor.id).await
+    }
+    .await;
+    if let Err(e) = refreshed {
+        tracing::error!("!!! 词根 ID={} 同义词缓存刷新失败: {}", plan.survivor.id, e);
+    }
+    if let Ok(Some(root)) = sqlx::query_as!(WordRoot, "SELECT * FROM standard_word_roots WHERE id = $1", plan.survivor.id)
+        .fetch_optional(&state.db)
+        .await
+    {
+        upsert_root_vector(&state, &root).await;
+    }
+    let victim_points: Vec<u64> = plan.victims.iter().map(|v| v.id as u64).collect();
+    let _ = state.qdrant.delete_points(DeletePointsBuilder::new("word_roots").points(victim_points)).await;
+    for f in plan.fields.iter().filter(|f| f.old_en_name != f.new_en_name) {
+        let mut payload_map: HashMap<String, Value> = HashMap::new();
+        payload_map.insert("en_name".to_string(), f.new_en_name.clone().into());
+        let _ = state.qdrant.set_payload(
+            SetPayloadPointsBuilder::new("standard_fields", Payload::from(payload_map))
+                .points_selector(PointsIdsList { ids: vec![PointId::from(f.id as u64)] }),
+        ).await;
+    }
+    dictionary_service::on_roots_changed(&state.db).await;
+    if !plan.fields.is_empty() {
+        dictionary_service::on_fields_changed(&state.db).await;
+    }
+
+    webhook_service::emit(&state.db, "root.merged", &plan).await;
+    tracing::info!("<<< 词根合并完成: 迁入词 {} 个, 改写字段 {} 个", plan.moved_terms.len(), plan.fields.len());
+    (StatusCode::OK, Json(plan)).into_response()
+}
@@ -34,14 +34,16 @@ mod middleware;
 mod models;
 mod services;
 
-// 使用 Lazy 确保 Jieba 词库只在启动时加载一次，并全局可用
-pub static JIEBA: Lazy<RwLock<Jieba>> = Lazy::new(|| RwLock::new(Jieba::new()));
+// 全局分词器：词根变更时由 dictionary_service 从数据库整体重建后原子替换
+// 读取方只需短暂持锁克隆 Arc，分词过程不阻塞替换
+pub static JIEBA: Lazy<RwLock<Arc<Jieba>>> = Lazy::new(|| RwLock::new(Arc::new(Jieba::new())));
 
 // 定义全局状态
 pub struct AppState {
     pub db: PgPool,
     pub qdrant: Qdrant,
     pub embed_model: Mutex<TextEmbedding>, // 使用 Mutex 保证 AI 模型调用的可变引用需求
+    pub events: tokio::sync::broadcast::Sender<services::event_bus::AppEvent>, // 本实例的事件广播，由 Postgres 通知驱动
 }
 
 /// 健康检查 Handler：用于运维平台监测服务可用性
@@ -96,7 +98,9 @@ async fn sync_roots_to_qdrant(state: &AppState) {
     tracing::info!("正在同步 [标准词根] 向量到 Qdrant...");
     let roots = sqlx::query_as!(
         crate::models::word_root::WordRoot,
-        "SELECT id, cn_name, en_abbr, en_full_name, associated_terms, remark, created_at FROM standard_word_roots"
+        "SELECT id, cn_name, en_abbr, en_full_name, associated_terms, remark, status,
+               is_class_word, default_data_type, default_length, default_precision,
+               pinyin, pinyin_initials, terms_pinyin, created_at FROM standard_word_roots"
     )
     .fetch_all(&state.db)
     .await
@@ -147,7 +151,7 @@ async fn sync_fields_to_qdrant(state: &AppState) {
     let fields = sqlx::query_as!(
         crate::models::field::StandardField,
         r#"SELECT id, field_cn_name, field_en_name, composition_ids as "composition_ids!", 
-           data_type, associated_terms, is_standard as "is_standard!", created_at FROM standard_fields"#
+           data_type, associated_terms, is_standard as "is_standard!", status, created_at FROM standard_fields"#
     )
     .fetch_all(&state.db)
     .await
@@ -208,20 +212,6 @@ async fn init_qdrant_collections(qdrant: &Qdrant) {
     }
 }
 
-async fn init_custom_dictionary(pool: &PgPool) {
-    tracing::info!("正在加载分词库自定义词典...");
-    let roots = sqlx::query!("SELECT cn_name FROM standard_word_roots")
-        .fetch_all(pool)
-        .await
-        .unwrap_or_default();
-
-    let mut jieba_write = JIEBA.write().await;
-    for r in &roots {
-        jieba_write.add_word(&r.cn_name, Some(99999), None);
-    }
-    tracing::info!("自定义词典加载完成，共计 {} 个词条", roots.len());
-}
-
 #[tokio::main]
 async fn main() {
     // 1. 初始化环境变量与日志
@@ -236,6 +226,8 @@ async fn main() {
         std::env::var("RUST_LOG").unwrap_or_else(|_| "info".into())
     );
 
+    services::token_service::init();
+
     // 2. 初始化数据库连接池
     let database_url = std::env::var("DATABASE_URL").expect("DATABASE_URL must be set");
     let pool = PgPoolOptions::new()
@@ -246,7 +238,15 @@ async fn main() {
 
     // 3. 执行启动预热逻辑
     ensure_default_admin(&pool).await;
-    init_custom_dictionary(&pool).await;
+    tracing::info!("正在加载分词库自定义词典...");
+    services::dictionary_service::rebuild(&pool).await;
+    services::stop_word_service::reload(&pool).await;
+    services::pinyin_service::backfill(&pool).await;
+    services::typeahead_service::refresh(&pool).await;
+    services::dictionary_service::spawn_listener(pool.clone());
+    let events = services::event_bus::channel();
+    services::event_bus::spawn_listener(pool.clone(), events.clone());
+    services::webhook_service::spawn_worker(pool.clone());
 
     // 4. 初始化 Embedding 模型与向量库
     let current_dir = env::current_dir().expect("Failed to get current dir");
@@ -272,6 +272,7 @@ async fn main() {
         db: pool,
         qdrant,
         embed_model: Mutex::new(model), // 使用高效同步锁
+        events,
     });
 
     // 5. 执行向量数据冷启动同步
@@ -287,16 +288,51 @@ async fn main() {
     // 7. 定义路由
     let auth_routes = Router::new()
         .route("/signup", post(handlers::auth_handler::signup))
-        .route("/login", post(handlers::auth_handler::login));
+        .route("/login", post(handlers::auth_handler::login))
+        .route("/refresh", post(handlers::auth_handler::refresh))
+        .route("/jwks", get(handlers::auth_handler::jwks));
 
     let public_routes = Router::new()
         .route("/health", get(health_check)) // 增加监控接口
         .route("/search", get(handlers::field_handler::search_field))
+        .route("/typeahead", get(handlers::typeahead_handler::typeahead))
         .route("/tasks", post(handlers::task_handler::submit_task))
         .route(
             "/similar-roots",
             get(handlers::mapping_handler::search_similar_roots),
-        );
+        )
+        .layer(axum::middleware::from_fn_with_state(
+            shared_state.clone(),
+            middleware::auth::optional_identity,
+        ));
+
+    // 登录用户接口：查看自己提交的申请、管理登录会话
+    let user_routes = Router::new()
+        .route("/tasks", get(handlers::task_handler::list_my_tasks))
+        .route("/tasks/:id", get(handlers::task_handler::get_my_task))
+        .route(
+            "/notifications",
+            get(handlers::notification_handler::list_notifications),
+        )
+        .route(
+            "/notifications/read-all",
+            put(handlers::notification_handler::mark_all_notifications_read),
+        )
+        .route(
+            "/notifications/:id/read",
+            put(handlers::notification_handler::mark_notification_read),
+        )
+        .route("/logout", post(handlers::auth_handler::logout))
+        .route("/logout-all", post(handlers::auth_handler::logout_all))
+        .route("/sessions", get(handlers::auth_handler::list_sessions))
+        .route(
+            "/sessions/:id",
+            delete(handlers::auth_handler::revoke_session),
+        )
+        .layer(axum::middleware::from_fn_with_state(
+            shared_state.clone(),
+            middleware::auth::user_guard,
+        ));
 
     let admin_routes = Router::new()
         .route(
@@ -308,6 +344,18 @@ async fn main() {
             "/roots/batch",
             post(handlers::word_root_handler::batch_create_roots),
         )
+        .route(
+            "/roots/abbreviations",
+            get(handlers::word_root_handler::suggest_abbreviations),
+        )
+        .route(
+            "/roots/merge",
+            post(handlers::word_root_handler::merge_roots),
+        )
+        .route(
+            "/roots/merge/preview",
+            post(handlers::word_root_handler::preview_merge_roots),
+        )
         .route(
             "/roots/clear",
             delete(handlers::word_root_handler::clear_all_roots),
@@ -317,6 +365,20 @@ async fn main() {
             put(handlers::word_root_handler::update_root)
                 .delete(handlers::word_root_handler::delete_root),
         )
+        .route(
+            "/roots/:id/synonyms",
+            post(handlers::synonym_handler::create_synonym)
+                .get(handlers::synonym_handler::list_root_synonyms),
+        )
+        .route(
+            "/synonyms/conflicts",
+            get(handlers::synonym_handler::list_conflicts),
+        )
+        .route(
+            "/synonyms/:id",
+            put(handlers::synonym_handler::update_synonym)
+                .delete(handlers::synonym_handler::delete_synonym),
+        )
         .route(
             "/fields",
             post(handlers::field_handler::create_field).get(handlers::field_handler::list_fields),
@@ -341,21 +403,121 @@ async fn main() {
                 .delete(handlers::auth_handler::delete_user),
         )
         .route("/suggest", get(handlers::mapping_handler::suggest_mapping))
+        .route("/reverse", get(handlers::mapping_handler::reverse_translate))
+        .route(
+            "/segment/debug",
+            get(handlers::mapping_handler::debug_segmentation),
+        )
+        .route(
+            "/naming-profiles",
+            post(handlers::naming_handler::create_profile)
+                .get(handlers::naming_handler::list_profiles),
+        )
+        .route(
+            "/naming-profiles/:id",
+            put(handlers::naming_handler::update_profile)
+                .delete(handlers::naming_handler::delete_profile),
+        )
+        .route(
+            "/naming/validate",
+            post(handlers::naming_handler::validate_name),
+        )
+        .route(
+            "/naming/check",
+            post(handlers::naming_handler::check_field_name),
+        )
+        .route(
+            "/stop-words",
+            post(handlers::stop_word_handler::create_stop_word)
+                .get(handlers::stop_word_handler::list_stop_words),
+        )
+        .route(
+            "/stop-words/:id",
+            put(handlers::stop_word_handler::update_stop_word)
+                .delete(handlers::stop_word_handler::delete_stop_word),
+        )
         .route("/tasks", get(handlers::task_handler::list_tasks))
         .route(
             "/tasks/count",
             get(handlers::task_handler::count_unprocessed_tasks),
         )
-        .route("/tasks/:id", put(handlers::task_handler::complete_task))
+        .route("/tasks/stats", get(handlers::task_handler::task_stats))
+        .route("/tasks/report", get(handlers::task_handler::task_report))
+        .route(
+            "/tasks/:id",
+            get(handlers::task_handler::get_task).put(handlers::task_handler::complete_task),
+        )
+        .route(
+            "/tasks/:id/comments",
+            post(handlers::task_handler::add_comment),
+        )
+        .route(
+            "/tasks/:id/draft",
+            get(handlers::task_handler::draft_task_field),
+        )
+        .route(
+            "/tasks/:id/fulfil",
+            post(handlers::task_handler::fulfil_task),
+        )
+        .route(
+            "/tasks/:id/apply/root",
+            post(handlers::task_handler::apply_root_request),
+        )
+        .route(
+            "/tasks/:id/apply/field-update",
+            post(handlers::task_handler::apply_field_update),
+        )
+        .route(
+            "/tasks/:id/apply/deprecation",
+            post(handlers::task_handler::apply_deprecation),
+        )
+        .route(
+            "/tasks/:id/apply/synonym",
+            post(handlers::task_handler::apply_synonym_suggestion),
+        )
+        .route(
+            "/webhooks",
+            get(handlers::webhook_handler::list_webhooks)
+                .post(handlers::webhook_handler::create_webhook),
+        )
+        .route(
+            "/webhooks/deliveries",
+            get(handlers::webhook_handler::list_deliveries),
+        )
+        .route(
+            "/webhooks/deliveries/:id/replay",
+            post(handlers::webhook_handler::replay_delivery),
+        )
+        .route(
+            "/webhooks/:id",
+            put(handlers::webhook_handler::update_webhook)
+                .delete(handlers::webhook_handler::delete_webhook),
+        )
+        .route(
+            "/webhooks/:id/ping",
+            post(handlers::webhook_handler::ping_webhook),
+        )
+        .route("/events/ticket", post(handlers::event_handler::issue_ticket))
         .layer(axum::middleware::from_fn_with_state(
             shared_state.clone(),
             middleware::auth::guard,
-        ));
+        ))
+        // 事件流单独鉴权：支持 ?ticket= 传递一次性票据
+        .merge(
+            Router::new()
+                .route("/events", get(handlers::event_handler::stream_events))
+                .layer(axum::middleware::from_fn_with_state(
+                    shared_state.clone(),
+                    middleware::auth::event_stream_guard,
+                )),
+        );
 
     // 8. 组合所有组件并启动
     let app = Router::new()
+        .route("/.well-known/jwks.json", get(handlers::auth_handler::jwks))
         .nest("/api/auth", auth_routes)
         .nest("/api/public", public_routes)
+        .nest("/api/user", user_routes)
         .nest("/api/admin", admin_routes)
         .layer(DefaultBodyLimit::max(20 * 1024 * 1024)) // 提高批量导入限制至 20MB
         .with_state(shared_state)
@@ -1,48 +1,149 @@
 use axum::{
     body::Body,
-    extract::State,
+    extract::{Query, State},
     http::{Request, StatusCode, header},
     middleware::Next,
     response::Response,
 };
+use std::collections::HashMap;
 use std::sync::Arc;
 use crate::AppState;
 use crate::models::user::Claims;
-use jsonwebtoken::{decode, DecodingKey, Validation};
+use crate::services::{session_service, token_service};
+
+/// 解码并验证 JWT
+fn decode_token(token: &str) -> Result<Claims, StatusCode> {
+    token_service::verify(token).map_err(|_| StatusCode::UNAUTHORIZED)
+}
+
+/// 从 Authorization Header 解析 JWT：未携带返回 None，携带但无效返回 Some(Err)
+fn bearer_claims(req: &Request<Body>) -> Option<Result<Claims, StatusCode>> {
+    // 1. 提取 Authorization Header
+    let auth_header = req.headers()
+        .get(header::AUTHORIZATION)
+        .and_then(|h| h.to_str().ok())?;
+
+    // 2. 检查是否为 Bearer Token
+    let Some(token) = auth_header.strip_prefix("Bearer ") else {
+        return Some(Err(StatusCode::UNAUTHORIZED));
+    };
+
+    // 3. 解码并验证 JWT
+    Some(decode_token(token))
+}
+
+/// 检查令牌未被吊销：会话已注销、角色变更或用户删除后拒绝
+async fn check_active(state: &AppState, claims: Claims) -> Result<Claims, StatusCode> {
+    match session_service::is_active(&state.db, &claims).await {
+        Ok(true) => Ok(claims),
+        Ok(false) => Err(StatusCode::UNAUTHORIZED),
+        Err(e) => {
+            tracing::error!("!!! 令牌状态校验失败: {}", e);
+            Err(StatusCode::INTERNAL_SERVER_ERROR)
+        }
+    }
+}
+
+/// 校验 bearer_claims 的解析结果 (请求体不可跨 await 借用，需先完成解析)
+async fn authenticate(state: &AppState, parsed: Option<Result<Claims, StatusCode>>) -> Option<Result<Claims, StatusCode>> {
+    match parsed? {
+        Ok(claims) => Some(check_active(state, claims).await),
+        Err(code) => Some(Err(code)),
+    }
+}
 
 /// 管理员权限守卫
 pub async fn guard(
-    State(_state): State<Arc<AppState>>,
-    req: Request<Body>,
+    State(state): State<Arc<AppState>>,
+    mut req: Request<Body>,
     next: Next,
 ) -> Result<Response, StatusCode> {
-    // 1. 提取 Authorization Header
-    let auth_header = req.headers()
-        .get(header::AUTHORIZATION)
-        .and_then(|h| h.to_str().ok());
-
-    if let Some(auth_header) = auth_header {
-        // 2. 检查是否为 Bearer Token
-        if auth_header.starts_with("Bearer ") {
-            let token = &auth_header[7..];
-            
-            // 3. 解码并验证 JWT
-            let token_data = decode::<Claims>(
-                token,
-                &DecodingKey::from_secret("secret_key".as_ref()),
-                &Validation::default(),
-            );
-
-            if let Ok(data) = token_data {
-                // 4. 只有角色为 admin 的用户才允许访问管理接口
-                if data.claims.role == "admin" {
-                    return Ok(next.run(req).await);
-                }
-                return Err(StatusCode::FORBIDDEN); // 权限不足
-            }
+    let parsed = bearer_claims(&req);
+    match authenticate(&state, parsed).await {
+        // 只有角色为 admin 的用户才允许访问管理接口
+        Some(Ok(claims)) if claims.role == "admin" => {
+            // 写入请求扩展，供处理函数通过 Extension<Claims> 获取当前操作人
+            req.extensions_mut().insert(claims);
+            Ok(next.run(req).await)
+        }
+        Some(Ok(_)) => Err(StatusCode::FORBIDDEN), // 权限不足
+        Some(Err(code)) => Err(code),
+        // 未提供 Token
+        None => Err(StatusCode::UNAUTHORIZED),
+    }
+}
+
+/// 登录用户守卫：任意角色的有效 Token 均可访问
+pub async fn user_guard(
+    State(state): State<Arc<AppState>>,
+    mut req: Request<Body>,
+    next: Next,
+) -> Result<Response, StatusCode> {
+    let parsed = bearer_claims(&req);
+    match authenticate(&state, parsed).await {
+        Some(Ok(claims)) => {
+            req.extensions_mut().insert(claims);
+            Ok(next.run(req).await)
+        }
+        Some(Err(code)) => Err(code),
+        None => Err(StatusCode::UNAUTHORIZED),
+    }
+}
+
+/// 可选身份：公开接口允许匿名访问，携带有效 Token 时写入 Claims
+/// 过期或无效的 Token 按匿名处理 (访问令牌有效期较短，公开接口不应因过期而失败)
+pub async fn optional_identity(
+    State(state): State<Arc<AppState>>,
+    mut req: Request<Body>,
+    next: Next,
+) -> Result<Response, StatusCode> {
+    let parsed = bearer_claims(&req);
+    match authenticate(&state, parsed).await {
+        Some(Ok(claims)) => {
+            req.extensions_mut().insert(claims);
+            Ok(next.run(req).await)
+        }
+        Some(Err(StatusCode::INTERNAL_SERVER_ERROR)) => Err(StatusCode::INTERNAL_SERVER_ERROR),
+        Some(Err(_)) | None => Ok(next.run(req).await),
+    }
+}
+
+/// 事件流所属的登录会话，连接期间用于定期校验会话状态
+#[derive(Clone, Copy)]
+pub struct StreamSession {
+    pub session_id: i64,
+}
+
+/// 事件流守卫：浏览器 EventSource 无法设置请求头，额外允许通过 ?ticket= 传递一次性事件流票据
+/// 票据由 POST /api/admin/events/ticket 签发，访问令牌不出现在 URL 中
+pub async fn event_stream_guard(
+    State(state): State<Arc<AppState>>,
+    mut req: Request<Body>,
+    next: Next,
+) -> Result<Response, StatusCode> {
+    if let Some(parsed) = bearer_claims(&req) {
+        let claims = check_active(&state, parsed?).await?;
+        if claims.role != "admin" {
+            return Err(StatusCode::FORBIDDEN);
+        }
+        req.extensions_mut().insert(StreamSession { session_id: claims.sid });
+        req.extensions_mut().insert(claims);
+        return Ok(next.run(req).await);
+    }
+
+    let query: Query<HashMap<String, String>> =
+        Query::try_from_uri(req.uri()).map_err(|_| StatusCode::UNAUTHORIZED)?;
+    let ticket = query.get("ticket").ok_or(StatusCode::UNAUTHORIZED)?.clone();
+    match session_service::redeem_stream_ticket(&state.db, &ticket).await {
+        Ok(Some((user_id, session_id))) => {
+            tracing::info!(">>> 用户 ID={} 使用票据订阅事件流", user_id);
+            req.extensions_mut().insert(StreamSession { session_id });
+            Ok(next.run(req).await)
+        }
+        Ok(None) => Err(StatusCode::UNAUTHORIZED),
+        Err(e) => {
+            tracing::error!("!!! 事件流票据校验失败: {}", e);
+            Err(StatusCode::INTERNAL_SERVER_ERROR)
         }
     }
-    
-    // 5. 未提供 Token 或 Token 无效
-    Err(StatusCode::UNAUTHORIZED)
-}
\ No newline at end of file
+}
@@ -11,6 +11,7 @@ pub struct StandardField {
     pub data_type: Option<String>,
     pub associated_terms: Option<String>,
     pub is_standard: bool,
+    pub status: String, // active / deprecated
     pub created_at: Option<DateTime<Utc>>,
 }
 
@@ -1,3 +1,7 @@
 pub mod word_root;
 pub mod field;
 pub mod user;
+pub mod naming_profile;
+pub mod stop_word;
+pub mod synonym;
+pub mod task_request;
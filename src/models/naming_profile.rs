@@ -0,0 +1,29 @@
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use chrono::{DateTime, Utc};
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct NamingProfile {
+    pub id: i32,
+    pub name: String,
+    pub case_style: String,        // snake / upper_snake / camel / pascal
+    pub prefix: Option<String>,
+    pub suffix: Option<String>,
+    pub max_length: Option<i32>,   // NULL 表示不限制长度
+    pub number_style: String,      // 数字片段: separate / attach / drop
+    pub is_default: bool,
+    pub remark: Option<String>,
+    pub created_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Deserialize)]
+pub struct CreateNamingProfile {
+    pub name: String,
+    pub case_style: String,
+    pub prefix: Option<String>,
+    pub suffix: Option<String>,
+    pub max_length: Option<i32>,
+    pub number_style: Option<String>, // 缺省为 separate
+    pub is_default: Option<bool>,
+    pub remark: Option<String>,
+}
@@ -0,0 +1,17 @@
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use chrono::{DateTime, Utc};
+
+#[derive(Debug, Serialize, Deserialize, FromRow)]
+pub struct StopWord {
+    pub id: i32,
+    pub word: String,
+    pub kind: String, // stop 停用词 / punct 标点
+    pub created_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Deserialize)]
+pub struct CreateStopWord {
+    pub word: String,
+    pub kind: Option<String>, // 缺省为 stop
+}
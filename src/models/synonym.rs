@@ -0,0 +1,24 @@
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use chrono::{DateTime, Utc};
+
+#[derive(Debug, Serialize, Deserialize, FromRow)]
+pub struct Synonym {
+    pub id: i32,
+    pub root_id: i32,
+    pub term: String,
+    pub syn_type: String, // exact / near / abbreviation / misspelling
+    pub weight: i32,      // 0~100，越大越优先，0 表示不参与匹配
+    pub source: String,
+    pub remark: Option<String>,
+    pub created_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Deserialize)]
+pub struct CreateSynonym {
+    pub term: String,
+    pub syn_type: Option<String>, // 缺省为 exact
+    pub weight: Option<i32>,      // 缺省为 100
+    pub source: Option<String>,   // 缺省为 manual
+    pub remark: Option<String>,
+}
@@ -0,0 +1,207 @@
+use serde::{Deserialize, Serialize};
+
+/// 用户申请：task_type 对应 notification_tasks.task_type，payload 原样存入 payload JSONB
+/// 请求体形如 {"task_type": "ROOT_REQUEST", "payload": {...}}
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(tag = "task_type", content = "payload", rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum TaskRequest {
+    FieldRequest(FieldRequestPayload),
+    RootRequest(RootRequestPayload),
+    FieldUpdate(FieldUpdatePayload),
+    DeprecationRequest(DeprecationPayload),
+    SynonymSuggestion(SynonymSuggestionPayload),
+}
+
+/// 新增标准字段
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct FieldRequestPayload {
+    pub field_cn_name: String,
+    pub reason: Option<String>, // 用途说明
+}
+
+/// 新增词根
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct RootRequestPayload {
+    pub cn_name: String,
+    pub en_full_name: Option<String>,
+    pub en_abbr: Option<String>, // 建议缩写，最终以管理员确认为准
+    pub reason: Option<String>,
+}
+
+/// 可申请修改的字段属性，未传表示不修改
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct FieldChanges {
+    pub field_cn_name: Option<String>,
+    pub field_en_name: Option<String>,
+    pub data_type: Option<String>,
+    pub associated_terms: Option<String>,
+}
+
+/// 单个属性的变更前后值
+#[derive(Debug, Serialize, Deserialize)]
+pub struct FieldDiff {
+    pub column: String,
+    pub old: Option<String>,
+    pub new: Option<String>,
+}
+
+/// 修改已有标准字段
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct FieldUpdatePayload {
+    pub field_id: i32,
+    pub changes: FieldChanges,
+    pub reason: String,
+    #[serde(default)]
+    pub diff: Vec<FieldDiff>, // 提交时由服务端按当前字段生成，客户端传入的值会被覆盖
+}
+
+/// 废弃词根或标准字段
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct DeprecationPayload {
+    pub target: String, // root / field
+    pub target_id: i32,
+    pub reason: String,
+    pub replacement_id: Option<i32>, // 建议替代的词根 / 字段
+}
+
+/// 为词根建议同义词
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct SynonymSuggestionPayload {
+    pub root_id: i32,
+    pub term: String,
+    pub syn_type: Option<String>, // 缺省为 exact
+    pub reason: Option<String>,
+}
+
+impl TaskRequest {
+    pub fn task_type(&self) -> &'static str {
+        match self {
+            TaskRequest::FieldRequest(_) => "FIELD_REQUEST",
+            TaskRequest::RootRequest(_) => "ROOT_REQUEST",
+            TaskRequest::FieldUpdate(_) => "FIELD_UPDATE",
+            TaskRequest::DeprecationRequest(_) => "DEPRECATION_REQUEST",
+            TaskRequest::SynonymSuggestion(_) => "SYNONYM_SUGGESTION",
+        }
+    }
+
+    /// 按 notification_tasks 的 task_type 与 payload 还原
+    pub fn from_row(task_type: &str, payload: &serde_json::Value) -> Result<Self, serde_json::Error> {
+        serde_json::from_value(serde_json::json!({ "task_type": task_type, "payload": payload }))
+    }
+
+    /// 写入 payload 列的内容 (不含 task_type)
+    pub fn payload(&self) -> serde_json::Value {
+        serde_json::to_value(self)
+            .ok()
+            .and_then(|mut v| v.get_mut("payload").map(serde_json::Value::take))
+            .unwrap_or_default()
+    }
+
+    /// 通知与日志中展示的申请标题
+    pub fn subject(&self) -> String {
+        match self {
+            TaskRequest::FieldRequest(p) => p.field_cn_name.clone(),
+            TaskRequest::RootRequest(p) => p.cn_name.clone(),
+            TaskRequest::FieldUpdate(p) => format!("修改字段 ID={}", p.field_id),
+            TaskRequest::DeprecationRequest(p) => format!("废弃{} ID={}", if p.target == "root" { "词根" } else { "字段" }, p.target_id),
+            TaskRequest::SynonymSuggestion(p) => p.term.clone(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn parse(body: serde_json::Value) -> Result<TaskRequest, serde_json::Error> {
+        serde_json::from_value(body)
+    }
+
+    #[test]
+    fn parses_tagged_request() {
+        let req = parse(json!({
+            "task_type": "ROOT_REQUEST",
+            "payload": { "cn_name": "金额", "en_full_name": "amount", "en_abbr": "amt" }
+        }))
+        .unwrap();
+        assert_eq!(req.task_type(), "ROOT_REQUEST");
+        assert_eq!(req.subject(), "金额");
+        match req {
+            TaskRequest::RootRequest(p) => {
+                assert_eq!(p.en_abbr.as_deref(), Some("amt"));
+                assert_eq!(p.reason, None);
+            }
+            other => panic!("解析出错误的申请类型: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn rejects_unknown_task_type() {
+        let err = parse(json!({ "task_type": "FIELD_DELETE", "payload": { "field_id": 1 } })).unwrap_err();
+        assert!(err.to_string().contains("unknown variant `FIELD_DELETE`"), "{}", err);
+    }
+
+    #[test]
+    fn rejects_unknown_payload_fields() {
+        let err = parse(json!({
+            "task_type": "FIELD_REQUEST",
+            "payload": { "field_cn_name": "客户名称", "field_en_name": "cust_name" }
+        }))
+        .unwrap_err();
+        assert!(err.to_string().contains("unknown field `field_en_name`"), "{}", err);
+
+        let err = parse(json!({
+            "task_type": "FIELD_UPDATE",
+            "payload": { "field_id": 1, "reason": "改名", "changes": { "status": "deprecated" } }
+        }))
+        .unwrap_err();
+        assert!(err.to_string().contains("unknown field `status`"), "{}", err);
+    }
+
+    #[test]
+    fn rejects_missing_or_mistyped_fields() {
+        let err = parse(json!({ "task_type": "DEPRECATION_REQUEST", "payload": { "target": "root", "target_id": 3 } }))
+            .unwrap_err();
+        assert!(err.to_string().contains("missing field `reason`"), "{}", err);
+
+        let err = parse(json!({ "task_type": "SYNONYM_SUGGESTION", "payload": { "root_id": "3", "term": "钱" } }))
+            .unwrap_err();
+        assert!(err.to_string().contains("invalid type"), "{}", err);
+
+        assert!(parse(json!({ "task_type": "FIELD_REQUEST" })).is_err());
+    }
+
+    #[test]
+    fn payload_round_trips_through_row() {
+        let req = parse(json!({
+            "task_type": "FIELD_UPDATE",
+            "payload": { "field_id": 7, "reason": "统一命名", "changes": { "field_en_name": "cust_nm" } }
+        }))
+        .unwrap();
+        let payload = req.payload();
+        assert_eq!(payload["field_id"], 7);
+        assert_eq!(payload["diff"], json!([]));
+        assert!(payload.get("task_type").is_none());
+
+        let restored = TaskRequest::from_row(req.task_type(), &payload).unwrap();
+        assert_eq!(restored.subject(), "修改字段 ID=7");
+        assert!(TaskRequest::from_row("ROOT_REQUEST", &payload).is_err());
+    }
+
+    #[test]
+    fn deprecation_subject_names_target() {
+        let req = parse(json!({
+            "task_type": "DEPRECATION_REQUEST",
+            "payload": { "target": "root", "target_id": 3, "reason": "重复" }
+        }))
+        .unwrap();
+        assert_eq!(req.subject(), "废弃词根 ID=3");
+    }
+}
@@ -11,9 +11,13 @@ pub struct User {
     pub created_at: Option<DateTime<Utc>>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Claims {
     pub sub: i32,      // user_id
     pub exp: usize,    // 过期时间
+    pub iat: usize,    // 签发时间 (秒)
+    #[serde(default)]
+    pub iat_ms: i64,   // 签发时间 (毫秒)，早于 users.tokens_invalidated_at 的令牌失效；旧令牌缺省为 0
+    pub sid: i64,      // 登录会话 ID，会话吊销后令牌失效
     pub role: String,  // 角色
 }
\ No newline at end of file
@@ -10,6 +10,14 @@ pub struct WordRoot {
     pub en_full_name: Option<String>,
     pub associated_terms: Option<String>, // 对应 SQL 的 TEXT
     pub remark: Option<String>,
+    pub status: String, // active / deprecated
+    pub is_class_word: bool,               // 是否为类别词 (如 amt / dt / cd / flg)
+    pub default_data_type: Option<String>, // 类别词对应的默认数据类型
+    pub default_length: Option<i32>,
+    pub default_precision: Option<i32>,
+    pub pinyin: Option<String>,          // 中文名全拼
+    pub pinyin_initials: Option<String>, // 中文名首字母
+    pub terms_pinyin: Option<String>,    // 同义词全拼及首字母
     pub created_at: Option<DateTime<Utc>>,
 }
 
@@ -18,6 +26,27 @@ pub struct CreateWordRoot {
     pub cn_name: String,
     pub en_abbr: String,
     pub en_full_name: Option<String>,
-    pub associated_terms: Option<String>, // 用户输入如："钱,费用,价格"
+    pub associated_terms: Option<String>, // 兼容旧表单，仅创建时使用，如："钱,费用,价格"
+    pub synonyms: Option<Vec<String>>,    // 同义词列表 (可含空格)，更新时按列表同步同义词表；未传则不变
     pub remark: Option<String>,
+    pub status: Option<String>, // active / deprecated，缺省为 active
+    pub is_class_word: Option<bool>,
+    pub default_data_type: Option<String>,
+    pub default_length: Option<i32>,
+    pub default_precision: Option<i32>,
+}
+
+impl WordRoot {
+    /// 类别词推荐的完整数据类型，如 DECIMAL(18,2) / VARCHAR(32) / DATE
+    pub fn recommended_data_type(&self) -> Option<String> {
+        if !self.is_class_word {
+            return None;
+        }
+        let base = self.default_data_type.as_deref()?.trim().to_uppercase();
+        Some(match (self.default_length, self.default_precision) {
+            (Some(len), Some(prec)) => format!("{}({},{})", base, len, prec),
+            (Some(len), None) => format!("{}({})", base, len),
+            _ => base,
+        })
+    }
 }
\ No newline at end of file
@@ -0,0 +1,224 @@
+use serde::Serialize;
+use sqlx::PgPool;
+
+use crate::services::{reserved_words, reverse_service};
+
+#[derive(Debug, Serialize)]
+pub struct LintIssue {
+    pub code: &'static str,
+    pub message: String,
+}
+
+/// 词根缩写规范，读取环境变量：
+/// ABBR_LINT_CHARSET 允许的字符: alpha 仅字母 / alnum 字母数字 (默认 alnum)
+/// ABBR_LINT_CASE 大小写: lower / upper / any (默认 lower)
+/// ABBR_LINT_MIN_LENGTH / ABBR_LINT_MAX_LENGTH 长度范围 (默认 2~10)
+/// ABBR_LINT_ALLOW_SEPARATORS 是否允许下划线等分隔符 (默认 false，带分隔符的缩写会被字段名切分成多段)
+/// ABBR_LINT_DIALECTS 需要避开保留字的 SQL 方言 (默认 common,mysql,postgres,oracle,hive)
+/// ABBR_LINT_FULL_NAME_CHECK 是否校验缩写与英文全称一致 (默认 true)
+pub struct LintConfig {
+    pub charset: String,
+    pub case: String,
+    pub min_length: usize,
+    pub max_length: usize,
+    pub allow_separators: bool,
+    pub dialects: Vec<String>,
+    pub full_name_check: bool,
+}
+
+fn env_or(key: &str, default: &str) -> String {
+    std::env::var(key).map(|v| v.trim().to_lowercase()).unwrap_or_else(|_| default.to_string())
+}
+
+impl LintConfig {
+    pub fn from_env() -> Self {
+        LintConfig {
+            charset: env_or("ABBR_LINT_CHARSET", "alnum"),
+            case: env_or("ABBR_LINT_CASE", "lower"),
+            min_length: env_or("ABBR_LINT_MIN_LENGTH", "2").parse().unwrap_or(2),
+            max_length: env_or("ABBR_LINT_MAX_LENGTH", "10").parse().unwrap_or(10),
+            allow_separators: env_or("ABBR_LINT_ALLOW_SEPARATORS", "false") == "true",
+            dialects: env_or("ABBR_LINT_DIALECTS", "common,mysql,postgres,oracle,hive")
+                .split(',')
+                .map(|d| d.trim().to_string())
+                .filter(|d| !d.is_empty())
+                .collect(),
+            full_name_check: env_or("ABBR_LINT_FULL_NAME_CHECK", "true") == "true",
+        }
+    }
+}
+
+/// 缩写的字母是否按顺序出现在全称中，且首字母一致，如 amt ⊂ amount
+fn is_subsequence(abbr: &str, full: &str) -> bool {
+    if abbr.chars().next() != full.chars().next() {
+        return false;
+    }
+    let mut rest = full.chars();
+    abbr.chars().all(|c| rest.any(|f| f == c))
+}
+
+/// 检查一个词根缩写，返回全部问题；为空表示通过
+/// 与英文全称的一致性：缩写词典中登记的约定缩写直接通过，否则缩写须是全称字母的有序子序列 (拼音缩写等无全称的情况跳过)
+pub async fn lint(
+    pool: &PgPool,
+    en_abbr: &str,
+    en_full_name: Option<&str>,
+    config: &LintConfig,
+) -> Result<Vec<LintIssue>, sqlx::Error> {
+    let mut issues = Vec::new();
+
+    if en_abbr.trim() != en_abbr {
+        issues.push(LintIssue { code: "WHITESPACE", message: "缩写首尾不能包含空白".to_string() });
+    }
+    let abbr = en_abbr.trim();
+
+    let len = abbr.chars().count();
+    if len < config.min_length || len > config.max_length {
+        issues.push(LintIssue {
+            code: "LENGTH",
+            message: format!("长度 {} 不在 {}~{} 之间", len, config.min_length, config.max_length),
+        });
+    }
+
+    let has_separator = abbr.chars().any(|c| c == '_' || c == '-' || c.is_whitespace());
+    if has_separator && !config.allow_separators {
+        issues.push(LintIssue { code: "SEPARATOR", message: "缩写不能包含下划线、连字符或空格".to_string() });
+    }
+    let charset_ok = abbr.chars().all(|c| match config.charset.as_str() {
+        "alpha" => c.is_ascii_alphabetic(),
+        _ => c.is_ascii_alphanumeric(),
+    } || c == '_' || c == '-' || c.is_whitespace());
+    if !charset_ok {
+        let allowed = if config.charset == "alpha" { "英文字母" } else { "英文字母和数字" };
+        issues.push(LintIssue { code: "CHARSET", message: format!("只允许包含{}", allowed) });
+    }
+    if abbr.starts_with(|c: char| !c.is_ascii_alphabetic()) {
+        issues.push(LintIssue { code: "LEADING", message: "必须以英文字母开头".to_string() });
+    }
+
+    let case_ok = match config.case.as_str() {
+        "upper" => !abbr.chars().any(|c| c.is_ascii_lowercase()),
+        "any" => true,
+        _ => !abbr.chars().any(|c| c.is_ascii_uppercase()),
+    };
+    if !case_ok {
+        issues.push(LintIssue { code: "CASE", message: format!("不符合大小写规范 [{}]", config.case) });
+    }
+
+    if let Some(dialect) = reserved_words::reserved_in(abbr, &config.dialects) {
+        issues.push(LintIssue { code: "RESERVED", message: format!("[{}] 是 {} 方言的 SQL 保留字", abbr, dialect) });
+    }
+
+    let full = en_full_name.map(str::trim).filter(|n| !n.is_empty());
+    if let (true, Some(full)) = (config.full_name_check, full) {
+        let lower = abbr.to_lowercase();
+        let phrase = reverse_service::split_identifier(full).join(" ");
+        let in_dictionary = sqlx::query_scalar!(
+            "SELECT count(*) FROM abbreviation_dictionary WHERE full_word = $1 AND lower(abbr) = $2",
+            phrase, lower
+        )
+        .fetch_one(pool)
+        .await?
+        .unwrap_or(0) > 0;
+        let letters: String = phrase.chars().filter(|c| c.is_ascii_alphanumeric()).collect();
+        let letters_only: String = lower.chars().filter(|c| c.is_ascii_alphanumeric()).collect();
+        if !in_dictionary && !is_subsequence(&letters_only, &letters) {
+            issues.push(LintIssue {
+                code: "FULL_NAME",
+                message: format!("缩写 [{}] 与英文全称 [{}] 不一致，字母应按顺序取自全称", abbr, full),
+            });
+        }
+    }
+
+    Ok(issues)
+}
+
+/// 把问题列表拼接为一行说明，用于批量导入的逐行报错
+pub fn summarize(issues: &[LintIssue]) -> String {
+    issues.iter().map(|i| i.message.as_str()).collect::<Vec<_>>().join("；")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config() -> LintConfig {
+        LintConfig {
+            charset: "alnum".into(),
+            case: "lower".into(),
+            min_length: 2,
+            max_length: 10,
+            allow_separators: false,
+            dialects: ["common", "mysql", "postgres", "oracle", "hive"].iter().map(|d| d.to_string()).collect(),
+            full_name_check: true,
+        }
+    }
+
+    /// 不带英文全称时不访问数据库，惰性连接池不会真正建立连接
+    async fn codes(abbr: &str, config: &LintConfig) -> Vec<&'static str> {
+        let pool = PgPool::connect_lazy("postgres://localhost/unused").unwrap();
+        lint(&pool, abbr, None, config).await.unwrap().into_iter().map(|i| i.code).collect()
+    }
+
+    #[test]
+    fn subsequence_requires_same_first_letter_and_order() {
+        assert!(is_subsequence("amt", "amount"));
+        assert!(is_subsequence("cstnbr", "customernumber"));
+        assert!(!is_subsequence("mnt", "amount"));
+        assert!(!is_subsequence("atm", "amount"));
+        assert!(!is_subsequence("", "amount"));
+    }
+
+    #[tokio::test]
+    async fn accepts_well_formed_abbr() {
+        assert!(codes("amt", &config()).await.is_empty());
+        assert!(codes("addr2", &config()).await.is_empty());
+    }
+
+    #[tokio::test]
+    async fn reports_format_rules() {
+        assert_eq!(codes("a", &config()).await, vec!["LENGTH"]);
+        assert_eq!(codes(" amt", &config()).await, vec!["WHITESPACE"]);
+        assert_eq!(codes("cust_no", &config()).await, vec!["SEPARATOR"]);
+        assert_eq!(codes("2fa", &config()).await, vec!["LEADING"]);
+        assert_eq!(codes("Amt", &config()).await, vec!["CASE"]);
+        assert_eq!(codes("amt$", &config()).await, vec!["CHARSET"]);
+
+        let alpha = LintConfig { charset: "alpha".into(), ..config() };
+        assert_eq!(codes("addr2", &alpha).await, vec!["CHARSET"]);
+        let upper = LintConfig { case: "upper".into(), ..config() };
+        assert_eq!(codes("AMT", &upper).await, Vec::<&str>::new());
+        let separators = LintConfig { allow_separators: true, ..config() };
+        assert!(codes("cust_no", &separators).await.is_empty());
+    }
+
+    #[tokio::test]
+    async fn rejects_reserved_words_of_enabled_dialects() {
+        assert_eq!(codes("desc", &config()).await, vec!["RESERVED"]);
+        assert_eq!(codes("rownum", &config()).await, vec!["RESERVED"]);
+
+        // 只检查启用的方言，通用保留字始终检查
+        let mysql_only = LintConfig { dialects: vec!["mysql".into()], ..config() };
+        assert!(codes("rownum", &mysql_only).await.is_empty());
+        assert_eq!(codes("desc", &mysql_only).await, vec!["RESERVED"]);
+    }
+
+    #[test]
+    fn reserved_in_reports_dialect() {
+        let all: Vec<String> = reserved_words::DIALECTS.iter().map(|d| d.to_string()).collect();
+        assert_eq!(reserved_words::reserved_in("ORDER", &all), Some("common"));
+        assert_eq!(reserved_words::reserved_in("sysdate", &all), Some("oracle"));
+        assert_eq!(reserved_words::reserved_in("ilike", &all), Some("postgres"));
+        assert_eq!(reserved_words::reserved_in("amt", &all), None);
+    }
+
+    #[test]
+    fn summarize_joins_messages() {
+        let issues = vec![
+            LintIssue { code: "LENGTH", message: "长度 1 不在 2~10 之间".into() },
+            LintIssue { code: "CASE", message: "不符合大小写规范 [lower]".into() },
+        ];
+        assert_eq!(summarize(&issues), "长度 1 不在 2~10 之间；不符合大小写规范 [lower]");
+        assert_eq!(summarize(&[]), "");
+    }
+}
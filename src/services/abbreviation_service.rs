@@ -0,0 +1,280 @@
+use serde::Serialize;
+use sqlx::PgPool;
+use std::collections::HashMap;
+
+use crate::services::abbr_lint::{self, LintConfig};
+use crate::services::{pinyin_service, reverse_service};
+
+/// 支持的缩写生成规则
+pub const RULES: [&str; 4] = ["dictionary", "vowel_drop", "first_n", "pinyin"];
+
+#[derive(Debug, Serialize)]
+pub struct AbbrCandidate {
+    pub abbr: String,
+    pub rule: &'static str,
+    pub reason: String,
+    pub score: i32, // 越高越推荐
+}
+
+#[derive(Debug, Serialize)]
+pub struct SkippedCandidate {
+    pub abbr: String,
+    pub reason: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct AbbrSuggestion {
+    pub cn_name: String,
+    pub en_full_name: Option<String>,
+    pub candidates: Vec<AbbrCandidate>,
+    pub skipped: Vec<SkippedCandidate>, // 被排除的候选及原因 (已占用 / 不符合缩写规范)
+}
+
+/// 缩写生成配置，读取环境变量：
+/// ABBR_RULES 启用的规则及优先顺序 (默认 dictionary,vowel_drop,first_n,pinyin)
+/// ABBR_FIRST_N 截取前 N 位的长度列表 (默认 3,4)
+/// ABBR_MAX_LENGTH 缩写最大长度 (默认 10)
+struct AbbrConfig {
+    rules: Vec<&'static str>,
+    first_n: Vec<usize>,
+    max_length: usize,
+}
+
+impl AbbrConfig {
+    fn from_env() -> Self {
+        let rules = match std::env::var("ABBR_RULES") {
+            Ok(v) => v
+                .split(',')
+                .filter_map(|r| RULES.iter().copied().find(|k| *k == r.trim()))
+                .collect(),
+            Err(_) => RULES.to_vec(),
+        };
+        let first_n = std::env::var("ABBR_FIRST_N")
+            .ok()
+            .map(|v| v.split(',').filter_map(|n| n.trim().parse().ok()).filter(|n| *n >= 2).collect::<Vec<usize>>())
+            .filter(|v| !v.is_empty())
+            .unwrap_or_else(|| vec![3, 4]);
+        let max_length = std::env::var("ABBR_MAX_LENGTH").ok().and_then(|v| v.parse().ok()).unwrap_or(10);
+        AbbrConfig { rules, first_n, max_length }
+    }
+}
+
+/// 去掉首字母之后的元音，并合并连续重复字母，如 "balance" -> "blnc"，"buffer" -> "bfr"
+fn vowel_drop(word: &str) -> String {
+    let mut out = String::new();
+    for (i, c) in word.chars().enumerate() {
+        if i > 0 && "aeiou".contains(c) {
+            continue;
+        }
+        if out.ends_with(c) {
+            continue;
+        }
+        out.push(c);
+    }
+    out
+}
+
+/// 基本格式过滤 (小写字母开头、仅字母数字、长度 2~max)，同一缩写保留得分最高的候选
+fn keep_best(raw: Vec<AbbrCandidate>, max_length: usize) -> HashMap<String, AbbrCandidate> {
+    let mut best: HashMap<String, AbbrCandidate> = HashMap::new();
+    for c in raw {
+        let len = c.abbr.chars().count();
+        let well_formed = len >= 2
+            && len <= max_length
+            && c.abbr.starts_with(|ch: char| ch.is_ascii_lowercase())
+            && c.abbr.chars().all(|ch| ch.is_ascii_alphanumeric());
+        if !well_formed {
+            continue;
+        }
+        match best.get(&c.abbr) {
+            Some(existing) if existing.score >= c.score => {}
+            _ => {
+                best.insert(c.abbr.clone(), c);
+            }
+        }
+    }
+    best
+}
+
+/// 为一个词根生成带排序的缩写候选
+/// 规则按配置顺序决定基础分，排前的规则得分更高；同一缩写由多个规则得出时保留得分最高的一个
+pub async fn suggest(pool: &PgPool, cn_name: &str, en_full_name: Option<&str>) -> Result<AbbrSuggestion, sqlx::Error> {
+    let config = AbbrConfig::from_env();
+    let words: Vec<String> = en_full_name
+        .map(reverse_service::split_identifier)
+        .unwrap_or_default()
+        .into_iter()
+        .filter(|w| w.chars().all(|c| c.is_ascii_alphabetic()))
+        .collect();
+
+    let mut raw: Vec<AbbrCandidate> = Vec::new();
+    for (order, &rule) in config.rules.iter().enumerate() {
+        let base = 100 - order as i32 * 20;
+        match rule {
+            "dictionary" if !words.is_empty() => {
+                let phrase = words.join(" ");
+                let mut keys = words.clone();
+                keys.push(phrase.clone());
+                let dict: HashMap<String, String> = sqlx::query!(
+                    "SELECT full_word, abbr FROM abbreviation_dictionary WHERE full_word = ANY($1)",
+                    &keys
+                )
+                .fetch_all(pool)
+                .await?
+                .into_iter()
+                .map(|r| (r.full_word, r.abbr.to_lowercase()))
+                .collect();
+
+                if let Some(abbr) = dict.get(&phrase) {
+                    raw.push(AbbrCandidate {
+                        abbr: abbr.clone(),
+                        rule,
+                        reason: format!("缩写词典收录: {} -> {}", phrase, abbr),
+                        score: base,
+                    });
+                } else if words.len() > 1 && words.iter().any(|w| dict.contains_key(w)) {
+                    // 词组逐词查词典，未收录的词保留原词
+                    let abbr: String = words.iter().map(|w| dict.get(w).cloned().unwrap_or_else(|| w.clone())).collect();
+                    raw.push(AbbrCandidate {
+                        abbr,
+                        rule,
+                        reason: "词组逐词采用缩写词典，未收录的词保留原词".to_string(),
+                        score: base - 5,
+                    });
+                }
+            }
+            "vowel_drop" if !words.is_empty() => {
+                let abbr: String = words.iter().map(|w| vowel_drop(w)).collect();
+                raw.push(AbbrCandidate {
+                    abbr,
+                    rule,
+                    reason: "去除首字母后的元音并合并重复字母".to_string(),
+                    score: base,
+                });
+            }
+            "first_n" if !words.is_empty() => {
+                if words.len() == 1 {
+                    for (i, n) in config.first_n.iter().enumerate() {
+                        let abbr: String = words[0].chars().take(*n).collect();
+                        if abbr.len() < words[0].len() {
+                            raw.push(AbbrCandidate {
+                                abbr,
+                                rule,
+                                reason: format!("取英文全称前 {} 个字母", n),
+                                score: base - i as i32,
+                            });
+                        }
+                    }
+                } else {
+                    let abbr: String = words.iter().filter_map(|w| w.chars().next()).collect();
+                    raw.push(AbbrCandidate { abbr, rule, reason: "取词组各词首字母".to_string(), score: base });
+                }
+            }
+            "pinyin" => {
+                // 没有英文全称时作为主要来源，否则仅作兜底
+                let base = if words.is_empty() { base.max(60) } else { base.min(20) };
+                let initials = pinyin_service::initials(cn_name);
+                raw.push(AbbrCandidate {
+                    abbr: initials,
+                    rule,
+                    reason: format!("中文名 [{}] 的拼音首字母", cn_name),
+                    score: base,
+                });
+                let full = pinyin_service::full(cn_name);
+                raw.push(AbbrCandidate {
+                    abbr: full,
+                    rule,
+                    reason: format!("中文名 [{}] 的拼音全拼", cn_name),
+                    score: base - 10,
+                });
+            }
+            _ => {}
+        }
+    }
+
+    let best = keep_best(raw, config.max_length);
+
+    // 排除已被占用的缩写
+    let abbrs: Vec<String> = best.keys().cloned().collect();
+    let used: HashMap<String, String> = sqlx::query!(
+        r#"SELECT lower(en_abbr) AS "abbr!", cn_name FROM standard_word_roots WHERE lower(en_abbr) = ANY($1)"#,
+        &abbrs
+    )
+    .fetch_all(pool)
+    .await?
+    .into_iter()
+    .map(|r| (r.abbr, r.cn_name))
+    .collect();
+
+    // 其余候选须通过缩写规范检查 (含各方言保留字)，拼音候选不与英文全称比对
+    let lint_config = LintConfig::from_env();
+    let mut candidates = Vec::new();
+    let mut skipped = Vec::new();
+    for (abbr, c) in best {
+        if let Some(owner) = used.get(&abbr) {
+            skipped.push(SkippedCandidate { reason: format!("已被词根 [{}] 使用", owner), abbr });
+            continue;
+        }
+        let full = if c.rule == "pinyin" { None } else { en_full_name };
+        let issues = abbr_lint::lint(pool, &abbr, full, &lint_config).await?;
+        if issues.is_empty() {
+            candidates.push(c);
+        } else {
+            skipped.push(SkippedCandidate { reason: abbr_lint::summarize(&issues), abbr });
+        }
+    }
+    candidates.sort_by(|a, b| b.score.cmp(&a.score).then(a.abbr.len().cmp(&b.abbr.len())).then(a.abbr.cmp(&b.abbr)));
+    skipped.sort_by(|a, b| a.abbr.cmp(&b.abbr));
+
+    Ok(AbbrSuggestion {
+        cn_name: cn_name.to_string(),
+        en_full_name: en_full_name.map(String::from),
+        candidates,
+        skipped,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn candidate(abbr: &str, rule: &'static str, score: i32) -> AbbrCandidate {
+        AbbrCandidate { abbr: abbr.to_string(), rule, reason: String::new(), score }
+    }
+
+    #[test]
+    fn vowel_drop_keeps_first_letter_and_merges_repeats() {
+        assert_eq!(vowel_drop("balance"), "blnc");
+        assert_eq!(vowel_drop("buffer"), "bfr");
+        assert_eq!(vowel_drop("amount"), "amnt");
+        assert_eq!(vowel_drop("id"), "id");
+        assert_eq!(vowel_drop(""), "");
+    }
+
+    #[test]
+    fn keep_best_drops_malformed_candidates() {
+        let raw = vec![
+            candidate("a", "first_n", 80), // 过短
+            candidate("customernumber", "vowel_drop", 80), // 超长
+            candidate("2fa", "pinyin", 20), // 数字开头
+            candidate("Cust", "dictionary", 100), // 大写开头
+            candidate("cst_no", "first_n", 60), // 含下划线
+            candidate("cstno", "vowel_drop", 80),
+        ];
+        let best = keep_best(raw, 10);
+        assert_eq!(best.keys().collect::<Vec<_>>(), vec!["cstno"]);
+    }
+
+    #[test]
+    fn keep_best_prefers_higher_score_for_same_abbr() {
+        let raw = vec![
+            candidate("amt", "first_n", 60),
+            candidate("amt", "dictionary", 100),
+            candidate("amt", "pinyin", 20),
+        ];
+        let best = keep_best(raw, 10);
+        assert_eq!(best.len(), 1);
+        assert_eq!(best["amt"].rule, "dictionary");
+        assert_eq!(best["amt"].score, 100);
+    }
+}
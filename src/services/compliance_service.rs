@@ -0,0 +1,233 @@
+use serde::Serialize;
+use sqlx::PgPool;
+use std::collections::HashMap;
+use crate::models::naming_profile::NamingProfile;
+use crate::services::{mapping_service, naming_service, reverse_service};
+
+/// 字段命名校验模式
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CheckMode {
+    Off,     // 不校验 (历史行为)
+    Warn,    // 校验但放行，结果随响应返回
+    Enforce, // 存在 error 级问题时拒绝写入
+}
+
+impl CheckMode {
+    /// 解析请求中的模式，未指定时读取环境变量 FIELD_NAME_CHECK_MODE (默认 off)
+    pub fn resolve(requested: Option<&str>) -> Result<Self, String> {
+        let raw = match requested {
+            Some(m) => m.to_string(),
+            None => std::env::var("FIELD_NAME_CHECK_MODE").unwrap_or_else(|_| "off".into()),
+        };
+        match raw.trim().to_lowercase().as_str() {
+            "off" | "" => Ok(CheckMode::Off),
+            "warn" => Ok(CheckMode::Warn),
+            "enforce" => Ok(CheckMode::Enforce),
+            other => Err(format!("不支持的校验模式 [{}]，可选: off / warn / enforce", other)),
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct ComplianceIssue {
+    pub code: &'static str,
+    pub severity: &'static str, // error / warning
+    pub message: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ComplianceReport {
+    pub passed: bool,
+    pub profile: String,
+    pub expected_en: String, // 按中文名推导出的标准英文名
+    pub issues: Vec<ComplianceIssue>,
+}
+
+struct RootInfo {
+    id: i32,
+    cn_name: String,
+    status: String,
+    is_class_word: bool,
+}
+
+fn error(code: &'static str, message: String) -> ComplianceIssue {
+    ComplianceIssue { code, severity: "error", message }
+}
+
+fn warning(code: &'static str, message: String) -> ComplianceIssue {
+    ComplianceIssue { code, severity: "warning", message }
+}
+
+/// 仅有 warning 级问题时视为通过
+fn verdict(issues: &[ComplianceIssue]) -> bool {
+    issues.iter().all(|i| i.severity != "error")
+}
+
+/// 去掉规范要求的前缀/后缀后切分英文名
+fn name_segments(en_name: &str, profile: &NamingProfile) -> Vec<String> {
+    let mut segments = reverse_service::split_identifier(en_name);
+    if let Some(p) = profile.prefix.as_deref().filter(|p| !p.is_empty()) {
+        let prefix = reverse_service::split_identifier(p);
+        if segments.starts_with(&prefix) {
+            segments.drain(..prefix.len());
+        }
+    }
+    if let Some(s) = profile.suffix.as_deref().filter(|s| !s.is_empty()) {
+        let suffix = reverse_service::split_identifier(s);
+        if segments.ends_with(&suffix) {
+            segments.truncate(segments.len() - suffix.len());
+        }
+    }
+    segments
+}
+
+/// 对一组 (中文名, 英文名) 做命名合规校验
+/// 检查项：格式规范、片段均为可用词根、片段顺序与中文分词一致、类别词 (末段) 正确、未使用废弃词根、不与现有标准字段重复
+pub async fn check_field_name(
+    pool: &PgPool,
+    cn_name: &str,
+    en_name: &str,
+    profile: &NamingProfile,
+    exclude_field_id: Option<i32>,
+) -> Result<ComplianceReport, sqlx::Error> {
+    let mut issues = Vec::new();
+
+    // 1. 格式规范
+    for msg in naming_service::validate_name(en_name, profile) {
+        issues.push(error("FORMAT", msg));
+    }
+
+    // 2. 按中文名推导期望的词根序列
+    let suggestion = mapping_service::suggest_field_name(pool, cn_name, profile).await;
+    for w in &suggestion.missing_words {
+        issues.push(warning("CN_UNMAPPED", format!("中文词 [{}] 没有对应的标准词根", w)));
+    }
+
+    // 3. 英文片段 -> 词根。与推导结果完全一致时 (含超长缩短的情况) 直接采用推导出的词根序列
+    let segments = name_segments(en_name, profile);
+    let seg_ids: Vec<Option<i32>> = if en_name.eq_ignore_ascii_case(&suggestion.suggested_en) {
+        suggestion.matched_ids.iter().map(|id| Some(*id)).collect()
+    } else {
+        let rows = sqlx::query!(
+            "SELECT id, en_abbr FROM standard_word_roots WHERE lower(en_abbr) = ANY($1) ORDER BY (status = 'active') DESC, id",
+            &segments
+        )
+        .fetch_all(pool)
+        .await?;
+        let mut by_abbr: HashMap<String, i32> = HashMap::new();
+        for r in rows {
+            by_abbr.entry(r.en_abbr.to_lowercase()).or_insert(r.id);
+        }
+        segments.iter().map(|s| by_abbr.get(s).copied()).collect()
+    };
+
+    for (seg, id) in segments.iter().zip(&seg_ids) {
+        if id.is_none() {
+            issues.push(error("UNKNOWN_SEGMENT", format!("片段 [{}] 不是已登记的词根缩写", seg)));
+        }
+    }
+
+    // 4. 废弃词根
+    let known_ids: Vec<i32> = seg_ids.iter().flatten().copied().collect();
+    let roots: HashMap<i32, RootInfo> = sqlx::query!(
+        "SELECT id, cn_name, status, is_class_word FROM standard_word_roots WHERE id = ANY($1)",
+        &known_ids
+    )
+    .fetch_all(pool)
+    .await?
+    .into_iter()
+    .map(|r| (r.id, RootInfo { id: r.id, cn_name: r.cn_name, status: r.status, is_class_word: r.is_class_word }))
+    .collect();
+
+    for root in known_ids.iter().filter_map(|id| roots.get(id)) {
+        if root.status != "active" {
+            issues.push(error("DEPRECATED_ROOT", format!("词根 [{}] (ID={}) 已废弃", root.cn_name, root.id)));
+        }
+    }
+
+    // 5. 片段顺序与类别词：仅在两边都完整映射时比较
+    let fully_mapped = known_ids.len() == seg_ids.len() && suggestion.missing_words.is_empty();
+    if fully_mapped && known_ids != suggestion.matched_ids {
+        let mut a = known_ids.clone();
+        let mut b = suggestion.matched_ids.clone();
+        a.sort_unstable();
+        b.sort_unstable();
+        if a == b {
+            issues.push(error("ORDER_MISMATCH", format!(
+                "片段顺序与中文分词不一致，期望: {}", suggestion.suggested_en
+            )));
+        } else {
+            issues.push(error("SEGMENT_MISMATCH", format!(
+                "英文片段与中文名 [{}] 的词根不对应，期望: {}", cn_name, suggestion.suggested_en
+            )));
+        }
+    }
+    if fully_mapped && known_ids.last() != suggestion.matched_ids.last() {
+        issues.push(error("CLASS_WORD", "末段类别词与中文名的末尾词根不一致".to_string()));
+    }
+    if let Some(last) = seg_ids.last().copied().flatten().and_then(|id| roots.get(&id)) {
+        if !last.is_class_word {
+            issues.push(error("NOT_CLASS_WORD", format!("末段词根 [{}] 不是类别词", last.cn_name)));
+        }
+    }
+
+    // 6. 与现有标准字段重复
+    let dup = sqlx::query!(
+        r#"SELECT id, field_cn_name, field_en_name FROM standard_fields
+           WHERE (lower(field_en_name) = lower($1) OR field_cn_name = $2) AND id <> $3
+           LIMIT 1"#,
+        en_name, cn_name, exclude_field_id.unwrap_or(0)
+    )
+    .fetch_optional(pool)
+    .await?;
+    if let Some(d) = dup {
+        issues.push(error("DUPLICATE", format!(
+            "与现有标准字段重复: ID={}, {} / {}", d.id, d.field_cn_name, d.field_en_name
+        )));
+    }
+
+    let passed = verdict(&issues);
+    Ok(ComplianceReport { passed, profile: profile.name.clone(), expected_en: suggestion.suggested_en, issues })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::naming_service::builtin_default;
+
+    #[test]
+    fn resolves_requested_mode() {
+        assert_eq!(CheckMode::resolve(Some("off")).unwrap(), CheckMode::Off);
+        assert_eq!(CheckMode::resolve(Some("")).unwrap(), CheckMode::Off);
+        assert_eq!(CheckMode::resolve(Some(" Warn ")).unwrap(), CheckMode::Warn);
+        assert_eq!(CheckMode::resolve(Some("ENFORCE")).unwrap(), CheckMode::Enforce);
+    }
+
+    #[test]
+    fn rejects_unknown_mode() {
+        assert_eq!(
+            CheckMode::resolve(Some("strict")).unwrap_err(),
+            "不支持的校验模式 [strict]，可选: off / warn / enforce"
+        );
+    }
+
+    #[test]
+    fn warnings_alone_pass() {
+        assert!(verdict(&[]));
+        assert!(verdict(&[warning("CN_UNMAPPED", "中文词 [的] 没有对应的标准词根".into())]));
+        assert!(!verdict(&[
+            warning("CN_UNMAPPED", "中文词 [的] 没有对应的标准词根".into()),
+            error("UNKNOWN_SEGMENT", "片段 [xx] 不是已登记的词根缩写".into()),
+        ]));
+    }
+
+    #[test]
+    fn segments_strip_profile_affixes() {
+        let p = NamingProfile { prefix: Some("ods".into()), suffix: Some("cd".into()), ..builtin_default() };
+        assert_eq!(name_segments("ods_cust_type_cd", &p), vec!["cust", "type"]);
+        assert_eq!(name_segments("odsCustTypeCd", &p), vec!["cust", "type"]);
+        // 缺少前后缀时不截取
+        assert_eq!(name_segments("cust_type", &p), vec!["cust", "type"]);
+        assert_eq!(name_segments("cust_open_dt", &builtin_default()), vec!["cust", "open", "dt"]);
+    }
+}
@@ -0,0 +1,227 @@
+use jieba_rs::Jieba;
+use once_cell::sync::Lazy;
+use sqlx::postgres::PgListener;
+use sqlx::PgPool;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::Mutex;
+
+use crate::services::{stop_word_service, typeahead_service};
+use crate::JIEBA;
+
+/// 跨实例同步词典的 Postgres 通知频道
+pub const DICTIONARY_CHANNEL: &str = "dictionary_changed";
+
+// 当前进程的实例标识，用于忽略自己发出的通知
+static INSTANCE_ID: Lazy<String> =
+    Lazy::new(|| format!("{}-{:08x}", std::process::id(), rand::random::<u32>()));
+
+// 不含自定义词条的原始分词器，仅供分词调试对比，首次使用时加载
+static BASELINE_JIEBA: Lazy<Jieba> = Lazy::new(Jieba::new);
+
+// 串行化重建，避免并发重建时旧词典覆盖新词典
+static REBUILD_LOCK: Lazy<Mutex<()>> = Lazy::new(|| Mutex::new(()));
+
+// 收到通知后等待的合并窗口，窗口内的后续通知合并为一次同步
+const COALESCE_WINDOW: Duration = Duration::from_millis(500);
+
+/// 变更范围
+#[derive(Debug, Clone, Copy)]
+pub enum Scope {
+    Roots,     // 词根增删改 -> 重建分词词典与联想索引
+    StopWords, // 停用词变更 -> 重新加载停用词表
+    Fields,    // 标准字段增删改 -> 重建联想索引
+}
+
+impl Scope {
+    fn as_str(&self) -> &'static str {
+        match self {
+            Scope::Roots => "roots",
+            Scope::StopWords => "stop_words",
+            Scope::Fields => "fields",
+        }
+    }
+}
+
+/// 加载自定义词典词条 (词根中文名及不含空格的同义词)，保证同义词作为整词切出
+pub async fn load_custom_words(pool: &PgPool) -> Result<Vec<String>, sqlx::Error> {
+    sqlx::query_scalar!(
+        r#"SELECT cn_name AS "word!" FROM standard_word_roots
+           UNION
+           SELECT term FROM word_root_synonyms WHERE weight > 0 AND term !~ '\s'"#
+    )
+    .fetch_all(pool)
+    .await
+}
+
+/// 获取不含自定义词条的原始分词器
+pub fn baseline() -> &'static Jieba {
+    &BASELINE_JIEBA
+}
+
+/// 从数据库全量重建分词词典，构建完成后整体替换，不在原词典上增删
+/// 读取词根失败时保留当前词典，避免把自定义词条清空
+pub async fn rebuild(pool: &PgPool) {
+    let _guard = REBUILD_LOCK.lock().await;
+    let words = match load_custom_words(pool).await {
+        Ok(w) => w,
+        Err(e) => {
+            tracing::error!("!!! 读取词根失败，跳过词典重建: {}", e);
+            return;
+        }
+    };
+    let count = words.len();
+
+    // 加载默认词库较耗时，放到阻塞线程池执行
+    let built = tokio::task::spawn_blocking(move || {
+        let mut jieba = Jieba::new();
+        for w in &words {
+            jieba.add_word(w, Some(99999), None);
+        }
+        jieba
+    })
+    .await;
+
+    match built {
+        Ok(jieba) => {
+            *JIEBA.write().await = Arc::new(jieba);
+            tracing::info!("分词词典已重建，自定义词条 {} 个", count);
+        }
+        Err(e) => tracing::error!("!!! 分词词典重建失败: {}", e),
+    }
+}
+
+/// 通知其他实例同步变更
+async fn publish(pool: &PgPool, scope: Scope) {
+    let payload = serde_json::json!({ "origin": INSTANCE_ID.as_str(), "scope": scope.as_str() }).to_string();
+    if let Err(e) = sqlx::query("SELECT pg_notify($1, $2)")
+        .bind(DICTIONARY_CHANNEL)
+        .bind(payload)
+        .execute(pool)
+        .await
+    {
+        tracing::warn!("--- 词典变更通知发送失败: {}", e);
+    }
+}
+
+/// 词根变更后调用：本实例立即重建，并通知其他实例
+pub async fn on_roots_changed(pool: &PgPool) {
+    rebuild(pool).await;
+    typeahead_service::refresh(pool).await;
+    publish(pool, Scope::Roots).await;
+}
+
+/// 停用词变更后调用：本实例立即重新加载，并通知其他实例
+pub async fn on_stop_words_changed(pool: &PgPool) {
+    stop_word_service::reload(pool).await;
+    publish(pool, Scope::StopWords).await;
+}
+
+/// 标准字段变更后调用：本实例立即重建联想索引，并通知其他实例
+pub async fn on_fields_changed(pool: &PgPool) {
+    typeahead_service::refresh(pool).await;
+    publish(pool, Scope::Fields).await;
+}
+
+/// 一个合并窗口内收到的待同步变更
+#[derive(Default)]
+struct Pending {
+    roots: bool,
+    stop_words: bool,
+    fields: bool,
+}
+
+impl Pending {
+    /// 连接中断后的全量同步
+    fn all() -> Self {
+        Pending { roots: true, stop_words: true, fields: true }
+    }
+
+    /// 记录一条通知，忽略本实例发出的通知
+    fn add(&mut self, payload: &str) {
+        let msg: serde_json::Value = serde_json::from_str(payload).unwrap_or_default();
+        if msg["origin"].as_str() == Some(INSTANCE_ID.as_str()) {
+            return;
+        }
+        match msg["scope"].as_str() {
+            Some("stop_words") => self.stop_words = true,
+            Some("fields") => self.fields = true,
+            _ => self.roots = true,
+        }
+    }
+
+    /// 每类变更只同步一次：词根变更重建词典，词根或字段变更刷新联想索引
+    async fn apply(self, pool: &PgPool) {
+        if self.roots {
+            rebuild(pool).await;
+        }
+        if self.stop_words {
+            stop_word_service::reload(pool).await;
+        }
+        if self.roots || self.fields {
+            typeahead_service::refresh(pool).await;
+        }
+    }
+}
+
+/// 后台监听其他实例的变更通知 (词典、停用词与联想索引)
+/// 收到通知后在合并窗口内继续收取，批量导入等连续变更只触发一次重建
+/// 连接中断时 PgListener 会自动重连，重连后做一次全量同步以弥补期间错过的通知
+pub fn spawn_listener(pool: PgPool) {
+    tokio::spawn(async move {
+        loop {
+            let mut listener = match PgListener::connect_with(&pool).await {
+                Ok(l) => l,
+                Err(e) => {
+                    tracing::error!("!!! 词典通知监听连接失败: {}", e);
+                    tokio::time::sleep(Duration::from_secs(5)).await;
+                    continue;
+                }
+            };
+            if let Err(e) = listener.listen(DICTIONARY_CHANNEL).await {
+                tracing::error!("!!! 订阅词典通知失败: {}", e);
+                tokio::time::sleep(Duration::from_secs(5)).await;
+                continue;
+            }
+            tracing::info!("已订阅词典变更通知: {}", DICTIONARY_CHANNEL);
+
+            let mut failed = false;
+            while !failed {
+                let mut pending = match listener.try_recv().await {
+                    Ok(Some(n)) => {
+                        let mut pending = Pending::default();
+                        pending.add(n.payload());
+                        pending
+                    }
+                    Ok(None) => {
+                        tracing::warn!("--- 词典通知连接中断，重连后执行全量同步");
+                        Pending::all()
+                    }
+                    Err(e) => {
+                        tracing::error!("!!! 词典通知监听异常: {}", e);
+                        break;
+                    }
+                };
+
+                let deadline = tokio::time::Instant::now() + COALESCE_WINDOW;
+                loop {
+                    match tokio::time::timeout_at(deadline, listener.try_recv()).await {
+                        Err(_) => break, // 合并窗口结束
+                        Ok(Ok(Some(n))) => pending.add(n.payload()),
+                        Ok(Ok(None)) => {
+                            tracing::warn!("--- 词典通知连接中断，重连后执行全量同步");
+                            pending = Pending::all();
+                        }
+                        Ok(Err(e)) => {
+                            tracing::error!("!!! 词典通知监听异常: {}", e);
+                            failed = true;
+                            break;
+                        }
+                    }
+                }
+                pending.apply(&pool).await;
+            }
+            tokio::time::sleep(Duration::from_secs(5)).await;
+        }
+    });
+}
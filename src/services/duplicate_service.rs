@@ -0,0 +1,158 @@
+use qdrant_client::qdrant::{point_id::PointIdOptions, SearchPointsBuilder};
+use serde::Serialize;
+use std::collections::HashSet;
+
+use crate::AppState;
+
+/// 与待创建词根疑似重复的已有词根
+#[derive(Debug, Serialize)]
+pub struct RootConflict {
+    pub kind: &'static str,  // cn_name / en_abbr / synonym / trigram / vector
+    pub root_id: i32,
+    pub cn_name: String,
+    pub en_abbr: String,
+    pub detail: String,
+    pub score: Option<f32>,  // 相似度 (仅 trigram / vector)
+}
+
+/// 待检查的词根信息
+pub struct RootCandidate<'a> {
+    pub cn_name: &'a str,
+    pub en_abbr: &'a str,
+    pub en_full_name: Option<&'a str>,
+    pub terms: Vec<String>, // 已规范化的同义词
+}
+
+fn threshold(key: &str, default: f32) -> f32 {
+    std::env::var(key).ok().and_then(|v| v.parse().ok()).unwrap_or(default)
+}
+
+/// 创建词根前的查重：中文名完全重复、缩写重复、同义词与已有词根交叉、中文名字面相似 (pg_trgm)、语义相似 (word_roots 向量)
+/// 相似度阈值分别读取 ROOT_TRGM_THRESHOLD (默认 0.6) 与 ROOT_VECTOR_THRESHOLD (默认 0.9)
+pub async fn find_root_conflicts(state: &AppState, c: &RootCandidate<'_>) -> Result<Vec<RootConflict>, sqlx::Error> {
+    let mut conflicts = Vec::new();
+    let mut seen: HashSet<i32> = HashSet::new();
+
+    // 1. 中文名 / 英文缩写完全重复
+    let same = sqlx::query!(
+        r#"SELECT id, cn_name, en_abbr FROM standard_word_roots
+           WHERE cn_name = $1 OR lower(en_abbr) = lower($2) ORDER BY id"#,
+        c.cn_name, c.en_abbr
+    )
+    .fetch_all(&state.db)
+    .await?;
+    for r in same {
+        let (kind, detail) = if r.cn_name == c.cn_name {
+            ("cn_name", format!("中文名 [{}] 已存在", r.cn_name))
+        } else {
+            ("en_abbr", format!("英文缩写 [{}] 已被占用", r.en_abbr))
+        };
+        seen.insert(r.id);
+        conflicts.push(RootConflict { kind, root_id: r.id, cn_name: r.cn_name, en_abbr: r.en_abbr, detail, score: None });
+    }
+
+    // 2. 同义词交叉：新中文名是已有同义词，或新同义词是已有中文名 / 同义词
+    let mut words: Vec<String> = c.terms.iter().map(|t| t.to_lowercase()).collect();
+    words.push(c.cn_name.to_lowercase());
+    let overlap = sqlx::query!(
+        r#"SELECT r.id AS "id!", r.cn_name AS "cn_name!", r.en_abbr AS "en_abbr!", s.term AS "term!" FROM word_root_synonyms s
+           JOIN standard_word_roots r ON r.id = s.root_id
+           WHERE lower(s.term) = ANY($1)
+           UNION ALL
+           SELECT id, cn_name, en_abbr, cn_name FROM standard_word_roots WHERE cn_name = ANY($2)
+           ORDER BY 1"#,
+        &words, &c.terms
+    )
+    .fetch_all(&state.db)
+    .await?;
+    for r in overlap {
+        if seen.contains(&r.id) {
+            continue;
+        }
+        let detail = if r.term.eq_ignore_ascii_case(c.cn_name) {
+            format!("中文名 [{}] 是词根 [{}] 的同义词", c.cn_name, r.cn_name)
+        } else if r.term == r.cn_name {
+            format!("同义词 [{}] 是已有词根的中文名", r.term)
+        } else {
+            format!("同义词 [{}] 已属于词根 [{}]", r.term, r.cn_name)
+        };
+        seen.insert(r.id);
+        conflicts.push(RootConflict { kind: "synonym", root_id: r.id, cn_name: r.cn_name, en_abbr: r.en_abbr, detail, score: None });
+    }
+
+    // 3. 中文名字面相似
+    let similar = sqlx::query!(
+        r#"SELECT id, cn_name, en_abbr, similarity(cn_name, $1) AS "score!"
+           FROM standard_word_roots
+           WHERE cn_name % $1 AND similarity(cn_name, $1) >= $2
+           ORDER BY 4 DESC LIMIT 5"#,
+        c.cn_name, threshold("ROOT_TRGM_THRESHOLD", 0.6)
+    )
+    .fetch_all(&state.db)
+    .await?;
+    for r in similar {
+        if seen.insert(r.id) {
+            conflicts.push(RootConflict {
+                kind: "trigram",
+                root_id: r.id,
+                detail: format!("中文名与 [{}] 字面相似", r.cn_name),
+                cn_name: r.cn_name,
+                en_abbr: r.en_abbr,
+                score: Some(r.score),
+            });
+        }
+    }
+
+    // 4. 语义相似：与词根入库时相同的向量文本，在 word_roots 集合中检索
+    let text = format!("{} {} {}", c.cn_name, c.en_full_name.unwrap_or(""), c.terms.join(" "));
+    let embeddings_res = {
+        let mut model = state.embed_model.lock();
+        model.embed(vec![text], None)
+    };
+    let vector_threshold = threshold("ROOT_VECTOR_THRESHOLD", 0.9);
+    match embeddings_res {
+        Ok(embeddings) => {
+            let search = state.qdrant
+                .search_points(
+                    SearchPointsBuilder::new("word_roots", embeddings[0].clone(), 5)
+                        .score_threshold(vector_threshold),
+                )
+                .await;
+            match search {
+                Ok(res) => {
+                    let hits: Vec<(i32, f32)> = res.result.into_iter()
+                        .filter_map(|p| match p.id?.point_id_options? {
+                            PointIdOptions::Num(n) => Some((n as i32, p.score)),
+                            PointIdOptions::Uuid(_) => None,
+                        })
+                        .filter(|(id, _)| !seen.contains(id))
+                        .collect();
+                    let ids: Vec<i32> = hits.iter().map(|(id, _)| *id).collect();
+                    let rows = sqlx::query!(
+                        "SELECT id, cn_name, en_abbr FROM standard_word_roots WHERE id = ANY($1)",
+                        &ids
+                    )
+                    .fetch_all(&state.db)
+                    .await?;
+                    for (id, score) in hits {
+                        if let Some(r) = rows.iter().find(|r| r.id == id) {
+                            seen.insert(id);
+                            conflicts.push(RootConflict {
+                                kind: "vector",
+                                root_id: r.id,
+                                cn_name: r.cn_name.clone(),
+                                en_abbr: r.en_abbr.clone(),
+                                detail: format!("与词根 [{}] 语义相近", r.cn_name),
+                                score: Some(score),
+                            });
+                        }
+                    }
+                }
+                Err(e) => tracing::warn!("--- 查重时向量检索失败，跳过语义查重: {}", e),
+            }
+        }
+        Err(e) => tracing::warn!("--- 查重时向量计算失败，跳过语义查重: {}", e),
+    }
+
+    Ok(conflicts)
+}
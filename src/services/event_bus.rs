@@ -0,0 +1,106 @@
+use serde::{Deserialize, Serialize};
+use sqlx::postgres::PgListener;
+use sqlx::{PgConnection, PgPool};
+use std::time::Duration;
+use tokio::sync::broadcast;
+
+use crate::services::dictionary_service::DICTIONARY_CHANNEL;
+
+/// 业务事件的 Postgres 通知频道
+pub const EVENT_CHANNEL: &str = "app_events";
+
+/// 本实例广播通道容量，订阅端落后超过该数量时收到 resync 事件
+const CAPACITY: usize = 256;
+
+/// 推送给管理端的事件
+/// kind: task_created / task_updated / task_resolved / dictionary_changed / resync
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AppEvent {
+    pub kind: String,
+    pub data: serde_json::Value,
+}
+
+pub fn channel() -> broadcast::Sender<AppEvent> {
+    broadcast::channel(CAPACITY).0
+}
+
+/// 发布事件：经 Postgres NOTIFY 送达所有实例 (含本实例)，在事务中调用时随提交发出
+pub async fn publish<'e, E: sqlx::PgExecutor<'e>>(db: E, kind: &str, data: serde_json::Value) {
+    let payload = serde_json::to_string(&AppEvent { kind: kind.to_string(), data }).unwrap_or_default();
+    if let Err(e) = sqlx::query("SELECT pg_notify($1, $2)")
+        .bind(EVENT_CHANNEL)
+        .bind(payload)
+        .execute(db)
+        .await
+    {
+        tracing::warn!("--- 事件通知发送失败: {}", e);
+    }
+}
+
+/// 任务事件：附带当前未结束任务数，管理端小红点直接使用
+/// 在事务中调用时随提交发出，计数包含本事务的变更
+pub async fn publish_task_event(conn: &mut PgConnection, kind: &str, task_id: i32, task_type: &str, status: &str) {
+    let open_count = sqlx::query_scalar!(
+        r#"SELECT count(*) AS "count!" FROM notification_tasks WHERE status IN ('open', 'in_progress')"#
+    )
+    .fetch_one(&mut *conn)
+    .await
+    .unwrap_or_default();
+    let data = serde_json::json!({
+        "task_id": task_id, "task_type": task_type, "status": status, "open_count": open_count,
+    });
+    publish(&mut *conn, kind, data).await;
+}
+
+/// 后台监听业务事件与词典变更通知，转发到本实例的广播通道
+/// 连接中断后重连，并向订阅端发送 resync 事件，提示重新拉取最新状态
+pub fn spawn_listener(pool: PgPool, sender: broadcast::Sender<AppEvent>) {
+    tokio::spawn(async move {
+        loop {
+            let mut listener = match PgListener::connect_with(&pool).await {
+                Ok(l) => l,
+                Err(e) => {
+                    tracing::error!("!!! 事件监听连接失败: {}", e);
+                    tokio::time::sleep(Duration::from_secs(5)).await;
+                    continue;
+                }
+            };
+            if let Err(e) = listener.listen_all([EVENT_CHANNEL, DICTIONARY_CHANNEL]).await {
+                tracing::error!("!!! 订阅事件通知失败: {}", e);
+                tokio::time::sleep(Duration::from_secs(5)).await;
+                continue;
+            }
+            tracing::info!("已订阅事件通知: {}, {}", EVENT_CHANNEL, DICTIONARY_CHANNEL);
+
+            loop {
+                match listener.try_recv().await {
+                    Ok(Some(n)) => {
+                        let event = if n.channel() == DICTIONARY_CHANNEL {
+                            let msg: serde_json::Value = serde_json::from_str(n.payload()).unwrap_or_default();
+                            AppEvent { kind: "dictionary_changed".to_string(), data: serde_json::json!({ "scope": msg["scope"] }) }
+                        } else {
+                            match serde_json::from_str(n.payload()) {
+                                Ok(e) => e,
+                                Err(e) => {
+                                    tracing::warn!("--- 无法解析的事件通知: {}", e);
+                                    continue;
+                                }
+                            }
+                        };
+                        // 没有订阅者时发送失败，忽略即可
+                        let _ = sender.send(event);
+                    }
+                    Ok(None) => {
+                        tracing::warn!("--- 事件通知连接中断，已通知订阅端重新同步");
+                        let _ = sender.send(AppEvent { kind: "resync".to_string(), data: serde_json::Value::Null });
+                    }
+                    Err(e) => {
+                        tracing::error!("!!! 事件监听异常: {}", e);
+                        break;
+                    }
+                }
+            }
+            tokio::time::sleep(Duration::from_secs(5)).await;
+        }
+    });
+}
@@ -1,42 +1,276 @@
+use serde::Serialize;
 use sqlx::PgPool;
+use std::collections::{HashMap, HashSet};
+use crate::models::naming_profile::NamingProfile;
 use crate::models::word_root::WordRoot;
+use crate::services::naming_service::{self, NamePart};
+use crate::services::text_normalizer::{self, Chunk};
+use crate::services::{dictionary_service, stop_word_service};
+
+/// 单个分词结果匹配到的词根及匹配途径
+pub struct RootMatch {
+    pub root: WordRoot,
+    pub path: &'static str, // name 中文名 / synonym 同义词 / misspelling 错别字 / abbr 英文缩写 / full_name 英文全称
+}
+
+/// 为单个分词结果查找词根：先精确匹配中文名，再查同义词表
+/// 同义词命中多个词根时：可用词根优先，其次按权重、类型 (exact > abbreviation > near > misspelling)，权重为 0 的不参与匹配
+/// 中文名只命中废弃词根 (如已被合并) 时，优先采用以该词为同义词的可用词根
+pub async fn match_root(pool: &PgPool, word: &str) -> Option<RootMatch> {
+    let by_name = sqlx::query_as!(
+        WordRoot,
+        r#"SELECT * FROM standard_word_roots WHERE cn_name = $1
+           ORDER BY (status = 'active') DESC, id
+           LIMIT 1"#,
+        word
+    )
+    .fetch_optional(pool)
+    .await
+    .unwrap_or(None);
+    let by_name = match by_name {
+        Some(root) if root.status == "active" => return Some(RootMatch { root, path: "name" }),
+        other => other.map(|root| RootMatch { root, path: "name" }),
+    };
+    match match_synonym(pool, word).await {
+        Some(m) if m.root.status == "active" || by_name.is_none() => Some(m),
+        _ => by_name,
+    }
+}
+
+async fn match_synonym(pool: &PgPool, word: &str) -> Option<RootMatch> {
+
+    let hit = sqlx::query!(
+        r#"SELECT s.root_id, s.syn_type FROM word_root_synonyms s
+           JOIN standard_word_roots r ON r.id = s.root_id
+           WHERE lower(s.term) = lower($1) AND s.weight > 0
+           ORDER BY (r.status = 'active') DESC, s.weight DESC,
+                    CASE s.syn_type WHEN 'exact' THEN 0 WHEN 'abbreviation' THEN 1 WHEN 'near' THEN 2 ELSE 3 END,
+                    s.id
+           LIMIT 1"#,
+        word
+    )
+    .fetch_optional(pool)
+    .await
+    .unwrap_or(None)?;
+
+    let root = sqlx::query_as!(WordRoot, "SELECT * FROM standard_word_roots WHERE id = $1", hit.root_id)
+        .fetch_optional(pool)
+        .await
+        .unwrap_or(None)?;
+    let path = if hit.syn_type == "misspelling" { "misspelling" } else { "synonym" };
+    Some(RootMatch { root, path })
+}
+
+/// 为输入中的 ASCII 单词查找词根：不区分大小写匹配英文缩写或英文全称
+pub async fn match_ascii_root(pool: &PgPool, word: &str) -> Option<RootMatch> {
+    let root = sqlx::query_as!(
+        WordRoot,
+        r#"SELECT * FROM standard_word_roots
+           WHERE lower(en_abbr) = lower($1) OR lower(en_full_name) = lower($1)
+           ORDER BY (lower(en_abbr) = lower($1)) DESC, (status = 'active') DESC, id
+           LIMIT 1"#,
+        word
+    )
+    .fetch_optional(pool)
+    .await
+    .unwrap_or(None)?;
+
+    let path = if root.en_abbr.eq_ignore_ascii_case(word) { "abbr" } else { "full_name" };
+    Some(RootMatch { root, path })
+}
+
+/// 字段英文名生成结果
+pub struct FieldNameSuggestion {
+    pub normalized_input: String, // 规范化 (全角转半角、繁转简) 后的输入
+    pub suggested_en: String,
+    pub missing_words: Vec<String>,
+    pub matched_ids: Vec<i32>,
+    pub shortened: bool,
+    pub data_type: Option<String>, // 由末尾类别词推导的推荐数据类型
+    pub warnings: Vec<String>,
+    pub ignored_tokens: Vec<String>, // 被丢弃的停用词与标点
+}
+
+pub async fn suggest_field_name(pool: &PgPool, cn_input: &str, profile: &NamingProfile) -> FieldNameSuggestion {
+    // 全角转半角、繁转简后，ASCII 单词与数字单独成段，其余交给 jieba
+    let normalized = text_normalizer::normalize(cn_input);
+    let jieba = crate::JIEBA.read().await.clone();
+    let stop_words = stop_word_service::snapshot().await;
 
-pub async fn suggest_field_name(pool: &PgPool, cn_input: &str) -> (String, Vec<String>, Vec<i32>) {
-    let jieba_read = crate::JIEBA.read().await;
-    let words = jieba_read.cut(cn_input, false);
-    
     let mut en_parts = Vec::new();
     let mut missing_words = Vec::new();
     let mut matched_ids = Vec::new();
+    let mut last_root: Option<WordRoot> = None;
+    let mut ignored_tokens = Vec::new();
+
+    for chunk in text_normalizer::split_scripts(&normalized) {
+        let words: Vec<String> = match chunk {
+            Chunk::Number(n) => {
+                en_parts.push(NamePart::Number(n));
+                continue;
+            }
+            Chunk::Word(w) => vec![w],
+            Chunk::Text(t) => jieba.cut(&t, false).into_iter().map(String::from).collect(),
+        };
+
+        for word in words {
+            let word = word.as_str();
+            if word.trim().is_empty() { continue; }
+            if stop_word_service::is_ignorable(word, &stop_words) {
+                ignored_tokens.push(word.to_string());
+                continue;
+            }
+
+            let root = if word.chars().all(|c| c.is_ascii_alphabetic()) {
+                match_ascii_root(pool, word).await
+            } else {
+                match_root(pool, word).await
+            };
+
+            match root.map(|m| m.root) {
+                Some(r) => {
+                    en_parts.push(NamePart::Root(r.en_abbr.clone()));
+                    matched_ids.push(r.id);
+                    last_root = Some(r);
+                },
+                None => {
+                    missing_words.push(word.to_string());
+                    en_parts.push(NamePart::Missing(word.to_string()));
+                    last_root = None;
+                }
+            }
+        }
+    }
+
+    // 末段必须是类别词，类别词决定推荐数据类型
+    let mut warnings = Vec::new();
+    let data_type = match &last_root {
+        Some(r) if r.is_class_word => r.recommended_data_type(),
+        Some(r) => {
+            warnings.push(format!("末尾词根 [{}]({}) 不是类别词，无法推荐数据类型", r.cn_name, r.en_abbr));
+            None
+        }
+        None => {
+            if !en_parts.is_empty() {
+                warnings.push("末尾词未匹配到标准词根，无法确认类别词".to_string());
+            }
+            None
+        }
+    };
+
+    let formatted = naming_service::apply_profile(&en_parts, profile);
+    FieldNameSuggestion {
+        normalized_input: normalized,
+        suggested_en: formatted.name,
+        missing_words,
+        matched_ids,
+        shortened: formatted.shortened,
+        data_type,
+        warnings,
+        ignored_tokens,
+    }
+}
 
-    for word in words {
-        if word.trim().is_empty() { continue; }
-        
-        // 同时匹配中文名和关联词 (ILIKE 是为了兼容同义词)
-        let root = sqlx::query_as!(
-            WordRoot,
-            r#"SELECT * FROM standard_word_roots 
-               WHERE cn_name = $1 
-               OR associated_terms ~* $2 
-               LIMIT 1"#,
-            word,
-            // 优化点：匹配开头、结尾或被空格包围的词，不区分大小写
-            format!(r"(^|[[:space:]]){}([[:space:]]|$)", word) 
-        )
+/// 根据词根链的末尾类别词推导数据类型
+pub async fn infer_data_type(pool: &PgPool, composition_ids: &[i32]) -> Option<String> {
+    let last_id = *composition_ids.last()?;
+    let root = sqlx::query_as!(WordRoot, "SELECT * FROM standard_word_roots WHERE id = $1", last_id)
         .fetch_optional(pool)
         .await
-        .unwrap_or(None);
-
-        match root {
-            Some(r) => {
-                en_parts.push(r.en_abbr);
-                matched_ids.push(r.id);
-            },
-            None => {
-                missing_words.push(word.to_string());
-                en_parts.push(format!("[{}]", word));
-            }
+        .unwrap_or(None)?;
+    root.recommended_data_type()
+}
+
+/// 分词调试：单个 token 的匹配情况
+#[derive(Serialize, Clone)]
+pub struct TokenDebug {
+    pub token: String,
+    pub ignored: bool, // 停用词/标点，生成英文名时会被丢弃
+    pub root_id: Option<i32>,
+    pub root_cn_name: Option<String>,
+    pub en_abbr: Option<String>,
+    pub match_path: Option<&'static str>, // name / synonym / abbr / full_name
+}
+
+/// 分词调试：自定义词条对切分结果的影响
+#[derive(Serialize)]
+pub struct CustomWordInfluence {
+    pub word: String,
+    pub in_cut: bool,          // 是否作为完整 token 出现在 cut 结果中
+    pub in_baseline_cut: bool, // 不加载自定义词条时是否同样会被切出
+}
+
+#[derive(Serialize)]
+pub struct SegmentationDebug {
+    pub input: String,
+    pub cut: Vec<TokenDebug>,        // 与 suggest_field_name 一致 (不启用 HMM)
+    pub cut_hmm: Vec<TokenDebug>,
+    pub cut_all: Vec<TokenDebug>,
+    pub cut_for_search: Vec<TokenDebug>,
+    pub baseline_cut: Vec<String>,   // 原始词库 (无自定义词条) 的切分结果
+    pub custom_words: Vec<CustomWordInfluence>,
+}
+
+/// 分词调试：对比各种切分模式，并给出每个 token 的词根匹配情况
+pub async fn debug_segmentation(pool: &PgPool, raw_input: &str) -> Result<SegmentationDebug, sqlx::Error> {
+    let normalized = text_normalizer::normalize(raw_input);
+    let input = normalized.as_str();
+    let jieba = crate::JIEBA.read().await.clone();
+    let stop_words = stop_word_service::snapshot().await;
+
+    let cut: Vec<String> = jieba.cut(input, false).into_iter().map(String::from).collect();
+    let cut_hmm: Vec<String> = jieba.cut(input, true).into_iter().map(String::from).collect();
+    let cut_all: Vec<String> = jieba.cut_all(input).into_iter().map(String::from).collect();
+    let cut_for_search: Vec<String> = jieba.cut_for_search(input, false).into_iter().map(String::from).collect();
+    let baseline_cut: Vec<String> = dictionary_service::baseline().cut(input, false).into_iter().map(String::from).collect();
+
+    // 同一 token 在多种模式下重复出现，只查一次库
+    let mut cache: HashMap<String, TokenDebug> = HashMap::new();
+    for token in cut.iter().chain(&cut_hmm).chain(&cut_all).chain(&cut_for_search) {
+        if cache.contains_key(token) || token.trim().is_empty() {
+            continue;
         }
+        let ignored = stop_word_service::is_ignorable(token, &stop_words);
+        let matched = if ignored {
+            None
+        } else if token.chars().all(|c| c.is_ascii_alphabetic()) {
+            match_ascii_root(pool, token).await
+        } else {
+            match_root(pool, token).await
+        };
+        cache.insert(token.clone(), TokenDebug {
+            token: token.clone(),
+            ignored,
+            root_id: matched.as_ref().map(|m| m.root.id),
+            root_cn_name: matched.as_ref().map(|m| m.root.cn_name.clone()),
+            en_abbr: matched.as_ref().map(|m| m.root.en_abbr.clone()),
+            match_path: matched.as_ref().map(|m| m.path),
+        });
     }
-    (en_parts.join("_"), missing_words, matched_ids)
-}
\ No newline at end of file
+    let annotate = |tokens: &[String]| -> Vec<TokenDebug> {
+        tokens.iter().filter_map(|t| cache.get(t).cloned()).collect()
+    };
+
+    let cut_set: HashSet<&String> = cut.iter().collect();
+    let baseline_set: HashSet<&String> = baseline_cut.iter().collect();
+    let custom_words = dictionary_service::load_custom_words(pool)
+        .await?
+        .into_iter()
+        .filter(|w| input.contains(w.as_str()))
+        .map(|w| CustomWordInfluence {
+            in_cut: cut_set.contains(&w),
+            in_baseline_cut: baseline_set.contains(&w),
+            word: w,
+        })
+        .collect();
+
+    Ok(SegmentationDebug {
+        input: input.to_string(),
+        cut: annotate(&cut),
+        cut_hmm: annotate(&cut_hmm),
+        cut_all: annotate(&cut_all),
+        cut_for_search: annotate(&cut_for_search),
+        baseline_cut,
+        custom_words,
+    })
+}
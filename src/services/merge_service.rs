@@ -0,0 +1,337 @@
+use serde::{Deserialize, Serialize};
+use sqlx::{PgConnection, PgPool};
+use std::collections::{HashMap, HashSet};
+
+#[derive(Deserialize)]
+pub struct MergeRootsPayload {
+    pub survivor_id: i32,
+    pub victim_ids: Vec<i32>,
+    pub victim_action: Option<String>, // deprecate (默认，保留为废弃词根) / delete
+}
+
+#[derive(Debug, Serialize)]
+pub struct RootBrief {
+    pub id: i32,
+    pub cn_name: String,
+    pub en_abbr: String,
+    pub status: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct FieldRewrite {
+    pub id: i32,
+    pub field_cn_name: String,
+    pub old_en_name: String,
+    pub new_en_name: String,
+    pub old_composition_ids: Vec<i32>,
+    pub new_composition_ids: Vec<i32>,
+}
+
+/// 合并方案：预览与执行返回相同结构
+#[derive(Debug, Serialize)]
+pub struct MergePlan {
+    pub survivor: RootBrief,
+    pub victims: Vec<RootBrief>,
+    pub victim_action: String,
+    pub moved_terms: Vec<String>, // 迁入保留词根的中文名与同义词
+    pub fields: Vec<FieldRewrite>,
+}
+
+#[derive(Debug)]
+pub enum MergeError {
+    Invalid(String),
+    Db(sqlx::Error),
+}
+
+impl From<sqlx::Error> for MergeError {
+    fn from(e: sqlx::Error) -> Self {
+        MergeError::Db(e)
+    }
+}
+
+/// 按驼峰、字母数字边界切分，保留原始大小写
+fn split_pieces(s: &str) -> Vec<&str> {
+    let chars: Vec<(usize, char)> = s.char_indices().collect();
+    let mut pieces = Vec::new();
+    let mut start = 0;
+    for i in 1..chars.len() {
+        let (pos, c) = chars[i];
+        let prev = chars[i - 1].1;
+        let next = chars.get(i + 1).map(|x| x.1);
+        let boundary = (prev.is_ascii_lowercase() && c.is_ascii_uppercase())
+            || (prev.is_ascii_uppercase() && c.is_ascii_uppercase() && next.is_some_and(|n| n.is_ascii_lowercase()))
+            || (prev.is_ascii_digit() != c.is_ascii_digit());
+        if boundary {
+            pieces.push(&s[start..pos]);
+            start = pos;
+        }
+    }
+    if start < s.len() {
+        pieces.push(&s[start..]);
+    }
+    pieces
+}
+
+/// 按原片段的大小写形式输出新缩写 (全大写 / 首字母大写 / 小写)
+fn adapt_case(template: &str, abbr: &str) -> String {
+    if template.chars().any(|c| c.is_ascii_uppercase()) && !template.chars().any(|c| c.is_ascii_lowercase()) {
+        abbr.to_uppercase()
+    } else if template.starts_with(|c: char| c.is_ascii_uppercase()) {
+        let lower = abbr.to_lowercase();
+        let mut chars = lower.chars();
+        chars.next().map(|c| c.to_ascii_uppercase().to_string() + chars.as_str()).unwrap_or_default()
+    } else {
+        abbr.to_lowercase()
+    }
+}
+
+/// 把英文名中的被合并缩写替换为保留词根的缩写，兼容下划线与驼峰命名
+fn rewrite_en_name(name: &str, replace: &HashMap<String, String>) -> String {
+    name.split('_')
+        .map(|part| {
+            split_pieces(part)
+                .into_iter()
+                .map(|p| match replace.get(&p.to_lowercase()) {
+                    Some(abbr) => adapt_case(p, abbr),
+                    None => p.to_string(),
+                })
+                .collect::<String>()
+        })
+        .collect::<Vec<_>>()
+        .join("_")
+}
+
+/// 生成合并方案并锁定相关行；在事务中调用，预览时回滚即可
+async fn plan(conn: &mut PgConnection, payload: &MergeRootsPayload) -> Result<MergePlan, MergeError> {
+    let victim_action = payload.victim_action.as_deref().unwrap_or("deprecate").to_string();
+    if victim_action != "deprecate" && victim_action != "delete" {
+        return Err(MergeError::Invalid(format!("不支持的处理方式 [{}]，可选: deprecate / delete", victim_action)));
+    }
+    let mut victim_ids: Vec<i32> = Vec::new();
+    for id in &payload.victim_ids {
+        if !victim_ids.contains(id) {
+            victim_ids.push(*id);
+        }
+    }
+    if victim_ids.is_empty() {
+        return Err(MergeError::Invalid("至少需要一个被合并词根".to_string()));
+    }
+    if victim_ids.contains(&payload.survivor_id) {
+        return Err(MergeError::Invalid("保留词根不能同时作为被合并词根".to_string()));
+    }
+
+    let mut all_ids = victim_ids.clone();
+    all_ids.push(payload.survivor_id);
+    let rows = sqlx::query!(
+        "SELECT id, cn_name, en_abbr, status FROM standard_word_roots WHERE id = ANY($1) ORDER BY id FOR UPDATE",
+        &all_ids
+    )
+    .fetch_all(&mut *conn)
+    .await?;
+    let mut by_id: HashMap<i32, RootBrief> = rows
+        .into_iter()
+        .map(|r| (r.id, RootBrief { id: r.id, cn_name: r.cn_name, en_abbr: r.en_abbr, status: r.status }))
+        .collect();
+
+    let survivor = by_id
+        .remove(&payload.survivor_id)
+        .ok_or_else(|| MergeError::Invalid(format!("保留词根 ID={} 不存在", payload.survivor_id)))?;
+    if survivor.status != "active" {
+        return Err(MergeError::Invalid(format!("保留词根 [{}] 已废弃，不能作为合并目标", survivor.cn_name)));
+    }
+    let mut victims = Vec::new();
+    for id in &victim_ids {
+        let v = by_id.remove(id).ok_or_else(|| MergeError::Invalid(format!("被合并词根 ID={} 不存在", id)))?;
+        victims.push(v);
+    }
+
+    // 迁入的词：被合并词根的中文名及其同义词，去掉保留词根已有的
+    let existing: HashSet<String> = sqlx::query_scalar!(
+        "SELECT term FROM word_root_synonyms WHERE root_id = $1",
+        survivor.id
+    )
+    .fetch_all(&mut *conn)
+    .await?
+    .into_iter()
+    .chain(std::iter::once(survivor.cn_name.clone()))
+    .collect();
+    let victim_terms = sqlx::query_scalar!(
+        "SELECT term FROM word_root_synonyms WHERE root_id = ANY($1) ORDER BY weight DESC, id",
+        &victim_ids
+    )
+    .fetch_all(&mut *conn)
+    .await?;
+    let mut moved_terms: Vec<String> = Vec::new();
+    for term in victims.iter().map(|v| v.cn_name.clone()).chain(victim_terms) {
+        if !existing.contains(&term) && !moved_terms.contains(&term) {
+            moved_terms.push(term);
+        }
+    }
+
+    // 引用被合并词根的标准字段：替换词根 ID 与英文名片段
+    let replace: HashMap<String, String> = victims
+        .iter()
+        .map(|v| (v.en_abbr.to_lowercase(), survivor.en_abbr.clone()))
+        .collect();
+    let field_rows = sqlx::query!(
+        r#"SELECT id, field_cn_name, field_en_name, composition_ids as "composition_ids!"
+           FROM standard_fields WHERE composition_ids && $1 ORDER BY id FOR UPDATE"#,
+        &victim_ids
+    )
+    .fetch_all(&mut *conn)
+    .await?;
+    let fields = field_rows
+        .into_iter()
+        .map(|f| FieldRewrite {
+            new_composition_ids: f
+                .composition_ids
+                .iter()
+                .map(|id| if victim_ids.contains(id) { survivor.id } else { *id })
+                .collect(),
+            new_en_name: rewrite_en_name(&f.field_en_name, &replace),
+            id: f.id,
+            field_cn_name: f.field_cn_name,
+            old_en_name: f.field_en_name,
+            old_composition_ids: f.composition_ids,
+        })
+        .collect();
+
+    Ok(MergePlan { survivor, victims, victim_action, moved_terms, fields })
+}
+
+/// 预览合并结果，不做任何修改
+pub async fn preview(pool: &PgPool, payload: &MergeRootsPayload) -> Result<MergePlan, MergeError> {
+    let mut tx = pool.begin().await?;
+    let plan = plan(&mut tx, payload).await?;
+    tx.rollback().await?;
+    Ok(plan)
+}
+
+/// 在单个事务中执行合并并记录审计日志
+/// 向量、分词词典与联想索引等派生数据由调用方在提交后刷新
+pub async fn execute(pool: &PgPool, payload: &MergeRootsPayload, actor_id: i32) -> Result<MergePlan, MergeError> {
+    let mut tx = pool.begin().await?;
+    let plan = plan(&mut tx, payload).await?;
+    let survivor_id = plan.survivor.id;
+    let victim_ids: Vec<i32> = plan.victims.iter().map(|v| v.id).collect();
+
+    // 1. 被合并词根的中文名作为同义词迁入，原有同义词连同类型与权重一并迁入
+    sqlx::query!(
+        r#"INSERT INTO word_root_synonyms (root_id, term, syn_type, source, remark)
+           SELECT $1, cn_name, 'exact', 'merge', '词根合并迁入'
+           FROM standard_word_roots
+           WHERE id = ANY($2) AND cn_name <> (SELECT cn_name FROM standard_word_roots WHERE id = $1)
+           ON CONFLICT (root_id, term) DO NOTHING"#,
+        survivor_id, &victim_ids
+    )
+    .execute(&mut *tx)
+    .await?;
+    sqlx::query!(
+        r#"INSERT INTO word_root_synonyms (root_id, term, syn_type, weight, source, remark)
+           SELECT $1, term, syn_type, weight, 'merge', remark
+           FROM word_root_synonyms
+           WHERE root_id = ANY($2) AND term <> (SELECT cn_name FROM standard_word_roots WHERE id = $1)
+           ON CONFLICT (root_id, term) DO NOTHING"#,
+        survivor_id, &victim_ids
+    )
+    .execute(&mut *tx)
+    .await?;
+    sqlx::query!("DELETE FROM word_root_synonyms WHERE root_id = ANY($1)", &victim_ids)
+        .execute(&mut *tx)
+        .await?;
+
+    // 2. 改写标准字段
+    for f in &plan.fields {
+        sqlx::query!(
+            "UPDATE standard_fields SET composition_ids = $1::INT[], field_en_name = $2 WHERE id = $3",
+            &f.new_composition_ids, f.new_en_name, f.id
+        )
+        .execute(&mut *tx)
+        .await?;
+    }
+
+    // 3. 已关联到被合并词根的申请改为指向保留词根
+    sqlx::query!(
+        "UPDATE notification_tasks SET resolved_root_id = $1 WHERE resolved_root_id = ANY($2)",
+        survivor_id, &victim_ids
+    )
+    .execute(&mut *tx)
+    .await?;
+
+    // 4. 处理被合并词根
+    if plan.victim_action == "delete" {
+        sqlx::query!("DELETE FROM standard_word_roots WHERE id = ANY($1)", &victim_ids)
+            .execute(&mut *tx)
+            .await?;
+    } else {
+        let note = format!("已合并至词根 [{}] (ID={})", plan.survivor.cn_name, survivor_id);
+        sqlx::query!(
+            r#"UPDATE standard_word_roots
+               SET status = 'deprecated', associated_terms = NULL, terms_pinyin = NULL,
+                   remark = concat_ws('；', NULLIF(remark, ''), $2::TEXT)
+               WHERE id = ANY($1)"#,
+            &victim_ids, note
+        )
+        .execute(&mut *tx)
+        .await?;
+    }
+
+    // 5. 审计日志
+    let detail = serde_json::to_value(&plan).unwrap_or_default();
+    sqlx::query!(
+        "INSERT INTO audit_logs (actor_id, action, target_type, target_id, detail) VALUES ($1, 'ROOT_MERGE', 'word_root', $2, $3)",
+        actor_id, survivor_id, detail
+    )
+    .execute(&mut *tx)
+    .await?;
+
+    tx.commit().await?;
+    Ok(plan)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn replace(pairs: &[(&str, &str)]) -> HashMap<String, String> {
+        pairs.iter().map(|(k, v)| (k.to_string(), v.to_string())).collect()
+    }
+
+    #[test]
+    fn splits_keeping_case() {
+        assert_eq!(split_pieces("custOpenAcct"), vec!["cust", "Open", "Acct"]);
+        assert_eq!(split_pieces("HTTPServer2"), vec!["HTTP", "Server", "2"]);
+        assert!(split_pieces("").is_empty());
+        assert_eq!(split_pieces("2024"), vec!["2024"]);
+    }
+
+    #[test]
+    fn adapts_case_to_template() {
+        assert_eq!(adapt_case("ACCT", "acc"), "ACC");
+        assert_eq!(adapt_case("Acct", "ACC"), "Acc");
+        assert_eq!(adapt_case("acct", "Acc"), "acc");
+    }
+
+    #[test]
+    fn rewrites_snake_and_camel_names_preserving_case() {
+        let r = replace(&[("acct", "acc")]);
+        assert_eq!(rewrite_en_name("cust_acct_no", &r), "cust_acc_no");
+        assert_eq!(rewrite_en_name("CUST_ACCT_NO", &r), "CUST_ACC_NO");
+        assert_eq!(rewrite_en_name("custAcctNo", &r), "custAccNo");
+        assert_eq!(rewrite_en_name("acct2", &r), "acc2");
+    }
+
+    #[test]
+    fn only_whole_pieces_are_replaced() {
+        let r = replace(&[("acct", "acc")]);
+        assert_eq!(rewrite_en_name("acctount_id", &r), "acctount_id");
+    }
+
+    #[test]
+    fn rewrite_edge_cases() {
+        let r = replace(&[("acct", "acc")]);
+        assert_eq!(rewrite_en_name("", &r), "");
+        assert_eq!(rewrite_en_name("123", &r), "123");
+        assert_eq!(rewrite_en_name("_acct_", &r), "_acc_");
+    }
+}
@@ -1 +1,22 @@
 pub mod mapping_service;
+pub mod naming_service;
+pub mod reverse_service;
+pub mod compliance_service;
+pub mod stop_word_service;
+pub mod dictionary_service;
+pub mod text_normalizer;
+pub mod pinyin_service;
+pub mod typeahead_service;
+pub mod synonym_service;
+pub mod duplicate_service;
+pub mod reserved_words;
+pub mod abbreviation_service;
+pub mod abbr_lint;
+pub mod merge_service;
+pub mod notification_service;
+pub mod task_request_service;
+pub mod event_bus;
+pub mod webhook_service;
+pub mod task_report_service;
+pub mod token_service;
+pub mod session_service;
@@ -0,0 +1,397 @@
+use sqlx::PgPool;
+use crate::models::naming_profile::NamingProfile;
+
+/// 支持的命名风格
+pub const CASE_STYLES: [&str; 4] = ["snake", "upper_snake", "camel", "pascal"];
+
+/// 数字片段的处理规则
+pub const NUMBER_STYLES: [&str; 3] = ["separate", "attach", "drop"];
+
+/// 英文名的组成片段
+#[derive(Debug, Clone)]
+pub enum NamePart {
+    /// 已匹配到词根的英文缩写，超长时可被缩短
+    Root(String),
+    /// 未匹配的中文词，以 [词] 形式原样保留
+    Missing(String),
+    /// 输入中的数字，按规范的 number_style 处理，不参与缩短
+    Number(String),
+}
+
+/// 按命名规范格式化后的结果
+#[derive(Debug)]
+pub struct FormattedName {
+    pub name: String,
+    pub shortened: bool, // 是否因超长而被缩短
+}
+
+/// 未配置任何规范时的兜底：小写下划线、不限长度
+pub fn builtin_default() -> NamingProfile {
+    NamingProfile {
+        id: 0,
+        name: "default".to_string(),
+        case_style: "snake".to_string(),
+        prefix: None,
+        suffix: None,
+        max_length: None,
+        number_style: "separate".to_string(),
+        is_default: true,
+        remark: None,
+        created_at: None,
+    }
+}
+
+/// 根据名称加载命名规范；未指定名称时使用默认规范
+pub async fn resolve_profile(pool: &PgPool, name: Option<&str>) -> Result<NamingProfile, String> {
+    let name = name.map(str::trim).filter(|n| !n.is_empty());
+
+    let res = match name {
+        Some(n) => sqlx::query_as!(
+            NamingProfile,
+            "SELECT id, name, case_style, prefix, suffix, max_length, number_style, is_default, remark, created_at
+             FROM naming_profiles WHERE name = $1",
+            n
+        )
+        .fetch_optional(pool)
+        .await,
+        None => sqlx::query_as!(
+            NamingProfile,
+            "SELECT id, name, case_style, prefix, suffix, max_length, number_style, is_default, remark, created_at
+             FROM naming_profiles WHERE is_default = true ORDER BY id LIMIT 1"
+        )
+        .fetch_optional(pool)
+        .await,
+    };
+
+    match (res, name) {
+        (Ok(Some(p)), _) => Ok(p),
+        (Ok(None), Some(n)) => Err(format!("命名规范 [{}] 不存在", n)),
+        (Ok(None), None) => Ok(builtin_default()),
+        (Err(e), _) => Err(format!("加载命名规范失败: {}", e)),
+    }
+}
+
+fn part_text(part: &NamePart) -> String {
+    match part {
+        NamePart::Root(s) => s.clone(),
+        NamePart::Missing(w) => format!("[{}]", w),
+        NamePart::Number(n) => n.clone(),
+    }
+}
+
+fn capitalize(s: &str) -> String {
+    let lower = s.to_lowercase();
+    let mut chars = lower.chars();
+    match chars.next() {
+        Some(c) => c.to_uppercase().collect::<String>() + chars.as_str(),
+        None => String::new(),
+    }
+}
+
+/// 按大小写风格拼接片段
+fn join_segments(segments: &[String], case_style: &str) -> String {
+    match case_style {
+        "upper_snake" => segments.iter().map(|s| s.to_uppercase()).collect::<Vec<_>>().join("_"),
+        "camel" => segments
+            .iter()
+            .enumerate()
+            .map(|(i, s)| if i == 0 { s.to_lowercase() } else { capitalize(s) })
+            .collect(),
+        "pascal" => segments.iter().map(|s| capitalize(s)).collect(),
+        _ => segments.iter().map(|s| s.to_lowercase()).collect::<Vec<_>>().join("_"),
+    }
+}
+
+/// 去掉缩写中首字母之后的元音，无法再去时返回 None
+fn drop_vowels(abbr: &str) -> Option<String> {
+    let mut chars = abbr.chars();
+    let first = chars.next()?;
+    let rest: String = chars.filter(|c| !"aeiouAEIOU".contains(*c)).collect();
+    let dropped = format!("{}{}", first, rest);
+    if dropped.len() < abbr.len() { Some(dropped) } else { None }
+}
+
+/// 按命名规范生成最终英文名
+/// 超长时的缩短规则 (结果确定、可复现)：
+/// 1. 每轮选取当前最长的词根缩写 (等长取最左)，先去除首字母后的元音，再逐位截掉末尾字母，最短保留 2 位；
+/// 2. 前缀、后缀与未匹配的 [词] 不参与缩短，并入词根的数字原样保留，只缩短其前面的缩写；
+/// 3. 所有缩写都无法再缩短时，直接按最大长度截断。
+pub fn apply_profile(parts: &[NamePart], profile: &NamingProfile) -> FormattedName {
+    let mut segments: Vec<String> = Vec::new();
+    // 各片段并入的数字，与 segments 一一对应，缩短时不受影响
+    let mut numbers: Vec<String> = Vec::new();
+    let mut shortenable: Vec<usize> = Vec::new();
+
+    if let Some(p) = profile.prefix.as_deref().filter(|p| !p.is_empty()) {
+        segments.push(p.to_string());
+        numbers.push(String::new());
+    }
+    let prefix_len = segments.len();
+    for part in parts {
+        match part {
+            NamePart::Number(n) => match profile.number_style.as_str() {
+                "drop" => continue,
+                // 并入前一个片段 (前缀除外)，没有前一个片段时独立成段
+                "attach" if segments.len() > prefix_len => {
+                    let last = numbers.len() - 1;
+                    numbers[last].push_str(n);
+                    continue;
+                }
+                _ => {}
+            },
+            NamePart::Root(_) => shortenable.push(segments.len()),
+            NamePart::Missing(_) => {}
+        }
+        segments.push(part_text(part));
+        numbers.push(String::new());
+    }
+    if let Some(s) = profile.suffix.as_deref().filter(|s| !s.is_empty()) {
+        segments.push(s.to_string());
+        numbers.push(String::new());
+    }
+
+    let join = |segments: &[String]| {
+        let full: Vec<String> = segments.iter().zip(&numbers).map(|(s, n)| format!("{}{}", s, n)).collect();
+        join_segments(&full, &profile.case_style)
+    };
+    let mut name = join(&segments);
+    let max_len = match profile.max_length {
+        Some(m) if m > 0 => m as usize,
+        _ => return FormattedName { name, shortened: false },
+    };
+
+    let mut shortened = false;
+    while name.chars().count() > max_len {
+        // 选取最长的可缩短片段
+        let target = shortenable
+            .iter()
+            .copied()
+            .filter(|&i| segments[i].chars().count() > 2)
+            .max_by(|&a, &b| {
+                segments[a].chars().count().cmp(&segments[b].chars().count()).then(b.cmp(&a))
+            });
+
+        let Some(idx) = target else {
+            name = name.chars().take(max_len).collect::<String>().trim_end_matches('_').to_string();
+            return FormattedName { name, shortened: true };
+        };
+
+        segments[idx] = match drop_vowels(&segments[idx]) {
+            Some(s) if s.chars().count() >= 2 => s,
+            _ => {
+                let mut s = segments[idx].clone();
+                s.pop();
+                s
+            }
+        };
+        shortened = true;
+        name = join(&segments);
+    }
+
+    FormattedName { name, shortened }
+}
+
+/// 校验一个英文名是否符合命名规范，返回全部问题描述
+pub fn validate_name(name: &str, profile: &NamingProfile) -> Vec<String> {
+    let mut issues = Vec::new();
+
+    if name.is_empty() {
+        issues.push("英文名不能为空".to_string());
+        return issues;
+    }
+
+    if let Some(max) = profile.max_length.filter(|m| *m > 0) {
+        let len = name.chars().count();
+        if len > max as usize {
+            issues.push(format!("长度 {} 超过规范 [{}] 的上限 {}", len, profile.name, max));
+        }
+    }
+
+    let ascii_ok = name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_');
+    if !ascii_ok {
+        issues.push("只允许包含英文字母、数字和下划线".to_string());
+    }
+    if name.starts_with(|c: char| c.is_ascii_digit()) {
+        issues.push("不能以数字开头".to_string());
+    }
+
+    let style_ok = match profile.case_style.as_str() {
+        "upper_snake" => !name.chars().any(|c| c.is_ascii_lowercase()),
+        "camel" => !name.contains('_') && name.starts_with(|c: char| c.is_ascii_lowercase()),
+        "pascal" => !name.contains('_') && name.starts_with(|c: char| c.is_ascii_uppercase()),
+        _ => !name.chars().any(|c| c.is_ascii_uppercase()),
+    };
+    if !style_ok {
+        issues.push(format!("不符合命名风格 [{}]", profile.case_style));
+    }
+    if matches!(profile.case_style.as_str(), "snake" | "upper_snake")
+        && (name.starts_with('_') || name.ends_with('_') || name.contains("__"))
+    {
+        issues.push("下划线不能出现在首尾或连续出现".to_string());
+    }
+
+    let lower = name.to_lowercase();
+    if let Some(p) = profile.prefix.as_deref().filter(|p| !p.is_empty()) {
+        if !lower.starts_with(&p.to_lowercase()) {
+            issues.push(format!("缺少规范要求的前缀 [{}]", p));
+        }
+    }
+    if let Some(s) = profile.suffix.as_deref().filter(|s| !s.is_empty()) {
+        if !lower.ends_with(&s.to_lowercase()) {
+            issues.push(format!("缺少规范要求的后缀 [{}]", s));
+        }
+    }
+
+    issues
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn profile(case_style: &str, max_length: Option<i32>) -> NamingProfile {
+        NamingProfile { case_style: case_style.to_string(), max_length, ..builtin_default() }
+    }
+
+    fn roots(abbrs: &[&str]) -> Vec<NamePart> {
+        abbrs.iter().map(|a| NamePart::Root(a.to_string())).collect()
+    }
+
+    #[test]
+    fn joins_by_case_style() {
+        let parts = roots(&["open", "date"]);
+        assert_eq!(apply_profile(&parts, &profile("snake", None)).name, "open_date");
+        assert_eq!(apply_profile(&parts, &profile("upper_snake", None)).name, "OPEN_DATE");
+        assert_eq!(apply_profile(&parts, &profile("camel", None)).name, "openDate");
+        assert_eq!(apply_profile(&parts, &profile("pascal", None)).name, "OpenDate");
+    }
+
+    #[test]
+    fn keeps_missing_words_and_affixes() {
+        let parts = vec![NamePart::Root("cust".into()), NamePart::Missing("某".into())];
+        let p = NamingProfile { prefix: Some("ods".into()), suffix: Some("cd".into()), ..builtin_default() };
+        assert_eq!(apply_profile(&parts, &p).name, "ods_cust_[某]_cd");
+    }
+
+    #[test]
+    fn number_styles() {
+        let parts = vec![NamePart::Root("addr".into()), NamePart::Number("2".into())];
+        let mut p = builtin_default();
+        assert_eq!(apply_profile(&parts, &p).name, "addr_2");
+        p.number_style = "attach".into();
+        assert_eq!(apply_profile(&parts, &p).name, "addr2");
+        p.number_style = "drop".into();
+        assert_eq!(apply_profile(&parts, &p).name, "addr");
+    }
+
+    #[test]
+    fn attach_without_previous_segment_stays_separate() {
+        let p = NamingProfile { prefix: Some("t".into()), number_style: "attach".into(), ..builtin_default() };
+        assert_eq!(apply_profile(&[NamePart::Number("1".into())], &p).name, "t_1");
+    }
+
+    #[test]
+    fn all_digit_input() {
+        let parts = vec![NamePart::Number("123".into())];
+        assert_eq!(apply_profile(&parts, &builtin_default()).name, "123");
+        let p = NamingProfile { number_style: "drop".into(), ..builtin_default() };
+        assert_eq!(apply_profile(&parts, &p).name, "");
+    }
+
+    #[test]
+    fn empty_input() {
+        let f = apply_profile(&[], &profile("snake", Some(5)));
+        assert_eq!(f.name, "");
+        assert!(!f.shortened);
+    }
+
+    #[test]
+    fn within_max_length_is_untouched() {
+        let f = apply_profile(&roots(&["cust", "name"]), &profile("snake", Some(9)));
+        assert_eq!(f.name, "cust_name");
+        assert!(!f.shortened);
+    }
+
+    #[test]
+    fn shortens_longest_root_first_dropping_vowels_then_letters() {
+        // customer -> cstmr (去元音) -> cstm (截末位)
+        let f = apply_profile(&roots(&["customer", "name"]), &profile("snake", Some(9)));
+        assert_eq!(f.name, "cstm_name");
+        assert!(f.shortened);
+    }
+
+    #[test]
+    fn equal_length_roots_shorten_leftmost() {
+        let f = apply_profile(&roots(&["abcd", "efgh"]), &profile("snake", Some(8)));
+        assert_eq!(f.name, "abc_efgh");
+    }
+
+    #[test]
+    fn attached_number_is_not_shortened() {
+        // amount2 -> amnt2：只缩短字母部分，数字保留
+        let parts = vec![NamePart::Root("amount".into()), NamePart::Number("2".into())];
+        let p = NamingProfile { number_style: "attach".into(), max_length: Some(5), ..builtin_default() };
+        assert_eq!(apply_profile(&parts, &p).name, "amnt2");
+
+        let parts = vec![NamePart::Root("addr".into()), NamePart::Number("12".into()), NamePart::Root("code".into())];
+        let p = NamingProfile { number_style: "attach".into(), max_length: Some(10), ..builtin_default() };
+        assert_eq!(apply_profile(&parts, &p).name, "add12_code");
+    }
+
+    #[test]
+    fn truncates_when_nothing_left_to_shorten() {
+        let p = NamingProfile { prefix: Some("ods".into()), max_length: Some(4), ..builtin_default() };
+        let f = apply_profile(&roots(&["xy"]), &p);
+        assert_eq!(f.name, "ods");
+        assert!(f.shortened);
+    }
+
+    #[test]
+    fn max_length_smaller_than_prefix() {
+        let p = NamingProfile { prefix: Some("ods".into()), max_length: Some(2), ..builtin_default() };
+        let f = apply_profile(&roots(&["amt"]), &p);
+        assert_eq!(f.name, "od");
+        assert!(f.shortened);
+    }
+
+    #[test]
+    fn validate_accepts_conforming_name() {
+        assert!(validate_name("cust_name", &builtin_default()).is_empty());
+        assert!(validate_name("custName", &profile("camel", None)).is_empty());
+    }
+
+    #[test]
+    fn validate_empty_name() {
+        assert_eq!(validate_name("", &builtin_default()), vec!["英文名不能为空".to_string()]);
+    }
+
+    #[test]
+    fn validate_all_digit_name() {
+        assert_eq!(validate_name("123", &builtin_default()), vec!["不能以数字开头".to_string()]);
+    }
+
+    #[test]
+    fn validate_reports_style_and_underscores() {
+        assert_eq!(validate_name("CustName", &builtin_default()), vec!["不符合命名风格 [snake]".to_string()]);
+        assert_eq!(
+            validate_name("CUST__NAME", &profile("upper_snake", None)),
+            vec!["下划线不能出现在首尾或连续出现".to_string()]
+        );
+        assert_eq!(
+            validate_name("cust-name", &builtin_default()),
+            vec!["只允许包含英文字母、数字和下划线".to_string()]
+        );
+    }
+
+    #[test]
+    fn validate_length_and_affixes() {
+        let p = NamingProfile { prefix: Some("ods".into()), suffix: Some("cd".into()), max_length: Some(2), ..builtin_default() };
+        assert_eq!(
+            validate_name("ods_cd", &p),
+            vec!["长度 6 超过规范 [default] 的上限 2".to_string()]
+        );
+        let issues = validate_name("cust_name", &p);
+        assert!(issues.contains(&"缺少规范要求的前缀 [ods]".to_string()));
+        assert!(issues.contains(&"缺少规范要求的后缀 [cd]".to_string()));
+    }
+}
@@ -0,0 +1,46 @@
+use serde::Serialize;
+
+#[derive(Serialize, sqlx::FromRow)]
+pub struct UserNotification {
+    pub id: i32,
+    pub kind: String,
+    pub title: String,
+    pub content: Option<String>,
+    pub task_id: Option<i32>,
+    pub is_read: bool,
+    pub created_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+/// 通知申请的关注人：申请人及所有附议用户 (可在事务中调用)
+pub async fn notify_task_followers<'e, E: sqlx::PgExecutor<'e>>(
+    db: E,
+    task_id: i32,
+    kind: &str,
+    title: &str,
+    content: Option<&str>,
+) -> Result<(), sqlx::Error> {
+    sqlx::query!(
+        r#"INSERT INTO user_notifications (user_id, kind, title, content, task_id)
+           SELECT u, $2, $3, $4, $1 FROM (
+               SELECT submitter_id AS u FROM notification_tasks WHERE id = $1
+               UNION
+               SELECT user_id FROM task_votes WHERE task_id = $1
+           ) x
+           WHERE u IS NOT NULL"#,
+        task_id, kind, title, content
+    )
+    .execute(db)
+    .await?;
+    Ok(())
+}
+
+/// 申请结束时通知申请人的文案
+pub fn task_resolved_message(status: &str, subject: &str, note: Option<&str>) -> (String, String) {
+    let title = match status {
+        "approved" => format!("您的申请 [{}] 已完成", subject),
+        "rejected" => format!("您的申请 [{}] 已被驳回", subject),
+        _ => format!("您的申请 [{}] 已合并至其他申请", subject),
+    };
+    let content = note.map(str::trim).filter(|n| !n.is_empty()).unwrap_or("无处理说明").to_string();
+    (title, content)
+}
@@ -0,0 +1,185 @@
+use pinyin::ToPinyin;
+use sqlx::PgPool;
+
+// 常见业务词中多音字的读音。pinyin 库按单字默认读音转换，如 "银行" 会得到 yinxing
+const PHRASES: &[(&str, &[&str])] = &[
+    ("银行", &["yin", "hang"]),
+    ("分行", &["fen", "hang"]),
+    ("支行", &["zhi", "hang"]),
+    ("总行", &["zong", "hang"]),
+    ("行号", &["hang", "hao"]),
+    ("行名", &["hang", "ming"]),
+    ("行业", &["hang", "ye"]),
+    ("长度", &["chang", "du"]),
+    ("长期", &["chang", "qi"]),
+    ("时长", &["shi", "chang"]),
+    ("会计", &["kuai", "ji"]),
+    ("还款", &["huan", "kuan"]),
+    ("归还", &["gui", "huan"]),
+    ("调整", &["tiao", "zheng"]),
+    ("调账", &["tiao", "zhang"]),
+    ("校验", &["jiao", "yan"]),
+    ("重复", &["chong", "fu"]),
+    ("重新", &["chong", "xin"]),
+    ("重置", &["chong", "zhi"]),
+];
+
+/// 逐字读音 (无声调，ü 写作 v)，非汉字为 None；命中多音字词表时按词表读音
+fn syllables(text: &str) -> Vec<(char, Option<String>)> {
+    let mut out: Vec<(char, Option<String>)> = text
+        .chars()
+        .zip(text.to_pinyin())
+        .map(|(c, p)| (c, p.map(|p| p.plain().replace('ü', "v"))))
+        .collect();
+    let offsets: Vec<usize> = text.char_indices().map(|(i, _)| i).collect();
+    let mut i = 0;
+    while i < offsets.len() {
+        match PHRASES.iter().find(|(word, _)| text[offsets[i]..].starts_with(word)) {
+            Some((_, readings)) => {
+                for (j, r) in readings.iter().enumerate() {
+                    out[i + j].1 = Some(r.to_string());
+                }
+                i += readings.len();
+            }
+            None => i += 1,
+        }
+    }
+    out
+}
+
+/// 全拼 (无声调、小写，ü 写作 v)，非汉字保留其中的字母数字，如 "金额" -> "jine"
+pub fn full(text: &str) -> String {
+    syllables(text)
+        .into_iter()
+        .map(|(c, p)| match p {
+            Some(p) => p,
+            None if c.is_ascii_alphanumeric() => c.to_ascii_lowercase().to_string(),
+            None => String::new(),
+        })
+        .collect()
+}
+
+/// 拼音首字母，如 "交易代码" -> "jydm"
+pub fn initials(text: &str) -> String {
+    syllables(text)
+        .into_iter()
+        .filter_map(|(c, p)| match p {
+            Some(p) => p.chars().next(),
+            None if c.is_ascii_alphanumeric() => Some(c.to_ascii_lowercase()),
+            None => None,
+        })
+        .collect()
+}
+
+/// 同义词的拼音索引：每个词的全拼与首字母，空格分隔
+pub fn terms_index(terms: Option<&str>) -> Option<String> {
+    let terms = terms?;
+    let parts: Vec<String> = terms
+        .split_whitespace()
+        .flat_map(|t| [full(t), initials(t)])
+        .filter(|p| !p.is_empty())
+        .collect();
+    if parts.is_empty() { None } else { Some(parts.join(" ")) }
+}
+
+/// 启动时为历史数据补齐拼音索引，并修正与当前转换规则不一致的旧索引 (如多音字读音调整后)
+pub async fn backfill(pool: &PgPool) {
+    let roots = sqlx::query!(
+        "SELECT id, cn_name, associated_terms, pinyin, pinyin_initials, terms_pinyin FROM standard_word_roots"
+    )
+    .fetch_all(pool)
+    .await
+    .unwrap_or_default();
+
+    let mut root_count = 0;
+    for r in &roots {
+        let (py, letters, terms) = (full(&r.cn_name), initials(&r.cn_name), terms_index(r.associated_terms.as_deref()));
+        if r.pinyin.as_ref() == Some(&py) && r.pinyin_initials.as_ref() == Some(&letters) && r.terms_pinyin == terms {
+            continue;
+        }
+        root_count += 1;
+        let _ = sqlx::query!(
+            "UPDATE standard_word_roots SET pinyin = $1, pinyin_initials = $2, terms_pinyin = $3 WHERE id = $4",
+            py, letters, terms, r.id
+        )
+        .execute(pool)
+        .await;
+    }
+
+    let fields = sqlx::query!(
+        "SELECT id, field_cn_name, associated_terms, pinyin, pinyin_initials, terms_pinyin FROM standard_fields"
+    )
+    .fetch_all(pool)
+    .await
+    .unwrap_or_default();
+
+    let mut field_count = 0;
+    for f in &fields {
+        let (py, letters, terms) = (full(&f.field_cn_name), initials(&f.field_cn_name), terms_index(f.associated_terms.as_deref()));
+        if f.pinyin.as_ref() == Some(&py) && f.pinyin_initials.as_ref() == Some(&letters) && f.terms_pinyin == terms {
+            continue;
+        }
+        field_count += 1;
+        let _ = sqlx::query!(
+            "UPDATE standard_fields SET pinyin = $1, pinyin_initials = $2, terms_pinyin = $3 WHERE id = $4",
+            py, letters, terms, f.id
+        )
+        .execute(pool)
+        .await;
+    }
+
+    if root_count > 0 || field_count > 0 {
+        tracing::info!("拼音索引补齐完成: 词根 {} 条, 字段 {} 条", root_count, field_count);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn phrase_table_matches_word_length() {
+        for (word, readings) in PHRASES {
+            assert_eq!(word.chars().count(), readings.len(), "多音字词表读音个数不一致: {}", word);
+        }
+    }
+
+    #[test]
+    fn full_and_initials() {
+        assert_eq!(full("金额"), "jine");
+        assert_eq!(initials("交易代码"), "jydm");
+        assert_eq!(full(""), "");
+        assert_eq!(initials(""), "");
+    }
+
+    #[test]
+    fn polyphones_follow_phrase_readings() {
+        assert_eq!(full("银行账号"), "yinhangzhanghao");
+        assert_eq!(initials("银行账号"), "yhzh");
+        assert_eq!(initials("会计科目"), "kjkm");
+        assert_eq!(initials("还款日期"), "hkrq");
+        assert_eq!(full("长度"), "changdu");
+        assert_eq!(initials("调整金额"), "tzje");
+        // 词表外按默认读音
+        assert_eq!(initials("行为"), "xw");
+    }
+
+    #[test]
+    fn umlaut_is_written_as_v() {
+        assert_eq!(full("利率"), "lilv");
+        assert_eq!(initials("女性"), "nx");
+    }
+
+    #[test]
+    fn keeps_ascii_alphanumerics() {
+        assert_eq!(full("GMV占比2"), "gmvzhanbi2");
+        assert_eq!(initials("T+1日期"), "t1rq");
+    }
+
+    #[test]
+    fn terms_index_joins_full_and_initials() {
+        assert_eq!(terms_index(Some("金额 余额")).as_deref(), Some("jine je yue ye"));
+        assert_eq!(terms_index(Some("  ")), None);
+        assert_eq!(terms_index(None), None);
+    }
+}
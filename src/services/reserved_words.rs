@@ -0,0 +1,70 @@
+/// 支持的 SQL 方言
+pub const DIALECTS: [&str; 5] = ["common", "mysql", "postgres", "oracle", "hive"];
+
+/// 各主流数据库通用的 SQL 保留字 (小写)，词根缩写与字段英文名均应避开
+pub const COMMON: &[&str] = &[
+    "add", "all", "alter", "and", "any", "as", "asc", "between", "by", "case", "cast", "check", "column",
+    "constraint", "create", "cross", "current", "current_date", "current_time", "current_timestamp",
+    "current_user", "date", "default", "delete", "desc", "distinct", "drop", "else", "end", "except",
+    "exists", "false", "fetch", "for", "foreign", "from", "full", "grant", "group", "having", "in", "index",
+    "inner", "insert", "intersect", "into", "is", "join", "key", "left", "like", "limit", "not", "null",
+    "of", "on", "or", "order", "outer", "primary", "references", "revoke", "right", "row", "rows",
+    "select", "set", "some", "table", "then", "to", "true", "union", "unique", "update", "user", "using",
+    "values", "view", "when", "where", "with",
+];
+
+/// MySQL 额外保留字
+pub const MYSQL: &[&str] = &[
+    "change", "database", "databases", "div", "dual", "explain", "force", "ignore", "interval", "keys",
+    "kill", "load", "lock", "long", "match", "mod", "option", "range", "read", "regexp", "rename",
+    "replace", "require", "rlike", "schema", "show", "signal", "sql", "ssl", "status", "straight_join",
+    "trigger", "unlock", "unsigned", "usage", "use", "xor", "year_month", "zerofill",
+];
+
+/// PostgreSQL 额外保留字
+pub const POSTGRES: &[&str] = &[
+    "analyse", "analyze", "array", "asymmetric", "both", "collate", "concurrently", "do", "freeze",
+    "ilike", "initially", "isnull", "lateral", "leading", "localtime", "localtimestamp", "natural",
+    "notnull", "offset", "only", "overlaps", "placing", "returning", "session_user", "similar",
+    "symmetric", "tablesample", "trailing", "variadic", "verbose", "window",
+];
+
+/// Oracle 额外保留字
+pub const ORACLE: &[&str] = &[
+    "access", "audit", "cluster", "comment", "compress", "connect", "exclusive", "file", "identified",
+    "immediate", "increment", "initial", "level", "lock", "long", "maxextents", "minus", "mode", "modify",
+    "noaudit", "nocompress", "nowait", "number", "offline", "online", "pctfree", "prior", "privileges",
+    "raw", "rename", "resource", "rowid", "rownum", "session", "share", "size", "start", "successful",
+    "synonym", "sysdate", "uid", "validate", "varchar", "varchar2", "whenever",
+];
+
+/// Hive 额外保留字
+pub const HIVE: &[&str] = &[
+    "array", "bigint", "binary", "boolean", "cube", "cursor", "decimal", "double", "exchange", "extended",
+    "external", "float", "function", "grouping", "import", "int", "interval", "less", "local", "macro",
+    "map", "more", "none", "out", "over", "partition", "percent", "preserve", "range", "reads", "reduce",
+    "rollup", "smallint", "timestamp", "transform", "trigger", "truncate", "unbounded",
+];
+
+fn words_of(dialect: &str) -> &'static [&'static str] {
+    match dialect {
+        "mysql" => MYSQL,
+        "postgres" => POSTGRES,
+        "oracle" => ORACLE,
+        "hive" => HIVE,
+        _ => COMMON,
+    }
+}
+
+/// 在指定方言中查找保留字，命中时返回所属方言 (通用保留字始终检查)
+pub fn reserved_in(word: &str, dialects: &[String]) -> Option<&'static str> {
+    let lower = word.to_lowercase();
+    if COMMON.contains(&lower.as_str()) {
+        return Some("common");
+    }
+    DIALECTS
+        .iter()
+        .copied()
+        .filter(|d| dialects.iter().any(|x| x == d))
+        .find(|d| words_of(d).contains(&lower.as_str()))
+}
@@ -0,0 +1,248 @@
+use serde::Serialize;
+use sqlx::PgPool;
+use std::collections::HashMap;
+
+/// 反向翻译中识别出的单个片段
+#[derive(Debug, Clone, Serialize)]
+pub struct ReverseSegment {
+    pub piece: String,
+    pub kind: &'static str,              // root / number / unknown
+    pub root_id: Option<i32>,
+    pub cn_name: Option<String>,
+    pub en_abbr: Option<String>,
+    pub matched_by: Option<&'static str>, // en_abbr / en_full_name
+}
+
+#[derive(Debug, Serialize)]
+pub struct ReverseResult {
+    pub input: String,
+    pub cn_name: String,
+    pub segments: Vec<ReverseSegment>,
+    pub matched_roots: Vec<ReverseSegment>,
+    pub unknown_pieces: Vec<String>,
+}
+
+#[derive(Clone)]
+struct RootKey {
+    id: i32,
+    cn_name: String,
+    en_abbr: String,
+    matched_by: &'static str,
+}
+
+/// 按分隔符、驼峰、字母数字边界切分英文标识符，结果统一小写
+/// 如 "custOpenAcct_dt" -> [cust, open, acct, dt]，"HTTPServer2" -> [http, server, 2]
+pub fn split_identifier(input: &str) -> Vec<String> {
+    let mut pieces = Vec::new();
+
+    for chunk in input.split(|c: char| !c.is_ascii_alphanumeric()).filter(|c| !c.is_empty()) {
+        let chars: Vec<char> = chunk.chars().collect();
+        let mut current = String::new();
+
+        for (i, &c) in chars.iter().enumerate() {
+            if let Some(&prev) = i.checked_sub(1).and_then(|p| chars.get(p)) {
+                let next = chars.get(i + 1).copied();
+                let boundary = (prev.is_ascii_lowercase() && c.is_ascii_uppercase())
+                    || (prev.is_ascii_uppercase() && c.is_ascii_uppercase()
+                        && next.is_some_and(|n| n.is_ascii_lowercase()))
+                    || (prev.is_ascii_digit() != c.is_ascii_digit());
+                if boundary && !current.is_empty() {
+                    pieces.push(current.to_lowercase());
+                    current.clear();
+                }
+            }
+            current.push(c);
+        }
+        if !current.is_empty() {
+            pieces.push(current.to_lowercase());
+        }
+    }
+    pieces
+}
+
+/// 加载词根的英文缩写与全称索引 (key 为去空格的小写形式，缩写优先)
+async fn load_root_keys(pool: &PgPool) -> Result<HashMap<String, RootKey>, sqlx::Error> {
+    let rows = sqlx::query!("SELECT id, cn_name, en_abbr, en_full_name FROM standard_word_roots ORDER BY id")
+        .fetch_all(pool)
+        .await?;
+
+    let mut keys: HashMap<String, RootKey> = HashMap::new();
+    for r in &rows {
+        if let Some(full) = r.en_full_name.as_deref() {
+            let key: String = full.chars().filter(|c| c.is_ascii_alphanumeric()).collect::<String>().to_lowercase();
+            if !key.is_empty() {
+                keys.entry(key).or_insert(RootKey {
+                    id: r.id, cn_name: r.cn_name.clone(), en_abbr: r.en_abbr.clone(), matched_by: "en_full_name",
+                });
+            }
+        }
+    }
+    for r in &rows {
+        let key = r.en_abbr.trim().to_lowercase();
+        if key.is_empty() { continue; }
+        // 缩写命中优先于全称命中，同一缩写保留最早创建的词根
+        let replace = keys.get(&key).is_none_or(|k| k.matched_by == "en_full_name");
+        if replace {
+            keys.insert(key, RootKey {
+                id: r.id, cn_name: r.cn_name.clone(), en_abbr: r.en_abbr.clone(), matched_by: "en_abbr",
+            });
+        }
+    }
+    Ok(keys)
+}
+
+fn root_segment(piece: &str, key: &RootKey) -> ReverseSegment {
+    ReverseSegment {
+        piece: piece.to_string(),
+        kind: "root",
+        root_id: Some(key.id),
+        cn_name: Some(key.cn_name.clone()),
+        en_abbr: Some(key.en_abbr.clone()),
+        matched_by: Some(key.matched_by),
+    }
+}
+
+fn plain_segment(piece: &str, kind: &'static str) -> ReverseSegment {
+    ReverseSegment { piece: piece.to_string(), kind, root_id: None, cn_name: None, en_abbr: None, matched_by: None }
+}
+
+/// 对无法整体命中的片段做贪婪最长匹配 (用于 custopenacct 这类粘连命名)
+/// 连续无法匹配的字符合并为一个未知片段
+fn greedy_split(piece: &str, keys: &HashMap<String, RootKey>, max_key_len: usize) -> Vec<ReverseSegment> {
+    let mut segments = Vec::new();
+    let mut unknown = String::new();
+    let mut i = 0;
+
+    while i < piece.len() {
+        let upper = (i + max_key_len).min(piece.len());
+        // 至少 2 个字符才参与贪婪匹配，避免单字母缩写把未知片段切碎
+        let hit = (i + 2..=upper).rev().find_map(|end| keys.get(&piece[i..end]).map(|k| (end, k)));
+
+        match hit {
+            Some((end, key)) => {
+                if !unknown.is_empty() {
+                    segments.push(plain_segment(&unknown, "unknown"));
+                    unknown.clear();
+                }
+                segments.push(root_segment(&piece[i..end], key));
+                i = end;
+            }
+            None => {
+                unknown.push_str(&piece[i..i + 1]);
+                i += 1;
+            }
+        }
+    }
+    if !unknown.is_empty() {
+        segments.push(plain_segment(&unknown, "unknown"));
+    }
+    segments
+}
+
+/// 英文标识符反向翻译为中文名 (suggest_field_name 的逆过程)
+pub async fn reverse_translate(pool: &PgPool, input: &str) -> Result<ReverseResult, sqlx::Error> {
+    let keys = load_root_keys(pool).await?;
+    let max_key_len = keys.keys().map(|k| k.len()).max().unwrap_or(0);
+
+    let mut segments = Vec::new();
+    for piece in split_identifier(input) {
+        if piece.chars().all(|c| c.is_ascii_digit()) {
+            segments.push(plain_segment(&piece, "number"));
+        } else if let Some(key) = keys.get(&piece) {
+            segments.push(root_segment(&piece, key));
+        } else {
+            segments.extend(greedy_split(&piece, &keys, max_key_len));
+        }
+    }
+
+    let cn_name = segments
+        .iter()
+        .map(|s| match s.kind {
+            "root" => s.cn_name.clone().unwrap_or_default(),
+            "number" => s.piece.clone(),
+            _ => format!("[{}]", s.piece),
+        })
+        .collect::<String>();
+
+    let matched_roots = segments.iter().filter(|s| s.kind == "root").cloned().collect();
+    let unknown_pieces = segments.iter().filter(|s| s.kind == "unknown").map(|s| s.piece.clone()).collect();
+
+    Ok(ReverseResult { input: input.to_string(), cn_name, segments, matched_roots, unknown_pieces })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn keys(abbrs: &[&str]) -> HashMap<String, RootKey> {
+        abbrs
+            .iter()
+            .enumerate()
+            .map(|(i, a)| {
+                let key = RootKey { id: i as i32 + 1, cn_name: format!("词根{}", i + 1), en_abbr: a.to_string(), matched_by: "en_abbr" };
+                (a.to_string(), key)
+            })
+            .collect()
+    }
+
+    fn pieces(segments: &[ReverseSegment]) -> Vec<(&str, &str)> {
+        segments.iter().map(|s| (s.piece.as_str(), s.kind)).collect()
+    }
+
+    #[test]
+    fn splits_separators_and_camel_case() {
+        assert_eq!(split_identifier("custOpenAcct_dt"), vec!["cust", "open", "acct", "dt"]);
+        assert_eq!(split_identifier("CUST_OPEN-DT"), vec!["cust", "open", "dt"]);
+    }
+
+    #[test]
+    fn splits_acronyms_and_digits() {
+        assert_eq!(split_identifier("HTTPServer2"), vec!["http", "server", "2"]);
+        assert_eq!(split_identifier("addr2Line"), vec!["addr", "2", "line"]);
+    }
+
+    #[test]
+    fn split_edge_cases() {
+        assert!(split_identifier("").is_empty());
+        assert!(split_identifier("__--").is_empty());
+        assert_eq!(split_identifier("12345"), vec!["12345"]);
+    }
+
+    #[test]
+    fn greedy_splits_glued_roots() {
+        let keys = keys(&["cust", "open", "acct"]);
+        let segs = greedy_split("custopenacct", &keys, 4);
+        assert_eq!(pieces(&segs), vec![("cust", "root"), ("open", "root"), ("acct", "root")]);
+        assert_eq!(segs[2].root_id, Some(3));
+    }
+
+    #[test]
+    fn greedy_prefers_longest_match() {
+        let keys = keys(&["acc", "acct"]);
+        assert_eq!(pieces(&greedy_split("acct", &keys, 4)), vec![("acct", "root")]);
+    }
+
+    #[test]
+    fn greedy_merges_unknown_runs() {
+        let keys = keys(&["cust", "acct"]);
+        assert_eq!(
+            pieces(&greedy_split("custxyzacct", &keys, 4)),
+            vec![("cust", "root"), ("xyz", "unknown"), ("acct", "root")]
+        );
+    }
+
+    #[test]
+    fn greedy_ignores_single_letter_keys() {
+        let keys = keys(&["x", "cust"]);
+        assert_eq!(pieces(&greedy_split("xcust", &keys, 4)), vec![("x", "unknown"), ("cust", "root")]);
+    }
+
+    #[test]
+    fn greedy_edge_cases() {
+        let keys = keys(&["cust"]);
+        assert!(greedy_split("", &keys, 4).is_empty());
+        assert_eq!(pieces(&greedy_split("123", &keys, 4)), vec![("123", "unknown")]);
+        // 词典为空时整体作为未知片段
+        assert_eq!(pieces(&greedy_split("custname", &HashMap::new(), 0)), vec![("custname", "unknown")]);
+    }
+}
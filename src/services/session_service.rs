@@ -0,0 +1,340 @@
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+use sqlx::PgPool;
+use crate::models::user::Claims;
+
+#[derive(Serialize)]
+pub struct AuthSession {
+    pub id: i64,
+    pub user_agent: Option<String>,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+    pub last_used_at: chrono::DateTime<chrono::Utc>,
+}
+
+/// 刷新成功：会话所属用户与新的刷新令牌
+pub struct Rotated {
+    pub user_id: i32,
+    pub role: String,
+    pub session_id: i64,
+    pub refresh_token: String,
+}
+
+pub enum RefreshError {
+    Invalid(&'static str), // 令牌不存在、已过期或会话已吊销
+    Reused(i64),           // 已轮换的令牌被再次使用，会话已吊销
+    Db(sqlx::Error),
+}
+
+impl From<sqlx::Error> for RefreshError {
+    fn from(e: sqlx::Error) -> Self {
+        RefreshError::Db(e)
+    }
+}
+
+fn env_i64(key: &str, default: i64) -> i64 {
+    std::env::var(key).ok().and_then(|v| v.parse().ok()).filter(|v| *v > 0).unwrap_or(default)
+}
+
+/// 访问令牌有效期 (秒)
+pub fn access_ttl_secs() -> i64 {
+    env_i64("JWT_ACCESS_TTL_MINUTES", 15) * 60
+}
+
+fn refresh_ttl_secs() -> f64 {
+    (env_i64("JWT_REFRESH_TTL_DAYS", 30) * 86400) as f64
+}
+
+// 事件流票据有效期 (秒)
+const STREAM_TICKET_TTL_SECS: f64 = 30.0;
+
+fn generate_token() -> String {
+    hex::encode(rand::random::<[u8; 32]>())
+}
+
+fn hash_token(token: &str) -> String {
+    hex::encode(Sha256::digest(token.as_bytes()))
+}
+
+async fn insert_refresh_token<'e, E: sqlx::PgExecutor<'e>>(executor: E, session_id: i64) -> Result<String, sqlx::Error> {
+    let token = generate_token();
+    sqlx::query!(
+        r#"INSERT INTO refresh_tokens (session_id, token_hash, expires_at)
+           VALUES ($1, $2, CURRENT_TIMESTAMP + make_interval(secs => $3))"#,
+        session_id, hash_token(&token), refresh_ttl_secs()
+    )
+    .execute(executor)
+    .await?;
+    Ok(token)
+}
+
+/// 登录时创建会话，返回 (会话 ID, 刷新令牌)
+pub async fn create(pool: &PgPool, user_id: i32, user_agent: Option<&str>) -> Result<(i64, String), sqlx::Error> {
+    let mut tx = pool.begin().await?;
+    let session_id = sqlx::query_scalar!(
+        "INSERT INTO auth_sessions (user_id, user_agent) VALUES ($1, $2) RETURNING id",
+        user_id, user_agent
+    )
+    .fetch_one(&mut *tx)
+    .await?;
+    let token = insert_refresh_token(&mut *tx, session_id).await?;
+    tx.commit().await?;
+    Ok((session_id, token))
+}
+
+/// 轮换刷新令牌：旧令牌标记为已使用并签发新令牌；已使用的令牌再次出现时吊销整个会话
+pub async fn rotate(pool: &PgPool, token: &str) -> Result<Rotated, RefreshError> {
+    let mut tx = pool.begin().await?;
+    let row = sqlx::query!(
+        r#"SELECT rt.id, rt.session_id, rt.used_at, rt.expires_at < CURRENT_TIMESTAMP AS "expired!",
+                  s.revoked_at, u.id AS user_id, u.role AS "role!"
+           FROM refresh_tokens rt
+           JOIN auth_sessions s ON s.id = rt.session_id
+           JOIN users u ON u.id = s.user_id
+           WHERE rt.token_hash = $1
+           FOR UPDATE OF rt, s"#,
+        hash_token(token)
+    )
+    .fetch_optional(&mut *tx)
+    .await?;
+
+    let Some(row) = row else {
+        return Err(RefreshError::Invalid("刷新令牌无效"));
+    };
+    if row.revoked_at.is_some() {
+        return Err(RefreshError::Invalid("会话已注销"));
+    }
+    if row.used_at.is_some() {
+        sqlx::query!(
+            "UPDATE auth_sessions SET revoked_at = CURRENT_TIMESTAMP, revoke_reason = 'reuse' WHERE id = $1",
+            row.session_id
+        )
+        .execute(&mut *tx)
+        .await?;
+        tx.commit().await?;
+        return Err(RefreshError::Reused(row.session_id));
+    }
+    if row.expired {
+        return Err(RefreshError::Invalid("刷新令牌已过期"));
+    }
+
+    sqlx::query!("UPDATE refresh_tokens SET used_at = CURRENT_TIMESTAMP WHERE id = $1", row.id)
+        .execute(&mut *tx)
+        .await?;
+    sqlx::query!("UPDATE auth_sessions SET last_used_at = CURRENT_TIMESTAMP WHERE id = $1", row.session_id)
+        .execute(&mut *tx)
+        .await?;
+    let refresh_token = insert_refresh_token(&mut *tx, row.session_id).await?;
+    tx.commit().await?;
+
+    Ok(Rotated { user_id: row.user_id, role: row.role, session_id: row.session_id, refresh_token })
+}
+
+/// 吊销用户的单个会话，返回是否存在未吊销的该会话
+pub async fn revoke(pool: &PgPool, user_id: i32, session_id: i64, reason: &str) -> Result<bool, sqlx::Error> {
+    let res = sqlx::query!(
+        r#"UPDATE auth_sessions SET revoked_at = CURRENT_TIMESTAMP, revoke_reason = $3
+           WHERE id = $1 AND user_id = $2 AND revoked_at IS NULL"#,
+        session_id, user_id, reason
+    )
+    .execute(pool)
+    .await?;
+    Ok(res.rows_affected() > 0)
+}
+
+/// 吊销用户全部会话，并使已签发的访问令牌立即失效
+pub async fn revoke_all(pool: &PgPool, user_id: i32, reason: &str) -> Result<u64, sqlx::Error> {
+    let mut tx = pool.begin().await?;
+    let res = sqlx::query!(
+        r#"UPDATE auth_sessions SET revoked_at = CURRENT_TIMESTAMP, revoke_reason = $2
+           WHERE user_id = $1 AND revoked_at IS NULL"#,
+        user_id, reason
+    )
+    .execute(&mut *tx)
+    .await?;
+    invalidate_tokens(&mut *tx, user_id).await?;
+    tx.commit().await?;
+    Ok(res.rows_affected())
+}
+
+/// 使用户此前签发的访问令牌失效 (会话保留，客户端刷新后获得新角色)
+pub async fn invalidate_tokens<'e, E: sqlx::PgExecutor<'e>>(executor: E, user_id: i32) -> Result<(), sqlx::Error> {
+    sqlx::query!("UPDATE users SET tokens_invalidated_at = CURRENT_TIMESTAMP WHERE id = $1", user_id)
+        .execute(executor)
+        .await?;
+    Ok(())
+}
+
+/// 用户的有效会话
+pub async fn list(pool: &PgPool, user_id: i32) -> Result<Vec<AuthSession>, sqlx::Error> {
+    sqlx::query_as!(
+        AuthSession,
+        r#"SELECT id, user_agent, created_at, last_used_at FROM auth_sessions
+           WHERE user_id = $1 AND revoked_at IS NULL
+           ORDER BY last_used_at DESC"#,
+        user_id
+    )
+    .fetch_all(pool)
+    .await
+}
+
+/// 校验访问令牌是否仍有效：用户存在、会话未吊销、角色未变更、签发时间不早于失效时间
+/// 签发时间按毫秒比较，失效前同一秒内签发的令牌同样失效
+pub async fn is_active(pool: &PgPool, claims: &Claims) -> Result<bool, sqlx::Error> {
+    let active = sqlx::query_scalar!(
+        r#"SELECT EXISTS (
+               SELECT 1 FROM users u JOIN auth_sessions s ON s.user_id = u.id
+               WHERE u.id = $1 AND s.id = $2 AND s.revoked_at IS NULL AND u.role = $4
+                 AND (u.tokens_invalidated_at IS NULL
+                      OR $3 >= floor(extract(epoch FROM u.tokens_invalidated_at) * 1000)::BIGINT)
+           ) AS "active!""#,
+        claims.sub, claims.sid, claims.iat_ms, claims.role
+    )
+    .fetch_one(pool)
+    .await?;
+    Ok(active)
+}
+
+/// 为当前会话签发一次性事件流票据，顺带清理过期票据
+pub async fn issue_stream_ticket(pool: &PgPool, session_id: i64) -> Result<(String, f64), sqlx::Error> {
+    let ticket = generate_token();
+    let mut tx = pool.begin().await?;
+    sqlx::query!("DELETE FROM event_stream_tickets WHERE expires_at < CURRENT_TIMESTAMP")
+        .execute(&mut *tx)
+        .await?;
+    sqlx::query!(
+        r#"INSERT INTO event_stream_tickets (ticket_hash, session_id, expires_at)
+           VALUES ($1, $2, CURRENT_TIMESTAMP + make_interval(secs => $3))"#,
+        hash_token(&ticket), session_id, STREAM_TICKET_TTL_SECS
+    )
+    .execute(&mut *tx)
+    .await?;
+    tx.commit().await?;
+    Ok((ticket, STREAM_TICKET_TTL_SECS))
+}
+
+/// 兑换事件流票据 (兑换即删除)：票据未过期、会话未吊销且用户仍为管理员时返回 (用户 ID, 会话 ID)
+pub async fn redeem_stream_ticket(pool: &PgPool, ticket: &str) -> Result<Option<(i32, i64)>, sqlx::Error> {
+    let row = sqlx::query!(
+        r#"WITH t AS (
+               DELETE FROM event_stream_tickets WHERE ticket_hash = $1 RETURNING session_id, expires_at
+           )
+           SELECT u.id AS "user_id!", s.id AS "session_id!" FROM t
+           JOIN auth_sessions s ON s.id = t.session_id
+           JOIN users u ON u.id = s.user_id
+           WHERE t.expires_at >= CURRENT_TIMESTAMP AND s.revoked_at IS NULL AND u.role = 'admin'"#,
+        hash_token(ticket)
+    )
+    .fetch_optional(pool)
+    .await?;
+    Ok(row.map(|r| (r.user_id, r.session_id)))
+}
+
+/// 事件流连接期间定期校验：会话未吊销且用户仍为管理员
+pub async fn is_admin_session(pool: &PgPool, session_id: i64) -> Result<bool, sqlx::Error> {
+    let active = sqlx::query_scalar!(
+        r#"SELECT EXISTS (
+               SELECT 1 FROM auth_sessions s JOIN users u ON u.id = s.user_id
+               WHERE s.id = $1 AND s.revoked_at IS NULL AND u.role = 'admin'
+           ) AS "active!""#,
+        session_id
+    )
+    .fetch_one(pool)
+    .await?;
+    Ok(active)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 连接 DATABASE_URL 指向的测试库并创建临时用户；未配置数据库时跳过
+    async fn setup(role: &str) -> Option<(PgPool, i32)> {
+        let url = std::env::var("DATABASE_URL").ok()?;
+        let pool = PgPool::connect(&url).await.expect("连接测试数据库失败");
+        let username = format!("session_test_{}", generate_token());
+        let user_id = sqlx::query_scalar!(
+            "INSERT INTO users (username, password_hash, role) VALUES ($1, '', $2) RETURNING id",
+            &username[..50], role
+        )
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+        Some((pool, user_id))
+    }
+
+    async fn cleanup(pool: &PgPool, user_id: i32) {
+        sqlx::query!("DELETE FROM users WHERE id = $1", user_id).execute(pool).await.unwrap();
+    }
+
+    fn claims(user_id: i32, session_id: i64, role: &str, iat_ms: i64) -> Claims {
+        Claims { sub: user_id, exp: 0, iat: (iat_ms / 1000) as usize, iat_ms, sid: session_id, role: role.to_string() }
+    }
+
+    #[tokio::test]
+    async fn rotation_issues_new_token_and_reuse_revokes_session() {
+        let Some((pool, user_id)) = setup("user").await else { return };
+        let (session_id, first) = create(&pool, user_id, None).await.unwrap();
+
+        let rotated = rotate(&pool, &first).await.unwrap_or_else(|_| panic!("首次轮换应成功"));
+        assert_eq!(rotated.session_id, session_id);
+        assert_ne!(rotated.refresh_token, first);
+
+        // 旧令牌再次使用：判定为泄露并吊销会话
+        assert!(matches!(rotate(&pool, &first).await, Err(RefreshError::Reused(id)) if id == session_id));
+        // 会话已吊销，新令牌同样失效
+        assert!(matches!(rotate(&pool, &rotated.refresh_token).await, Err(RefreshError::Invalid(_))));
+        assert!(matches!(rotate(&pool, "unknown").await, Err(RefreshError::Invalid(_))));
+
+        cleanup(&pool, user_id).await;
+    }
+
+    #[tokio::test]
+    async fn stream_ticket_is_single_use_and_admin_only() {
+        let Some((pool, admin_id)) = setup("admin").await else { return };
+        let (session_id, _) = create(&pool, admin_id, None).await.unwrap();
+
+        let (ticket, _) = issue_stream_ticket(&pool, session_id).await.unwrap();
+        assert_eq!(redeem_stream_ticket(&pool, &ticket).await.unwrap(), Some((admin_id, session_id)));
+        assert_eq!(redeem_stream_ticket(&pool, &ticket).await.unwrap(), None);
+
+        // 会话吊销后票据不可兑换
+        let (ticket, _) = issue_stream_ticket(&pool, session_id).await.unwrap();
+        revoke(&pool, admin_id, session_id, "logout").await.unwrap();
+        assert_eq!(redeem_stream_ticket(&pool, &ticket).await.unwrap(), None);
+        assert!(!is_admin_session(&pool, session_id).await.unwrap());
+        cleanup(&pool, admin_id).await;
+
+        // 非管理员会话不可兑换
+        let Some((pool, user_id)) = setup("user").await else { return };
+        let (session_id, _) = create(&pool, user_id, None).await.unwrap();
+        let (ticket, _) = issue_stream_ticket(&pool, session_id).await.unwrap();
+        assert_eq!(redeem_stream_ticket(&pool, &ticket).await.unwrap(), None);
+        cleanup(&pool, user_id).await;
+    }
+
+    #[tokio::test]
+    async fn invalidation_rejects_tokens_issued_earlier_in_the_same_second() {
+        let Some((pool, user_id)) = setup("user").await else { return };
+        let (session_id, _) = create(&pool, user_id, None).await.unwrap();
+
+        let before = chrono::Utc::now().timestamp_millis();
+        assert!(is_active(&pool, &claims(user_id, session_id, "user", before)).await.unwrap());
+
+        invalidate_tokens(&pool, user_id).await.unwrap();
+        let invalidated_at = sqlx::query_scalar!(
+            r#"SELECT floor(extract(epoch FROM tokens_invalidated_at) * 1000)::BIGINT AS "ms!" FROM users WHERE id = $1"#,
+            user_id
+        )
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+
+        // 失效前 1 毫秒签发的令牌 (与失效时间同一秒时旧实现会放行)
+        assert!(!is_active(&pool, &claims(user_id, session_id, "user", invalidated_at - 1)).await.unwrap());
+        assert!(is_active(&pool, &claims(user_id, session_id, "user", invalidated_at + 1)).await.unwrap());
+        // 角色与令牌不一致时失效
+        assert!(!is_active(&pool, &claims(user_id, session_id, "admin", invalidated_at + 1)).await.unwrap());
+
+        cleanup(&pool, user_id).await;
+    }
+}
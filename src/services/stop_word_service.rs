@@ -0,0 +1,33 @@
+use once_cell::sync::Lazy;
+use sqlx::PgPool;
+use std::collections::HashSet;
+use tokio::sync::RwLock;
+
+// 停用词与标点的内存副本，分词时每个 token 都要判断，避免逐个查库
+static STOP_WORDS: Lazy<RwLock<HashSet<String>>> = Lazy::new(|| RwLock::new(HashSet::new()));
+
+/// 从数据库重新加载停用词表 (启动时及增删改后调用)
+pub async fn reload(pool: &PgPool) {
+    let words = match sqlx::query_scalar!("SELECT word FROM stop_words").fetch_all(pool).await {
+        Ok(w) => w,
+        Err(e) => {
+            tracing::error!("!!! 停用词表加载失败，保留当前副本: {}", e);
+            return;
+        }
+    };
+
+    let count = words.len();
+    *STOP_WORDS.write().await = words.into_iter().collect();
+    tracing::info!("停用词表加载完成，共计 {} 个词条", count);
+}
+
+/// 获取当前停用词表的快照
+pub async fn snapshot() -> HashSet<String> {
+    STOP_WORDS.read().await.clone()
+}
+
+/// 判断分词结果是否应被忽略：命中停用词表，或完全由空白/符号组成
+pub fn is_ignorable(token: &str, stop_words: &HashSet<String>) -> bool {
+    let t = token.trim();
+    t.is_empty() || stop_words.contains(t) || t.chars().all(|c| !c.is_alphanumeric())
+}
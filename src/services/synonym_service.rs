@@ -0,0 +1,133 @@
+use serde::Serialize;
+use sqlx::{PgConnection, PgPool};
+use std::collections::BTreeMap;
+
+use crate::services::pinyin_service;
+
+/// 支持的同义词类型
+pub const SYN_TYPES: [&str; 4] = ["exact", "near", "abbreviation", "misspelling"];
+
+/// 冲突中涉及的词根
+#[derive(Debug, Serialize)]
+pub struct ConflictRoot {
+    pub root_id: i32,
+    pub cn_name: String,
+    pub en_abbr: String,
+    pub syn_type: Option<String>, // 作为中文名命中时为空
+    pub weight: Option<i32>,
+}
+
+/// 同义词冲突
+#[derive(Debug, Serialize)]
+pub struct SynonymConflict {
+    pub term: String,
+    pub kind: &'static str, // shared 多个词根共用同一同义词 / name 同义词与其他可用词根的中文名相同 (永远不会被匹配到)
+    pub roots: Vec<ConflictRoot>,
+}
+
+/// 按同义词表重新生成词根的 associated_terms 缓存及同义词拼音索引
+/// 缓存按权重降序排列，错别字只用于匹配，不写入缓存
+pub async fn refresh_root_cache(conn: &mut PgConnection, root_id: i32) -> Result<(), sqlx::Error> {
+    let terms = sqlx::query_scalar!(
+        "SELECT term FROM word_root_synonyms WHERE root_id = $1 AND syn_type <> 'misspelling' ORDER BY weight DESC, id",
+        root_id
+    )
+    .fetch_all(&mut *conn)
+    .await?;
+
+    let cache = if terms.is_empty() { None } else { Some(terms.join(" ")) };
+    let terms_pinyin = pinyin_service::terms_index(cache.as_deref());
+    sqlx::query!(
+        "UPDATE standard_word_roots SET associated_terms = $1, terms_pinyin = $2 WHERE id = $3",
+        cache, terms_pinyin, root_id
+    )
+    .execute(&mut *conn)
+    .await?;
+    Ok(())
+}
+
+/// 按词根表单提交的同义词列表同步同义词表 (在调用方事务中执行)：
+/// 列表中不再出现的同义词 (错别字除外) 被删除，新出现的按 exact 类型写入，保留已有同义词的类型与权重
+pub async fn replace_synonyms(conn: &mut PgConnection, root_id: i32, terms: &[String]) -> Result<(), sqlx::Error> {
+    sqlx::query!(
+        "DELETE FROM word_root_synonyms WHERE root_id = $1 AND syn_type <> 'misspelling' AND term <> ALL($2)",
+        root_id, terms
+    )
+    .execute(&mut *conn)
+    .await?;
+    sqlx::query!(
+        r#"INSERT INTO word_root_synonyms (root_id, term)
+           SELECT $1, t FROM UNNEST($2::TEXT[]) AS t
+           ON CONFLICT (root_id, term) DO NOTHING"#,
+        root_id, terms
+    )
+    .execute(&mut *conn)
+    .await?;
+    refresh_root_cache(conn, root_id).await
+}
+
+/// 检测跨词根的同义词冲突，可按同义词过滤
+pub async fn find_conflicts(pool: &PgPool, term: Option<&str>) -> Result<Vec<SynonymConflict>, sqlx::Error> {
+    let term = term.map(str::trim).filter(|t| !t.is_empty());
+    let mut conflicts: BTreeMap<(String, &'static str), Vec<ConflictRoot>> = BTreeMap::new();
+
+    // 1. 同一同义词 (不区分大小写) 挂在多个词根下
+    let shared = sqlx::query!(
+        r#"SELECT lower(s.term) AS "term!", s.root_id, r.cn_name, r.en_abbr, s.syn_type, s.weight
+           FROM word_root_synonyms s JOIN standard_word_roots r ON r.id = s.root_id
+           WHERE lower(s.term) IN (
+                 SELECT lower(term) FROM word_root_synonyms GROUP BY lower(term) HAVING count(DISTINCT root_id) > 1)
+             AND ($1::TEXT IS NULL OR lower(s.term) = lower($1))
+           ORDER BY 1, s.weight DESC, s.root_id"#,
+        term
+    )
+    .fetch_all(pool)
+    .await?;
+    for r in shared {
+        conflicts.entry((r.term, "shared")).or_default().push(ConflictRoot {
+            root_id: r.root_id,
+            cn_name: r.cn_name,
+            en_abbr: r.en_abbr,
+            syn_type: Some(r.syn_type),
+            weight: Some(r.weight),
+        });
+    }
+
+    // 2. 同义词与另一个词根的中文名相同：中文名优先匹配，该同义词形同虚设
+    let shadowed = sqlx::query!(
+        r#"SELECT s.term, s.root_id, r.cn_name, r.en_abbr, s.syn_type, s.weight,
+                  o.id AS other_id, o.cn_name AS other_cn_name, o.en_abbr AS other_en_abbr
+           FROM word_root_synonyms s
+           JOIN standard_word_roots r ON r.id = s.root_id
+           JOIN standard_word_roots o ON o.cn_name = s.term AND o.id <> s.root_id AND o.status = 'active'
+           WHERE ($1::TEXT IS NULL OR lower(s.term) = lower($1))
+           ORDER BY s.term, s.root_id"#,
+        term
+    )
+    .fetch_all(pool)
+    .await?;
+    for r in shadowed {
+        let roots = conflicts.entry((r.term, "name")).or_default();
+        if !roots.iter().any(|c| c.root_id == r.other_id) {
+            roots.push(ConflictRoot {
+                root_id: r.other_id,
+                cn_name: r.other_cn_name,
+                en_abbr: r.other_en_abbr,
+                syn_type: None,
+                weight: None,
+            });
+        }
+        roots.push(ConflictRoot {
+            root_id: r.root_id,
+            cn_name: r.cn_name,
+            en_abbr: r.en_abbr,
+            syn_type: Some(r.syn_type),
+            weight: Some(r.weight),
+        });
+    }
+
+    Ok(conflicts
+        .into_iter()
+        .map(|((term, kind), roots)| SynonymConflict { term, kind, roots })
+        .collect())
+}
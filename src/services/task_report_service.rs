@@ -0,0 +1,219 @@
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::Serialize;
+use sqlx::PgPool;
+
+/// 按类型统计的未结束申请数
+#[derive(Serialize)]
+pub struct OpenByType {
+    pub task_type: String,
+    pub open: i64,
+    pub in_progress: i64,
+}
+
+/// 处理时长 (小时)，按类型统计
+#[derive(Serialize)]
+pub struct ResolutionTime {
+    pub task_type: String,
+    pub resolved: i64,
+    pub median_hours: Option<f64>,
+    pub p90_hours: Option<f64>,
+}
+
+/// 超期未处理的申请
+#[derive(Serialize)]
+pub struct StaleTask {
+    pub id: i32,
+    pub task_type: String,
+    pub status: String,
+    pub payload: serde_json::Value,
+    pub assignee_id: Option<i32>,
+    pub vote_count: i32,
+    pub age_days: i32,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Serialize)]
+pub struct TaskStats {
+    pub open_total: i64,
+    pub open_by_type: Vec<OpenByType>,
+    pub window_days: i32,              // 处理时长的统计窗口 (按处理时间)
+    pub resolution: Vec<ResolutionTime>,
+    pub median_hours: Option<f64>,     // 窗口内全部类型的处理时长中位数
+    pub older_than_days: i32,
+    pub stale_total: i64,
+    pub stale: Vec<StaleTask>,         // 最早的 100 条
+}
+
+/// 申请的积压与时效统计
+pub async fn stats(pool: &PgPool, older_than_days: i32, window_days: i32) -> Result<TaskStats, sqlx::Error> {
+    let open_by_type = sqlx::query_as!(
+        OpenByType,
+        r#"SELECT task_type,
+                  count(*) FILTER (WHERE status = 'open') AS "open!",
+                  count(*) FILTER (WHERE status = 'in_progress') AS "in_progress!"
+           FROM notification_tasks
+           WHERE status IN ('open', 'in_progress')
+           GROUP BY task_type ORDER BY 2 DESC, task_type"#
+    )
+    .fetch_all(pool)
+    .await?;
+    let open_total = open_by_type.iter().map(|t| t.open + t.in_progress).sum();
+
+    let resolution = sqlx::query_as!(
+        ResolutionTime,
+        r#"SELECT task_type, count(*) AS "resolved!",
+                  percentile_cont(0.5) WITHIN GROUP (ORDER BY extract(epoch FROM resolved_at - created_at)::FLOAT8) / 3600 AS median_hours,
+                  percentile_cont(0.9) WITHIN GROUP (ORDER BY extract(epoch FROM resolved_at - created_at)::FLOAT8) / 3600 AS p90_hours
+           FROM notification_tasks
+           WHERE resolved_at IS NOT NULL AND resolved_at >= CURRENT_TIMESTAMP - make_interval(days => $1)
+           GROUP BY task_type ORDER BY task_type"#,
+        window_days
+    )
+    .fetch_all(pool)
+    .await?;
+    let median_hours = sqlx::query_scalar!(
+        r#"SELECT percentile_cont(0.5) WITHIN GROUP (ORDER BY extract(epoch FROM resolved_at - created_at)::FLOAT8) / 3600
+           FROM notification_tasks
+           WHERE resolved_at IS NOT NULL AND resolved_at >= CURRENT_TIMESTAMP - make_interval(days => $1)"#,
+        window_days
+    )
+    .fetch_one(pool)
+    .await?;
+
+    let stale_total = sqlx::query_scalar!(
+        r#"SELECT count(*) AS "count!" FROM notification_tasks
+           WHERE status IN ('open', 'in_progress') AND created_at < CURRENT_TIMESTAMP - make_interval(days => $1)"#,
+        older_than_days
+    )
+    .fetch_one(pool)
+    .await?;
+    let stale = sqlx::query_as!(
+        StaleTask,
+        r#"SELECT id, task_type, status, payload AS "payload!", assignee_id, vote_count,
+                  extract(day FROM CURRENT_TIMESTAMP - created_at)::INT AS "age_days!", created_at AS "created_at!"
+           FROM notification_tasks
+           WHERE status IN ('open', 'in_progress') AND created_at < CURRENT_TIMESTAMP - make_interval(days => $1)
+           ORDER BY created_at
+           LIMIT 100"#,
+        older_than_days
+    )
+    .fetch_all(pool)
+    .await?;
+
+    Ok(TaskStats {
+        open_total,
+        open_by_type,
+        window_days,
+        resolution,
+        median_hours,
+        older_than_days,
+        stale_total,
+        stale,
+    })
+}
+
+/// 申请人维度：区间内提交的申请及其当前结果
+#[derive(Serialize)]
+pub struct RequesterThroughput {
+    pub user_id: Option<i32>, // 匿名申请为空
+    pub username: Option<String>,
+    pub submitted: i64,
+    pub approved: i64,
+    pub rejected: i64,
+    pub duplicate: i64,
+    pub pending: i64,
+}
+
+/// 管理员维度：区间内处理完成的申请
+#[derive(Serialize)]
+pub struct AdminThroughput {
+    pub user_id: Option<i32>,
+    pub username: Option<String>,
+    pub resolved: i64,
+    pub approved: i64,
+    pub rejected: i64,
+    pub duplicate: i64,
+    pub median_hours: Option<f64>,
+}
+
+/// 按周的新增与处理数
+#[derive(Serialize)]
+pub struct WeeklyThroughput {
+    pub week_start: NaiveDate,
+    pub submitted: i64,
+    pub resolved: i64,
+}
+
+#[derive(Serialize)]
+pub struct ThroughputReport {
+    pub from: NaiveDate,
+    pub to: NaiveDate, // 含当天
+    pub submitted: i64,
+    pub resolved: i64,
+    pub by_requester: Vec<RequesterThroughput>,
+    pub by_admin: Vec<AdminThroughput>,
+    pub weekly: Vec<WeeklyThroughput>,
+}
+
+/// 区间吞吐报表：按申请人、处理人与周汇总
+pub async fn throughput(pool: &PgPool, from: NaiveDate, to: NaiveDate) -> Result<ThroughputReport, sqlx::Error> {
+    let by_requester = sqlx::query_as!(
+        RequesterThroughput,
+        r#"SELECT t.submitter_id AS user_id, u.username AS "username?",
+                  count(*) AS "submitted!",
+                  count(*) FILTER (WHERE t.status = 'approved') AS "approved!",
+                  count(*) FILTER (WHERE t.status = 'rejected') AS "rejected!",
+                  count(*) FILTER (WHERE t.status = 'duplicate') AS "duplicate!",
+                  count(*) FILTER (WHERE t.status IN ('open', 'in_progress')) AS "pending!"
+           FROM notification_tasks t LEFT JOIN users u ON u.id = t.submitter_id
+           WHERE t.created_at >= $1::DATE AND t.created_at < $2::DATE + 1
+           GROUP BY t.submitter_id, u.username
+           ORDER BY 3 DESC, 1"#,
+        from, to
+    )
+    .fetch_all(pool)
+    .await?;
+
+    let by_admin = sqlx::query_as!(
+        AdminThroughput,
+        r#"SELECT t.resolved_by AS user_id, u.username AS "username?",
+                  count(*) AS "resolved!",
+                  count(*) FILTER (WHERE t.status = 'approved') AS "approved!",
+                  count(*) FILTER (WHERE t.status = 'rejected') AS "rejected!",
+                  count(*) FILTER (WHERE t.status = 'duplicate') AS "duplicate!",
+                  percentile_cont(0.5) WITHIN GROUP (ORDER BY extract(epoch FROM t.resolved_at - t.created_at)::FLOAT8) / 3600 AS median_hours
+           FROM notification_tasks t LEFT JOIN users u ON u.id = t.resolved_by
+           WHERE t.resolved_at >= $1::DATE AND t.resolved_at < $2::DATE + 1
+             AND t.status IN ('approved', 'rejected', 'duplicate')
+           GROUP BY t.resolved_by, u.username
+           ORDER BY 3 DESC, 1"#,
+        from, to
+    )
+    .fetch_all(pool)
+    .await?;
+
+    let weekly = sqlx::query_as!(
+        WeeklyThroughput,
+        r#"SELECT w::DATE AS "week_start!",
+                  (SELECT count(*) FROM notification_tasks
+                   WHERE created_at >= GREATEST(w, $1::DATE) AND created_at < LEAST(w + INTERVAL '7 days', $2::DATE + 1)) AS "submitted!",
+                  (SELECT count(*) FROM notification_tasks
+                   WHERE resolved_at >= GREATEST(w, $1::DATE) AND resolved_at < LEAST(w + INTERVAL '7 days', $2::DATE + 1)
+                     AND status IN ('approved', 'rejected', 'duplicate')) AS "resolved!"
+           FROM generate_series(date_trunc('week', $1::DATE), $2::DATE, INTERVAL '7 days') AS w
+           ORDER BY w"#,
+        from, to
+    )
+    .fetch_all(pool)
+    .await?;
+
+    Ok(ThroughputReport {
+        from,
+        to,
+        submitted: by_requester.iter().map(|r| r.submitted).sum(),
+        resolved: by_admin.iter().map(|a| a.resolved).sum(),
+        by_requester,
+        by_admin,
+        weekly,
+    })
+}
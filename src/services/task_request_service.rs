@@ -0,0 +1,175 @@
+use sqlx::PgPool;
+
+use crate::models::task_request::{FieldDiff, TaskRequest};
+use crate::services::{synonym_service, text_normalizer};
+
+#[derive(Debug)]
+pub enum TaskRequestError {
+    Invalid(String),
+    Db(sqlx::Error),
+}
+
+impl From<sqlx::Error> for TaskRequestError {
+    fn from(e: sqlx::Error) -> Self {
+        TaskRequestError::Db(e)
+    }
+}
+
+/// 合并相同申请的键：同类型下键相同的未结束申请视为同一申请
+pub fn dedup_key(request: &TaskRequest) -> String {
+    let norm = |s: &str| text_normalizer::normalize(s).to_lowercase();
+    match request {
+        TaskRequest::FieldRequest(p) => norm(&p.field_cn_name),
+        TaskRequest::RootRequest(p) => norm(&p.cn_name),
+        TaskRequest::FieldUpdate(p) => format!(
+            "{}:{}",
+            p.field_id,
+            serde_json::to_string(&p.diff).unwrap_or_default()
+        ),
+        TaskRequest::DeprecationRequest(p) => format!("{}:{}", p.target, p.target_id),
+        TaskRequest::SynonymSuggestion(p) => format!("{}:{}", p.root_id, norm(&p.term)),
+    }
+}
+
+fn required(value: &str, name: &str, max_len: usize) -> Result<(), TaskRequestError> {
+    let value = value.trim();
+    if value.is_empty() {
+        return Err(TaskRequestError::Invalid(format!("{}不能为空", name)));
+    }
+    if value.chars().count() > max_len {
+        return Err(TaskRequestError::Invalid(format!("{}长度不能超过 {}", name, max_len)));
+    }
+    Ok(())
+}
+
+// 空串视为清空
+fn non_empty(value: Option<&str>) -> Option<String> {
+    value.map(str::trim).filter(|v| !v.is_empty()).map(String::from)
+}
+
+/// 生成字段修改的差异，只保留与当前值不同的属性
+fn field_diff(
+    current: [(&str, Option<String>); 4],
+    changes: [Option<&str>; 4],
+) -> Vec<FieldDiff> {
+    current
+        .into_iter()
+        .zip(changes)
+        .filter_map(|((column, old), new)| {
+            let new = new.map(|v| non_empty(Some(v)))?;
+            let old = non_empty(old.as_deref());
+            (old != new).then(|| FieldDiff { column: column.to_string(), old, new })
+        })
+        .collect()
+}
+
+/// 按申请类型校验 payload；字段修改申请同时按当前字段生成差异
+pub async fn validate(pool: &PgPool, request: &mut TaskRequest) -> Result<(), TaskRequestError> {
+    match request {
+        TaskRequest::FieldRequest(p) => required(&p.field_cn_name, "字段中文名", 200),
+        TaskRequest::RootRequest(p) => {
+            required(&p.cn_name, "词根中文名", 100)?;
+            let exists = sqlx::query_scalar!(
+                "SELECT id FROM standard_word_roots WHERE cn_name = $1 AND status = 'active' LIMIT 1",
+                p.cn_name.trim()
+            )
+            .fetch_optional(pool)
+            .await?;
+            match exists {
+                Some(id) => Err(TaskRequestError::Invalid(format!("词根 [{}] 已存在 (ID={})", p.cn_name.trim(), id))),
+                None => Ok(()),
+            }
+        }
+        TaskRequest::FieldUpdate(p) => {
+            required(&p.reason, "修改原因", 500)?;
+            let field = sqlx::query!(
+                "SELECT field_cn_name, field_en_name, data_type, associated_terms FROM standard_fields WHERE id = $1",
+                p.field_id
+            )
+            .fetch_optional(pool)
+            .await?
+            .ok_or_else(|| TaskRequestError::Invalid(format!("标准字段 ID={} 不存在", p.field_id)))?;
+            if matches!(p.changes.field_cn_name.as_deref().map(str::trim), Some("")) {
+                return Err(TaskRequestError::Invalid("字段中文名不能修改为空".to_string()));
+            }
+            if matches!(p.changes.field_en_name.as_deref().map(str::trim), Some("")) {
+                return Err(TaskRequestError::Invalid("字段英文名不能修改为空".to_string()));
+            }
+            p.diff = field_diff(
+                [
+                    ("field_cn_name", Some(field.field_cn_name)),
+                    ("field_en_name", Some(field.field_en_name)),
+                    ("data_type", field.data_type),
+                    ("associated_terms", field.associated_terms),
+                ],
+                [
+                    p.changes.field_cn_name.as_deref(),
+                    p.changes.field_en_name.as_deref(),
+                    p.changes.data_type.as_deref(),
+                    p.changes.associated_terms.as_deref(),
+                ],
+            );
+            if p.diff.is_empty() {
+                return Err(TaskRequestError::Invalid("申请的内容与当前字段一致，无需修改".to_string()));
+            }
+            Ok(())
+        }
+        TaskRequest::DeprecationRequest(p) => {
+            required(&p.reason, "废弃原因", 500)?;
+            let status = match p.target.as_str() {
+                "root" => sqlx::query_scalar!("SELECT status FROM standard_word_roots WHERE id = $1", p.target_id)
+                    .fetch_optional(pool)
+                    .await?,
+                "field" => sqlx::query_scalar!("SELECT status FROM standard_fields WHERE id = $1", p.target_id)
+                    .fetch_optional(pool)
+                    .await?,
+                other => {
+                    return Err(TaskRequestError::Invalid(format!("不支持的废弃对象 [{}]，可选: root / field", other)));
+                }
+            };
+            match status.as_deref() {
+                None => return Err(TaskRequestError::Invalid(format!("废弃对象 ID={} 不存在", p.target_id))),
+                Some("deprecated") => return Err(TaskRequestError::Invalid(format!("废弃对象 ID={} 已是废弃状态", p.target_id))),
+                _ => {}
+            }
+            if let Some(rid) = p.replacement_id {
+                if rid == p.target_id {
+                    return Err(TaskRequestError::Invalid("替代对象不能是被废弃对象本身".to_string()));
+                }
+                let active = match p.target.as_str() {
+                    "root" => sqlx::query_scalar!("SELECT status = 'active' FROM standard_word_roots WHERE id = $1", rid)
+                        .fetch_optional(pool)
+                        .await?,
+                    _ => sqlx::query_scalar!("SELECT status = 'active' FROM standard_fields WHERE id = $1", rid)
+                        .fetch_optional(pool)
+                        .await?,
+                };
+                if active.flatten() != Some(true) {
+                    return Err(TaskRequestError::Invalid(format!("替代对象 ID={} 不存在或已废弃", rid)));
+                }
+            }
+            Ok(())
+        }
+        TaskRequest::SynonymSuggestion(p) => {
+            required(&p.term, "同义词", 100)?;
+            if let Some(t) = p.syn_type.as_deref() {
+                if !synonym_service::SYN_TYPES.contains(&t) {
+                    return Err(TaskRequestError::Invalid(format!("不支持的同义词类型 [{}]，可选: {:?}", t, synonym_service::SYN_TYPES)));
+                }
+            }
+            let root = sqlx::query!(
+                r#"SELECT r.cn_name,
+                          EXISTS (SELECT 1 FROM word_root_synonyms s WHERE s.root_id = r.id AND lower(s.term) = lower($2)) AS "has_term!"
+                   FROM standard_word_roots r WHERE r.id = $1"#,
+                p.root_id, p.term.trim()
+            )
+            .fetch_optional(pool)
+            .await?
+            .ok_or_else(|| TaskRequestError::Invalid(format!("词根 ID={} 不存在", p.root_id)))?;
+            if root.has_term || root.cn_name == p.term.trim() {
+                return Err(TaskRequestError::Invalid(format!("[{}] 已是词根 [{}] 的名称或同义词", p.term.trim(), root.cn_name)));
+            }
+            Ok(())
+        }
+    }
+}
@@ -0,0 +1,130 @@
+use once_cell::sync::Lazy;
+use std::collections::HashMap;
+
+// 常用繁体字 -> 简体字对照 (仅覆盖数据字典中常见的业务用字，并非完整的繁简映射；两表按位置一一对应)
+const TRADITIONAL: [&str; 8] = [
+    "資額帳賬戶號碼機構類狀態時間區員證聯繫電話郵編標準數據庫錶單價錢費貨幣銀務業產團",
+    "體國內門網絡線級層歷紀記錄統計總稅開關閉啟動變舊應實際報週歲齡會點積獎勵簽約訂購",
+    "買賣銷換負責審認驗長寬廣東華縣鄉鎮條項細節貸還擔險賠權設備維護廠倉儲運輸車輛駕駛",
+    "醫療藥診斷學習師課題試績專屬別職稱補貼發佈傳訊檔圖聲視頻頁鏈擊異錯誤處顯隱選擇預",
+    "註說籤餘幾個們這裡後來對於為與從並將無當階歸過濾測結談論議規範則營淨潤億萬頭經濟",
+    "財監風評優紅禮贈遞種鋪櫃臺兩雙複雜簡轉匯債現戰軍農漢語讀寫識張陳劉楊趙黃紙織組導",
+    "領觀勞賽屆衛災氣溫濕壓淺齊順頂顧質貿隊園圓遠陽陰壽養撥擬臨裝製貴賺虧損賦徵儀鐘鍵",
+    "鎖",
+];
+const SIMPLIFIED: [&str; 8] = [
+    "资额账账户号码机构类状态时间区员证联系电话邮编标准数据库表单价钱费货币银务业产团",
+    "体国内门网络线级层历纪记录统计总税开关闭启动变旧应实际报周岁龄会点积奖励签约订购",
+    "买卖销换负责审认验长宽广东华县乡镇条项细节贷还担险赔权设备维护厂仓储运输车辆驾驶",
+    "医疗药诊断学习师课题试绩专属别职称补贴发布传讯档图声视频页链击异错误处显隐选择预",
+    "注说签余几个们这里后来对于为与从并将无当阶归过滤测结谈论议规范则营净润亿万头经济",
+    "财监风评优红礼赠递种铺柜台两双复杂简转汇债现战军农汉语读写识张陈刘杨赵黄纸织组导",
+    "领观劳赛届卫灾气温湿压浅齐顺顶顾质贸队园圆远阳阴寿养拨拟临装制贵赚亏损赋征仪钟键",
+    "锁",
+];
+
+static T2S: Lazy<HashMap<char, char>> = Lazy::new(|| {
+    TRADITIONAL
+        .concat()
+        .chars()
+        .zip(SIMPLIFIED.concat().chars())
+        .collect()
+});
+
+/// 输入中切分出的文本片段
+#[derive(Debug, Clone, PartialEq)]
+pub enum Chunk {
+    Text(String),   // 中文及其他字符，交给 jieba 分词
+    Word(String),   // 连续的 ASCII 字母，如 GMV
+    Number(String), // 连续的 ASCII 数字，如 2
+}
+
+/// 全角字符转半角 (全角空格转普通空格)
+pub fn to_half_width(input: &str) -> String {
+    input
+        .chars()
+        .map(|c| match c {
+            '\u{3000}' => ' ',
+            '\u{FF01}'..='\u{FF5E}' => char::from_u32(c as u32 - 0xFEE0).unwrap_or(c),
+            _ => c,
+        })
+        .collect()
+}
+
+/// 常用繁体字转简体 (按内置常用字对照表逐字转换，表外的繁体字及其他字符原样保留)
+pub fn to_simplified(input: &str) -> String {
+    input.chars().map(|c| *T2S.get(&c).unwrap_or(&c)).collect()
+}
+
+/// 命名输入的统一规范化：全角转半角 + 常用繁体字转简体 + 去除首尾空白
+pub fn normalize(input: &str) -> String {
+    to_simplified(&to_half_width(input)).trim().to_string()
+}
+
+/// 按书写体系切分：ASCII 字母串、数字串单独成段，其余部分交给分词器
+/// 如 "T+1日期" -> [Word(T), Text(+), Number(1), Text(日期)]
+pub fn split_scripts(input: &str) -> Vec<Chunk> {
+    let mut chunks: Vec<Chunk> = Vec::new();
+    for c in input.chars() {
+        let extended = match (chunks.last_mut(), c) {
+            (Some(Chunk::Word(w)), c) if c.is_ascii_alphabetic() => { w.push(c); true }
+            (Some(Chunk::Number(n)), c) if c.is_ascii_digit() => { n.push(c); true }
+            (Some(Chunk::Text(t)), c) if !c.is_ascii_alphanumeric() => { t.push(c); true }
+            _ => false,
+        };
+        if !extended {
+            chunks.push(if c.is_ascii_alphabetic() {
+                Chunk::Word(c.to_string())
+            } else if c.is_ascii_digit() {
+                Chunk::Number(c.to_string())
+            } else {
+                Chunk::Text(c.to_string())
+            });
+        }
+    }
+    chunks
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn text(s: &str) -> Chunk { Chunk::Text(s.to_string()) }
+    fn word(s: &str) -> Chunk { Chunk::Word(s.to_string()) }
+    fn number(s: &str) -> Chunk { Chunk::Number(s.to_string()) }
+
+    #[test]
+    fn conversion_tables_align() {
+        for (t, s) in TRADITIONAL.iter().zip(SIMPLIFIED.iter()) {
+            assert_eq!(t.chars().count(), s.chars().count(), "对照表行长度不一致: {}", t);
+        }
+    }
+
+    #[test]
+    fn splits_mixed_scripts() {
+        assert_eq!(split_scripts("T+1日期"), vec![word("T"), text("+"), number("1"), text("日期")]);
+        assert_eq!(split_scripts("GMV2占比"), vec![word("GMV"), number("2"), text("占比")]);
+    }
+
+    #[test]
+    fn split_edge_cases() {
+        assert!(split_scripts("").is_empty());
+        assert_eq!(split_scripts("2024"), vec![number("2024")]);
+        assert_eq!(split_scripts("客户名称"), vec![text("客户名称")]);
+    }
+
+    #[test]
+    fn normalizes_width_script_and_whitespace() {
+        assert_eq!(to_half_width("ＧＭＶ　１"), "GMV 1");
+        assert_eq!(to_simplified("帳戶號碼"), "账户号码");
+        assert_eq!(normalize("　開戶日期ＩＤ "), "开户日期ID");
+        assert_eq!(normalize(""), "");
+    }
+
+    #[test]
+    fn characters_outside_table_pass_through() {
+        // 續 (续)、鑰 (钥) 不在常用字对照表内，原样保留
+        assert_eq!(to_simplified("續費"), "續费");
+        assert_eq!(normalize("鑰匙"), "鑰匙");
+    }
+}
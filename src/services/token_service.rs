@@ -0,0 +1,256 @@
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+use jsonwebtoken::{decode, decode_header, encode, errors::ErrorKind, Algorithm, DecodingKey, EncodingKey, Header, Validation};
+use once_cell::sync::Lazy;
+use rsa::{pkcs8::DecodePublicKey, traits::PublicKeyParts, RsaPublicKey};
+use crate::models::user::Claims;
+
+// HS256 密钥最短长度 (字节)
+const MIN_SECRET_LEN: usize = 32;
+
+// Ed25519 公钥的 SubjectPublicKeyInfo DER 前缀，其后紧跟 32 字节原始公钥
+const ED25519_SPKI_PREFIX: [u8; 12] = [0x30, 0x2a, 0x30, 0x05, 0x06, 0x03, 0x2b, 0x65, 0x70, 0x03, 0x21, 0x00];
+
+/// 一把验证密钥；非对称密钥同时生成 JWKS 条目
+struct VerifyKey {
+    kid: String,
+    alg: Algorithm,
+    key: DecodingKey,
+    jwk: Option<serde_json::Value>,
+}
+
+struct KeySet {
+    kid: String,
+    alg: Algorithm,
+    signing: EncodingKey,
+    verify: Vec<VerifyKey>, // 当前密钥在首位，其后为轮换期间仍接受的旧密钥
+}
+
+// 启动时由 init() 加载，配置有误直接终止启动
+static KEYS: Lazy<KeySet> = Lazy::new(|| load().unwrap_or_else(|e| panic!("JWT 密钥配置错误: {}", e)));
+
+fn env_opt(key: &str) -> Option<String> {
+    std::env::var(key).ok().map(|v| v.trim().to_string()).filter(|v| !v.is_empty())
+}
+
+fn parse_alg(raw: &str) -> Result<Algorithm, String> {
+    match raw {
+        "HS256" => Ok(Algorithm::HS256),
+        "RS256" => Ok(Algorithm::RS256),
+        "EdDSA" => Ok(Algorithm::EdDSA),
+        other => Err(format!("不支持的算法 [{}]，可选: HS256 / RS256 / EdDSA", other)),
+    }
+}
+
+fn read_pem(path: &str) -> Result<String, String> {
+    std::fs::read_to_string(path).map_err(|e| format!("读取密钥文件 [{}] 失败: {}", path, e))
+}
+
+fn check_secret(secret: &str) -> Result<(), String> {
+    if secret.len() < MIN_SECRET_LEN {
+        return Err(format!("HS256 密钥长度至少 {} 字节", MIN_SECRET_LEN));
+    }
+    Ok(())
+}
+
+/// 由公钥 PEM 生成 JWKS 条目
+fn public_jwk(kid: &str, alg: Algorithm, pem: &str) -> Result<serde_json::Value, String> {
+    match alg {
+        Algorithm::RS256 => {
+            let key = RsaPublicKey::from_public_key_pem(pem).map_err(|e| format!("RSA 公钥解析失败: {}", e))?;
+            Ok(serde_json::json!({
+                "kty": "RSA", "use": "sig", "alg": "RS256", "kid": kid,
+                "n": URL_SAFE_NO_PAD.encode(key.n().to_bytes_be()),
+                "e": URL_SAFE_NO_PAD.encode(key.e().to_bytes_be()),
+            }))
+        }
+        Algorithm::EdDSA => {
+            let body: String = pem.lines().filter(|l| !l.starts_with("-----")).collect();
+            let der = base64::engine::general_purpose::STANDARD
+                .decode(body.trim())
+                .map_err(|e| format!("Ed25519 公钥解析失败: {}", e))?;
+            let raw = der
+                .strip_prefix(&ED25519_SPKI_PREFIX[..])
+                .filter(|raw| raw.len() == 32)
+                .ok_or("Ed25519 公钥须为 SubjectPublicKeyInfo 格式 (BEGIN PUBLIC KEY)")?;
+            Ok(serde_json::json!({
+                "kty": "OKP", "use": "sig", "alg": "EdDSA", "crv": "Ed25519", "kid": kid,
+                "x": URL_SAFE_NO_PAD.encode(raw),
+            }))
+        }
+        _ => Err("对称密钥不能公开".to_string()),
+    }
+}
+
+/// 构造验证密钥：HS256 传入密钥本身，非对称算法传入公钥 PEM 文件路径
+fn verify_key(kid: &str, alg: Algorithm, source: &str) -> Result<VerifyKey, String> {
+    let (key, jwk) = match alg {
+        Algorithm::HS256 => {
+            check_secret(source)?;
+            (DecodingKey::from_secret(source.as_bytes()), None)
+        }
+        Algorithm::RS256 => {
+            let pem = read_pem(source)?;
+            let key = DecodingKey::from_rsa_pem(pem.as_bytes()).map_err(|e| format!("RSA 公钥 [{}] 无效: {}", source, e))?;
+            (key, Some(public_jwk(kid, alg, &pem)?))
+        }
+        _ => {
+            let pem = read_pem(source)?;
+            let key = DecodingKey::from_ed_pem(pem.as_bytes()).map_err(|e| format!("Ed25519 公钥 [{}] 无效: {}", source, e))?;
+            (key, Some(public_jwk(kid, alg, &pem)?))
+        }
+    };
+    Ok(VerifyKey { kid: kid.to_string(), alg, key, jwk })
+}
+
+fn load() -> Result<KeySet, String> {
+    let alg = parse_alg(&env_opt("JWT_ALGORITHM").unwrap_or_else(|| "HS256".to_string()))?;
+    let kid = env_opt("JWT_KEY_ID").unwrap_or_else(|| "default".to_string());
+
+    let (signing, current) = match alg {
+        Algorithm::HS256 => {
+            let secret = env_opt("JWT_SECRET").ok_or("HS256 需配置 JWT_SECRET")?;
+            check_secret(&secret)?;
+            (EncodingKey::from_secret(secret.as_bytes()), verify_key(&kid, alg, &secret)?)
+        }
+        _ => {
+            let private_path = env_opt("JWT_PRIVATE_KEY_PATH").ok_or("RS256 / EdDSA 需配置 JWT_PRIVATE_KEY_PATH")?;
+            let public_path = env_opt("JWT_PUBLIC_KEY_PATH").ok_or("RS256 / EdDSA 需配置 JWT_PUBLIC_KEY_PATH")?;
+            let pem = read_pem(&private_path)?;
+            let signing = if alg == Algorithm::RS256 {
+                EncodingKey::from_rsa_pem(pem.as_bytes())
+            } else {
+                EncodingKey::from_ed_pem(pem.as_bytes())
+            }
+            .map_err(|e| format!("私钥 [{}] 无效: {}", private_path, e))?;
+            (signing, verify_key(&kid, alg, &public_path)?)
+        }
+    };
+
+    // 旧密钥格式: kid=算法:密钥或公钥路径，多个以逗号分隔
+    let mut verify = vec![current];
+    for item in env_opt("JWT_PREVIOUS_KEYS").unwrap_or_default().split(',').map(str::trim).filter(|s| !s.is_empty()) {
+        let (old_kid, rest) = item.split_once('=').ok_or_else(|| format!("JWT_PREVIOUS_KEYS 格式错误: [{}]", item))?;
+        let (old_alg, source) = rest.split_once(':').ok_or_else(|| format!("JWT_PREVIOUS_KEYS 格式错误: [{}]", item))?;
+        let old_kid = old_kid.trim();
+        if verify.iter().any(|k| k.kid == old_kid) {
+            return Err(format!("kid [{}] 重复", old_kid));
+        }
+        verify.push(verify_key(old_kid, parse_alg(old_alg.trim())?, source.trim())?);
+    }
+
+    Ok(KeySet { kid, alg, signing, verify })
+}
+
+/// 启动时加载并校验密钥配置
+pub fn init() {
+    let keys = Lazy::force(&KEYS);
+    tracing::info!("JWT 签名算法: {:?}, kid={}, 可验证密钥 {} 个", keys.alg, keys.kid, keys.verify.len());
+}
+
+/// 使用当前密钥签发令牌，Header 中携带 kid
+pub fn issue(claims: &Claims) -> Result<String, jsonwebtoken::errors::Error> {
+    let mut header = Header::new(KEYS.alg);
+    header.kid = Some(KEYS.kid.clone());
+    encode(&header, claims, &KEYS.signing)
+}
+
+/// 按 kid 选择验证密钥并校验；算法由密钥决定，不信任 Header 中的 alg
+pub fn verify(token: &str) -> Result<Claims, jsonwebtoken::errors::Error> {
+    verify_with(&KEYS.verify, token)
+}
+
+fn verify_with(keys: &[VerifyKey], token: &str) -> Result<Claims, jsonwebtoken::errors::Error> {
+    let header = decode_header(token)?;
+    let kid = header.kid.ok_or(ErrorKind::InvalidToken)?;
+    let key = keys.iter().find(|k| k.kid == kid).ok_or(ErrorKind::InvalidToken)?;
+    decode::<Claims>(token, &key.key, &Validation::new(key.alg)).map(|data| data.claims)
+}
+
+/// 公开的 JWKS：仅包含非对称公钥，HS256 密钥不对外暴露
+pub fn jwks() -> serde_json::Value {
+    let keys: Vec<&serde_json::Value> = KEYS.verify.iter().filter_map(|k| k.jwk.as_ref()).collect();
+    serde_json::json!({ "keys": keys })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CURRENT: &str = "current-secret-0123456789abcdefghij";
+    const PREVIOUS: &str = "previous-secret-0123456789abcdefghi";
+
+    fn keys() -> Vec<VerifyKey> {
+        vec![
+            verify_key("k2", Algorithm::HS256, CURRENT).unwrap(),
+            verify_key("k1", Algorithm::HS256, PREVIOUS).unwrap(),
+        ]
+    }
+
+    fn claims() -> Claims {
+        let now = chrono::Utc::now();
+        Claims {
+            sub: 1,
+            exp: (now.timestamp() + 600) as usize,
+            iat: now.timestamp() as usize,
+            iat_ms: now.timestamp_millis(),
+            sid: 1,
+            role: "user".to_string(),
+        }
+    }
+
+    fn sign(alg: Algorithm, kid: Option<&str>, secret: &str) -> String {
+        let mut header = Header::new(alg);
+        header.kid = kid.map(String::from);
+        encode(&header, &claims(), &EncodingKey::from_secret(secret.as_bytes())).unwrap()
+    }
+
+    fn kind(token: &str) -> ErrorKind {
+        verify_with(&keys(), token).map(|_| ()).unwrap_err().into_kind()
+    }
+
+    #[test]
+    fn accepts_current_and_previous_keys_by_kid() {
+        assert_eq!(verify_with(&keys(), &sign(Algorithm::HS256, Some("k2"), CURRENT)).unwrap().sub, 1);
+        assert_eq!(verify_with(&keys(), &sign(Algorithm::HS256, Some("k1"), PREVIOUS)).unwrap().sub, 1);
+    }
+
+    #[test]
+    fn rejects_missing_or_unknown_kid() {
+        assert_eq!(kind(&sign(Algorithm::HS256, None, CURRENT)), ErrorKind::InvalidToken);
+        assert_eq!(kind(&sign(Algorithm::HS256, Some("k9"), CURRENT)), ErrorKind::InvalidToken);
+    }
+
+    #[test]
+    fn kid_pins_the_key() {
+        // 旧密钥签名却声明当前 kid
+        assert_eq!(kind(&sign(Algorithm::HS256, Some("k2"), PREVIOUS)), ErrorKind::InvalidSignature);
+    }
+
+    #[test]
+    fn header_alg_cannot_override_key_alg() {
+        assert_eq!(kind(&sign(Algorithm::HS512, Some("k2"), CURRENT)), ErrorKind::InvalidAlgorithm);
+    }
+
+    #[test]
+    fn hs256_secret_needs_minimum_length() {
+        assert!(check_secret(&"x".repeat(MIN_SECRET_LEN)).is_ok());
+        assert_eq!(check_secret(&"x".repeat(MIN_SECRET_LEN - 1)).unwrap_err(), "HS256 密钥长度至少 32 字节");
+        assert!(verify_key("k", Algorithm::HS256, "short").is_err());
+    }
+
+    #[test]
+    fn only_supported_algorithms_parse() {
+        assert_eq!(parse_alg("HS256").unwrap(), Algorithm::HS256);
+        assert_eq!(parse_alg("RS256").unwrap(), Algorithm::RS256);
+        assert_eq!(parse_alg("EdDSA").unwrap(), Algorithm::EdDSA);
+        for raw in ["none", "hs256", "HS512", ""] {
+            assert!(parse_alg(raw).is_err(), "{}", raw);
+        }
+    }
+
+    #[test]
+    fn symmetric_keys_are_not_published() {
+        assert!(keys().iter().all(|k| k.jwk.is_none()));
+        assert!(public_jwk("k", Algorithm::HS256, "").is_err());
+    }
+}
@@ -0,0 +1,253 @@
+use once_cell::sync::Lazy;
+use parking_lot::RwLock;
+use serde::Serialize;
+use sqlx::PgPool;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use crate::services::pinyin_service;
+
+// 单次查询最多扫描的前缀命中键数，保证极短前缀 (如单个字母) 时的响应时间
+const MAX_SCAN: usize = 2000;
+
+// 匹配等级：数值越小排序越靠前
+const RANK_CN_NAME: u8 = 0;
+const RANK_SYNONYM: u8 = 1;
+const RANK_EN_NAME: u8 = 2;
+const RANK_PINYIN: u8 = 3;
+
+#[derive(Debug, Clone, Serialize)]
+pub struct TypeaheadItem {
+    pub kind: &'static str, // field / root
+    pub id: i32,
+    pub cn_name: String,
+    pub en_name: String,
+    pub rank: u8,           // 0 中文名 / 1 同义词 / 2 英文名 / 3 拼音或首字母
+    pub matched: String,    // 命中的索引键
+}
+
+struct Entry {
+    kind: &'static str,
+    id: i32,
+    cn_name: String,
+    en_name: String,
+}
+
+/// 内存前缀索引：按键排序的数组，前缀查询即一次二分定位加顺序扫描
+#[derive(Default)]
+pub struct PrefixIndex {
+    entries: Vec<Entry>,
+    keys: Vec<(String, u8, usize)>, // (小写键, 匹配等级, 条目下标)
+}
+
+static INDEX: Lazy<RwLock<Arc<PrefixIndex>>> = Lazy::new(|| RwLock::new(Arc::new(PrefixIndex::default())));
+
+impl PrefixIndex {
+    fn add_entry(&mut self, entry: Entry, keys: Vec<(String, u8)>) {
+        let idx = self.entries.len();
+        self.entries.push(entry);
+        for (key, rank) in keys {
+            let key = key.trim().to_lowercase();
+            if !key.is_empty() {
+                self.keys.push((key, rank, idx));
+            }
+        }
+    }
+
+    fn finish(mut self) -> Self {
+        self.keys.sort();
+        self.keys.dedup();
+        self
+    }
+
+    fn search(&self, prefix: &str, limit: usize) -> Vec<TypeaheadItem> {
+        let prefix = prefix.trim().to_lowercase();
+        if prefix.is_empty() {
+            return Vec::new();
+        }
+
+        let start = self.keys.partition_point(|(k, _, _)| k.as_str() < prefix.as_str());
+        // 同一条目可能被多个键命中，只保留最好的等级
+        let mut best: HashMap<usize, (u8, &str)> = HashMap::new();
+        for (key, rank, idx) in self.keys[start..].iter().take(MAX_SCAN) {
+            if !key.starts_with(&prefix) {
+                break;
+            }
+            let slot = best.entry(*idx).or_insert((*rank, key.as_str()));
+            if *rank < slot.0 {
+                *slot = (*rank, key.as_str());
+            }
+        }
+
+        let mut items: Vec<TypeaheadItem> = best
+            .into_iter()
+            .map(|(idx, (rank, matched))| {
+                let e = &self.entries[idx];
+                TypeaheadItem {
+                    kind: e.kind,
+                    id: e.id,
+                    cn_name: e.cn_name.clone(),
+                    en_name: e.en_name.clone(),
+                    rank,
+                    matched: matched.to_string(),
+                }
+            })
+            .collect();
+        items.sort_by(|a, b| {
+            a.rank
+                .cmp(&b.rank)
+                .then(a.cn_name.chars().count().cmp(&b.cn_name.chars().count()))
+                .then(a.cn_name.cmp(&b.cn_name))
+                .then(a.kind.cmp(b.kind))
+        });
+        items.truncate(limit);
+        items
+    }
+}
+
+fn synonym_keys(term: &str) -> [(String, u8); 3] {
+    [
+        (term.to_string(), RANK_SYNONYM),
+        (pinyin_service::full(term), RANK_PINYIN),
+        (pinyin_service::initials(term), RANK_PINYIN),
+    ]
+}
+
+/// 从数据库全量重建前缀索引并原子替换
+pub async fn refresh(pool: &PgPool) {
+    let fields = sqlx::query!(
+        "SELECT id, field_cn_name, field_en_name, associated_terms, pinyin, pinyin_initials FROM standard_fields"
+    )
+    .fetch_all(pool)
+    .await;
+    let roots = sqlx::query!(
+        "SELECT id, cn_name, en_abbr, en_full_name, pinyin, pinyin_initials FROM standard_word_roots"
+    )
+    .fetch_all(pool)
+    .await;
+    // 词根同义词取自同义词表 (可含空格)，错别字与权重为 0 的不参与联想
+    let synonyms = sqlx::query!(
+        "SELECT root_id, term FROM word_root_synonyms WHERE weight > 0 AND syn_type <> 'misspelling'"
+    )
+    .fetch_all(pool)
+    .await;
+
+    let (fields, roots, synonyms) = match (fields, roots, synonyms) {
+        (Ok(f), Ok(r), Ok(s)) => (f, r, s),
+        (Err(e), _, _) | (_, Err(e), _) | (_, _, Err(e)) => {
+            tracing::error!("!!! 联想索引重建失败，保留当前索引: {}", e);
+            return;
+        }
+    };
+    let mut terms_by_root: HashMap<i32, Vec<String>> = HashMap::new();
+    for s in synonyms {
+        terms_by_root.entry(s.root_id).or_default().push(s.term);
+    }
+
+    let mut index = PrefixIndex::default();
+    for f in fields {
+        let mut keys = vec![
+            (f.field_cn_name.clone(), RANK_CN_NAME),
+            (f.field_en_name.clone(), RANK_EN_NAME),
+        ];
+        keys.extend(f.pinyin.map(|p| (p, RANK_PINYIN)));
+        keys.extend(f.pinyin_initials.map(|p| (p, RANK_PINYIN)));
+        for term in f.associated_terms.as_deref().unwrap_or("").split_whitespace() {
+            keys.extend(synonym_keys(term));
+        }
+        index.add_entry(
+            Entry { kind: "field", id: f.id, cn_name: f.field_cn_name, en_name: f.field_en_name },
+            keys,
+        );
+    }
+    for r in roots {
+        let mut keys = vec![
+            (r.cn_name.clone(), RANK_CN_NAME),
+            (r.en_abbr.clone(), RANK_EN_NAME),
+        ];
+        keys.extend(r.en_full_name.map(|n| (n, RANK_EN_NAME)));
+        keys.extend(r.pinyin.map(|p| (p, RANK_PINYIN)));
+        keys.extend(r.pinyin_initials.map(|p| (p, RANK_PINYIN)));
+        for term in terms_by_root.remove(&r.id).unwrap_or_default() {
+            keys.extend(synonym_keys(&term));
+        }
+        index.add_entry(
+            Entry { kind: "root", id: r.id, cn_name: r.cn_name, en_name: r.en_abbr },
+            keys,
+        );
+    }
+
+    let index = index.finish();
+    tracing::info!("联想索引已重建: 条目 {} 个, 索引键 {} 个", index.entries.len(), index.keys.len());
+    *INDEX.write() = Arc::new(index);
+}
+
+/// 前缀联想查询 (纯内存，不访问数据库与向量模型)
+pub fn search(prefix: &str, limit: usize) -> Vec<TypeaheadItem> {
+    let index = INDEX.read().clone();
+    index.search(prefix, limit)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(kind: &'static str, id: i32, cn_name: &str, en_name: &str) -> Entry {
+        Entry { kind, id, cn_name: cn_name.to_string(), en_name: en_name.to_string() }
+    }
+
+    fn keys(pairs: &[(&str, u8)]) -> Vec<(String, u8)> {
+        pairs.iter().map(|(k, r)| (k.to_string(), *r)).collect()
+    }
+
+    fn sample() -> PrefixIndex {
+        let mut index = PrefixIndex::default();
+        index.add_entry(
+            entry("field", 1, "客户名称", "cust_name"),
+            keys(&[("客户名称", RANK_CN_NAME), ("cust_name", RANK_EN_NAME), ("kehumingcheng", RANK_PINYIN), ("khmc", RANK_PINYIN)]),
+        );
+        index.add_entry(
+            entry("root", 2, "客户", "cust"),
+            keys(&[("客户", RANK_CN_NAME), ("Cust", RANK_EN_NAME), ("Customer", RANK_EN_NAME), ("kehu", RANK_PINYIN), ("kh", RANK_PINYIN)]),
+        );
+        index.add_entry(
+            entry("root", 3, "顾客", "cstm"),
+            keys(&[("顾客", RANK_CN_NAME), ("客户", RANK_SYNONYM), ("cstm", RANK_EN_NAME), ("guke", RANK_PINYIN), ("gk", RANK_PINYIN)]),
+        );
+        index.finish()
+    }
+
+    fn ids(items: &[TypeaheadItem]) -> Vec<i32> {
+        items.iter().map(|i| i.id).collect()
+    }
+
+    #[test]
+    fn ranks_cn_name_before_synonym_then_shorter_names() {
+        let items = sample().search("客户", 10);
+        assert_eq!(ids(&items), vec![2, 1, 3]);
+        assert_eq!(items.iter().map(|i| i.rank).collect::<Vec<_>>(), vec![RANK_CN_NAME, RANK_CN_NAME, RANK_SYNONYM]);
+    }
+
+    #[test]
+    fn keeps_best_rank_per_entry() {
+        // cust / customer / cust_name 均命中，每个条目只返回一次
+        let items = sample().search("CUST", 10);
+        assert_eq!(ids(&items), vec![2, 1]);
+        assert!(items.iter().all(|i| i.rank == RANK_EN_NAME));
+        assert_eq!(items[0].matched, "cust");
+    }
+
+    #[test]
+    fn matches_pinyin_and_initials() {
+        assert_eq!(ids(&sample().search("kh", 10)), vec![2, 1]);
+        assert_eq!(ids(&sample().search("gu", 10)), vec![3]);
+        assert_eq!(sample().search("kehum", 10)[0].rank, RANK_PINYIN);
+    }
+
+    #[test]
+    fn empty_prefix_and_limit() {
+        assert!(sample().search("  ", 10).is_empty());
+        assert!(sample().search("zzz", 10).is_empty());
+        assert_eq!(ids(&sample().search("客户", 1)), vec![2]);
+    }
+}
@@ -0,0 +1,334 @@
+use hmac::{Hmac, Mac};
+use once_cell::sync::Lazy;
+use serde::Serialize;
+use sha2::Sha256;
+use sqlx::PgPool;
+use std::net::IpAddr;
+use std::time::Duration;
+use tokio::sync::Notify;
+
+/// 支持订阅的事件类型
+pub const EVENT_TYPES: [&str; 12] = [
+    "root.created", "root.updated", "root.deprecated", "root.deleted", "root.merged",
+    "field.created", "field.updated", "field.deprecated", "field.deleted",
+    "task.approved", "task.rejected", "task.duplicate",
+];
+
+// 新投递入队后唤醒本实例的投递线程，其他实例靠轮询兜底
+static WAKE: Lazy<Notify> = Lazy::new(Notify::new);
+
+// 不跟随重定向：目标地址只在投递前校验，重定向可能指向内网
+static CLIENT: Lazy<reqwest::Client> = Lazy::new(|| {
+    reqwest::Client::builder()
+        .timeout(Duration::from_secs(timeout_secs()))
+        .redirect(reqwest::redirect::Policy::none())
+        .build()
+        .expect("Webhook HTTP 客户端初始化失败")
+});
+
+// 每轮领取的投递数；领取后租约内其他实例不会重复领取
+const BATCH_SIZE: i64 = 20;
+const POLL_INTERVAL: Duration = Duration::from_secs(5);
+
+fn env_u64(key: &str, default: u64) -> u64 {
+    std::env::var(key).ok().and_then(|v| v.parse().ok()).unwrap_or(default)
+}
+
+fn timeout_secs() -> u64 {
+    env_u64("WEBHOOK_TIMEOUT_SECS", 10)
+}
+
+/// 租约时长：一批按顺序投递，需覆盖整批全部超时的情况，另留 30 秒余量
+fn lease_secs() -> f64 {
+    (BATCH_SIZE as u64 * timeout_secs() + 30) as f64
+}
+
+/// 是否为公网地址：回环、私有、链路本地、CGNAT、组播等地址不允许作为回调目标
+fn is_public_ip(ip: IpAddr) -> bool {
+    match ip {
+        IpAddr::V4(v4) => {
+            let [a, b, ..] = v4.octets();
+            !(v4.is_loopback()
+                || v4.is_private()
+                || v4.is_link_local()
+                || v4.is_unspecified()
+                || v4.is_broadcast()
+                || v4.is_multicast()
+                || a == 0
+                || (a == 100 && (64..128).contains(&b)))
+        }
+        IpAddr::V6(v6) => {
+            if let Some(v4) = v6.to_ipv4_mapped() {
+                return is_public_ip(IpAddr::V4(v4));
+            }
+            let first = v6.segments()[0];
+            !(v6.is_loopback()
+                || v6.is_unspecified()
+                || v6.is_multicast()
+                || (first & 0xfe00) == 0xfc00  // 唯一本地地址 fc00::/7
+                || (first & 0xffc0) == 0xfe80) // 链路本地地址 fe80::/10
+        }
+    }
+}
+
+/// 校验回调地址：须为 http(s)，且解析出的地址均为公网地址
+/// 内网部署需要回调内网服务时可设置 WEBHOOK_ALLOW_PRIVATE=true 关闭地址检查
+pub async fn check_target(url: &str) -> Result<(), String> {
+    let parsed = reqwest::Url::parse(url).map_err(|e| format!("回调地址无效: {}", e))?;
+    if !matches!(parsed.scheme(), "http" | "https") {
+        return Err("回调地址必须以 http:// 或 https:// 开头".to_string());
+    }
+    if std::env::var("WEBHOOK_ALLOW_PRIVATE").is_ok_and(|v| v == "true") {
+        return Ok(());
+    }
+    let host = parsed.host_str().ok_or("回调地址缺少主机名")?;
+    let port = parsed.port_or_known_default().unwrap_or(80);
+    let addrs: Vec<IpAddr> = tokio::net::lookup_host((host.trim_matches(|c| c == '[' || c == ']'), port))
+        .await
+        .map_err(|e| format!("回调地址 [{}] 无法解析: {}", host, e))?
+        .map(|a| a.ip())
+        .collect();
+    match addrs.iter().find(|ip| !is_public_ip(**ip)) {
+        Some(ip) => Err(format!("回调地址 [{}] 指向内网或保留地址 {}，不允许投递", host, ip)),
+        None if addrs.is_empty() => Err(format!("回调地址 [{}] 无法解析", host)),
+        None => Ok(()),
+    }
+}
+
+/// 投递请求体
+#[derive(Serialize)]
+struct Envelope<'a> {
+    delivery_id: i64,
+    event: &'a str,
+    occurred_at: chrono::DateTime<chrono::Utc>,
+    data: &'a serde_json::Value,
+}
+
+/// 计算签名：HMAC-SHA256(secret, "{timestamp}.{body}")，十六进制输出
+pub fn sign(secret: &str, timestamp: i64, body: &str) -> String {
+    let mut mac = Hmac::<Sha256>::new_from_slice(secret.as_bytes()).expect("HMAC 支持任意长度密钥");
+    mac.update(timestamp.to_string().as_bytes());
+    mac.update(b".");
+    mac.update(body.as_bytes());
+    hex::encode(mac.finalize().into_bytes())
+}
+
+/// 第 n 次失败后的重试间隔：首次间隔按 2 的幂递增，最长 1 小时
+fn backoff(attempts: i32) -> Duration {
+    let base = env_u64("WEBHOOK_RETRY_BASE_SECS", 30);
+    let factor = 1u64 << (attempts.clamp(1, 16) - 1) as u32;
+    Duration::from_secs((base * factor).min(3600))
+}
+
+/// 唤醒本实例的投递线程
+pub fn wake() {
+    WAKE.notify_one();
+}
+
+/// 为订阅了该事件的启用中订阅写入待投递记录，返回写入数
+pub async fn enqueue(pool: &PgPool, event_type: &str, data: &serde_json::Value) -> Result<u64, sqlx::Error> {
+    let res = sqlx::query!(
+        r#"INSERT INTO webhook_deliveries (subscription_id, event_type, payload)
+           SELECT id, $1::VARCHAR, $2 FROM webhook_subscriptions
+           WHERE is_active AND (cardinality(events) = 0 OR $1::TEXT = ANY(events))"#,
+        event_type, data
+    )
+    .execute(pool)
+    .await?;
+    if res.rows_affected() > 0 {
+        wake();
+    }
+    Ok(res.rows_affected())
+}
+
+/// 业务处理完成后调用：写入投递队列，失败只记录日志，不影响业务结果
+pub async fn emit<T: Serialize>(pool: &PgPool, event_type: &str, data: &T) {
+    let data = serde_json::to_value(data).unwrap_or_default();
+    if let Err(e) = enqueue(pool, event_type, &data).await {
+        tracing::error!("!!! Webhook 事件 [{}] 入队失败: {}", event_type, e);
+    }
+}
+
+/// 重放一条投递：复制原请求体生成新的投递记录，原记录保留
+pub async fn replay(pool: &PgPool, delivery_id: i64) -> Result<Option<i64>, sqlx::Error> {
+    let id = sqlx::query_scalar!(
+        r#"INSERT INTO webhook_deliveries (subscription_id, event_type, payload, replay_of)
+           SELECT subscription_id, event_type, payload, id FROM webhook_deliveries WHERE id = $1
+           RETURNING id"#,
+        delivery_id
+    )
+    .fetch_optional(pool)
+    .await?;
+    if id.is_some() {
+        wake();
+    }
+    Ok(id)
+}
+
+struct Due {
+    id: i64,
+    url: String,
+    secret: String,
+    event_type: String,
+    payload: serde_json::Value,
+    attempts: i32,
+    created_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+/// 领取到期的投递并设置租约：SKIP LOCKED 保证多实例不会同时领取同一条
+async fn claim(pool: &PgPool) -> Result<Vec<Due>, sqlx::Error> {
+    let rows = sqlx::query!(
+        r#"WITH due AS (
+               SELECT d.id FROM webhook_deliveries d
+               JOIN webhook_subscriptions s ON s.id = d.subscription_id AND s.is_active
+               WHERE d.status IN ('pending', 'failed') AND d.next_attempt_at <= CURRENT_TIMESTAMP
+               ORDER BY d.next_attempt_at
+               LIMIT $1
+               FOR UPDATE OF d SKIP LOCKED
+           )
+           UPDATE webhook_deliveries d
+           SET next_attempt_at = CURRENT_TIMESTAMP + make_interval(secs => $2)
+           FROM due, webhook_subscriptions s
+           WHERE d.id = due.id AND s.id = d.subscription_id
+           RETURNING d.id, s.url, s.secret, d.event_type, d.payload, d.attempts, d.created_at"#,
+        BATCH_SIZE, lease_secs()
+    )
+    .fetch_all(pool)
+    .await?;
+    Ok(rows
+        .into_iter()
+        .map(|r| Due {
+            id: r.id,
+            url: r.url,
+            secret: r.secret,
+            event_type: r.event_type,
+            payload: r.payload,
+            attempts: r.attempts,
+            created_at: r.created_at,
+        })
+        .collect())
+}
+
+/// 发送一次投递，返回 (HTTP 状态码, 错误信息)；2xx 视为成功
+async fn send(due: &Due) -> (Option<i32>, Option<String>) {
+    let body = serde_json::to_string(&Envelope {
+        delivery_id: due.id,
+        event: &due.event_type,
+        occurred_at: due.created_at.unwrap_or_else(chrono::Utc::now),
+        data: &due.payload,
+    })
+    .unwrap_or_default();
+    let timestamp = chrono::Utc::now().timestamp();
+
+    let res = CLIENT
+        .post(&due.url)
+        .header("Content-Type", "application/json")
+        .header("X-Webhook-Event", &due.event_type)
+        .header("X-Webhook-Delivery", due.id.to_string())
+        .header("X-Webhook-Timestamp", timestamp.to_string())
+        .header("X-Webhook-Signature", format!("sha256={}", sign(&due.secret, timestamp, &body)))
+        .body(body)
+        .send()
+        .await;
+
+    match res {
+        Ok(r) if r.status().is_success() => (Some(r.status().as_u16() as i32), None),
+        Ok(r) => {
+            let code = r.status().as_u16() as i32;
+            let text: String = r.text().await.unwrap_or_default().chars().take(500).collect();
+            (Some(code), Some(format!("HTTP {}: {}", code, text)))
+        }
+        Err(e) => (None, Some(e.to_string())),
+    }
+}
+
+async fn deliver(pool: &PgPool, due: Due) {
+    // 投递前重新校验地址，防止域名在创建订阅后被解析到内网
+    let (code, error) = match check_target(&due.url).await {
+        Ok(()) => send(&due).await,
+        Err(msg) => (None, Some(msg)),
+    };
+    let attempts = due.attempts + 1;
+    let max_attempts = env_u64("WEBHOOK_MAX_ATTEMPTS", 8) as i32;
+    let (status, retry_in) = match &error {
+        None => ("success", 0.0),
+        Some(_) if attempts >= max_attempts => ("dead", 0.0),
+        Some(_) => ("failed", backoff(attempts).as_secs_f64()),
+    };
+    match &error {
+        None => tracing::info!("<<< Webhook 投递成功: ID={}, event={}", due.id, due.event_type),
+        Some(e) => tracing::warn!("--- Webhook 投递失败: ID={}, 第 {} 次, 状态={}, 错误: {}", due.id, attempts, status, e),
+    }
+
+    let res = sqlx::query!(
+        r#"UPDATE webhook_deliveries
+           SET status = $1::VARCHAR, attempts = $2, last_status_code = $3, last_error = $4,
+               next_attempt_at = CURRENT_TIMESTAMP + make_interval(secs => $5),
+               delivered_at = CASE WHEN $1::TEXT = 'success' THEN CURRENT_TIMESTAMP ELSE delivered_at END
+           WHERE id = $6"#,
+        status, attempts, code, error, retry_in, due.id
+    )
+    .execute(pool)
+    .await;
+    if let Err(e) = res {
+        tracing::error!("!!! Webhook 投递结果写入失败: ID={}, {}", due.id, e);
+    }
+}
+
+/// 后台投递线程：有新投递时立即处理，否则定期轮询到期的重试
+pub fn spawn_worker(pool: PgPool) {
+    tokio::spawn(async move {
+        tracing::info!("Webhook 投递线程已启动");
+        loop {
+            match claim(&pool).await {
+                Ok(batch) if !batch.is_empty() => {
+                    let full = batch.len() as i64 == BATCH_SIZE;
+                    for due in batch {
+                        deliver(&pool, due).await;
+                    }
+                    // 整批领满说明还有积压，继续领取
+                    if full {
+                        continue;
+                    }
+                }
+                Ok(_) => {}
+                Err(e) => tracing::error!("!!! 领取 Webhook 投递失败: {}", e),
+            }
+            tokio::select! {
+                _ = WAKE.notified() => {}
+                _ = tokio::time::sleep(POLL_INTERVAL) => {}
+            }
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn public(ip: &str) -> bool {
+        is_public_ip(ip.parse().unwrap())
+    }
+
+    #[test]
+    fn rejects_loopback_private_and_link_local() {
+        for ip in ["127.0.0.1", "10.1.2.3", "172.16.0.1", "192.168.1.1", "169.254.169.254", "0.0.0.0", "100.64.0.1"] {
+            assert!(!public(ip), "{}", ip);
+        }
+        for ip in ["::1", "::", "fd00::1", "fe80::1", "::ffff:127.0.0.1", "::ffff:10.0.0.1"] {
+            assert!(!public(ip), "{}", ip);
+        }
+    }
+
+    #[test]
+    fn accepts_public_addresses() {
+        for ip in ["8.8.8.8", "1.1.1.1", "100.128.0.1", "2606:4700::1111", "::ffff:8.8.8.8"] {
+            assert!(public(ip), "{}", ip);
+        }
+    }
+
+    #[test]
+    fn lease_covers_a_full_batch_of_timeouts() {
+        assert!(lease_secs() >= (BATCH_SIZE as u64 * timeout_secs()) as f64);
+    }
+}